@@ -0,0 +1,40 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records the git commit being built, reported by `GET /about`. Builds
+//! outside a git checkout, e.g. from a source archive, report none.
+
+#![allow(clippy::print_stdout)]
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    println!("cargo:rerun-if-env-changed=DRASI_SERVER_GIT_COMMIT");
+
+    // An explicit value, e.g. from a Docker build without .git, wins
+    if std::env::var_os("DRASI_SERVER_GIT_COMMIT").is_some() {
+        return;
+    }
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    if let Some(commit) = commit {
+        println!("cargo:rustc-env=DRASI_SERVER_GIT_COMMIT={commit}");
+    }
+}
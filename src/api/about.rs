@@ -0,0 +1,187 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! What a running server is and what it supports, for `GET /about` and the
+//! startup banner.
+//!
+//! Everything reported is fixed when the server is created, so fleet tooling
+//! can compare servers without looking at their config files.
+
+use axum::extract::Extension;
+use axum::response::Json;
+use serde::Serialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use super::handlers::ApiResponse;
+use crate::config::{ReactionConfig, SourceConfig};
+
+/// Server settings that change what the server does
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct AboutFeatures {
+    /// Query indexes are kept in RocksDB and survive restarts
+    pub persist_index: bool,
+    /// The config file is the desired state; API changes are reverted
+    pub reconcile: bool,
+    /// The config file is not writable, so the API cannot change components
+    pub read_only: bool,
+    /// Queries whose joins have problems are rejected
+    pub strict_joins: bool,
+    /// Sources and reactions that fail to build do not stop the server
+    pub tolerate_component_errors: bool,
+    /// The CPU profiling endpoint is compiled in
+    pub pprof: bool,
+    /// Requests that change the server need an API key
+    pub api_keys: bool,
+    /// Bearer JWTs from the configured issuer are accepted
+    pub jwt: bool,
+    /// The API is served over HTTPS
+    pub tls: bool,
+    /// Clients are asked for a certificate (mutual TLS)
+    pub client_cert: bool,
+}
+
+impl AboutFeatures {
+    /// Names of the enabled features, e.g. for the startup banner
+    pub fn enabled(&self) -> Vec<&'static str> {
+        [
+            (self.persist_index, "persist_index"),
+            (self.reconcile, "reconcile"),
+            (self.read_only, "read_only"),
+            (self.strict_joins, "strict_joins"),
+            (self.tolerate_component_errors, "tolerate_component_errors"),
+            (self.pprof, "pprof"),
+            (self.api_keys, "api_keys"),
+            (self.jwt, "jwt"),
+            (self.tls, "tls"),
+            (self.client_cert, "client_cert"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect()
+    }
+}
+
+/// Configured capacities and thread counts; `null` where the built-in
+/// default applies
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct AboutLimits {
+    pub default_priority_queue_capacity: Option<usize>,
+    pub default_dispatch_buffer_capacity: Option<usize>,
+    pub worker_threads: Option<usize>,
+    pub api_worker_threads: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AboutResponse {
+    pub version: String,
+    /// Commit the binary was built from, if it was built from a git checkout
+    pub git_commit: Option<String>,
+    pub features: AboutFeatures,
+    /// Values accepted in a source's `kind` field
+    pub source_kinds: Vec<String>,
+    /// Values accepted in a reaction's `kind` field
+    pub reaction_kinds: Vec<String>,
+    pub limits: AboutLimits,
+}
+
+impl AboutResponse {
+    pub fn new(features: AboutFeatures, limits: AboutLimits) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: option_env!("DRASI_SERVER_GIT_COMMIT")
+                .filter(|commit| !commit.is_empty())
+                .map(str::to_string),
+            features,
+            source_kinds: SourceConfig::KINDS.iter().map(|k| k.to_string()).collect(),
+            reaction_kinds: ReactionConfig::KINDS
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            limits,
+        }
+    }
+
+    /// One-line summary logged at startup
+    pub fn banner(&self) -> String {
+        let commit = self.git_commit.as_deref().unwrap_or("unknown commit");
+        let features = self.features.enabled();
+        let features = if features.is_empty() {
+            "none".to_string()
+        } else {
+            features.join(", ")
+        };
+        format!(
+            "Drasi Server {} ({commit}); features: {features}",
+            self.version
+        )
+    }
+}
+
+/// Describe the server's version and capabilities
+///
+/// Reports the version and build commit, the settings that change server
+/// behavior, the source and reaction kinds this build accepts, and the
+/// configured capacities.
+#[utoipa::path(
+    get,
+    path = "/about",
+    responses(
+        (status = 200, description = "Server version and capabilities", body = ApiResponse),
+    ),
+    tag = "Health"
+)]
+pub async fn about(
+    Extension(about): Extension<Arc<AboutResponse>>,
+) -> Json<ApiResponse<AboutResponse>> {
+    Json(ApiResponse::success(about.as_ref().clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reported_kinds_are_accepted_in_config() {
+        for kind in SourceConfig::KINDS {
+            let yaml = format!("kind: {kind}\nid: s1\n");
+            if let Err(e) = serde_yaml::from_str::<SourceConfig>(&yaml) {
+                assert!(!e.to_string().contains("unknown variant"), "{kind}: {e}");
+            }
+        }
+        for kind in ReactionConfig::KINDS {
+            let yaml = format!("kind: {kind}\nid: r1\nqueries: [q1]\n");
+            if let Err(e) = serde_yaml::from_str::<ReactionConfig>(&yaml) {
+                assert!(!e.to_string().contains("unknown variant"), "{kind}: {e}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_banner_lists_enabled_features() {
+        let about = AboutResponse::new(
+            AboutFeatures {
+                persist_index: true,
+                strict_joins: true,
+                ..Default::default()
+            },
+            AboutLimits::default(),
+        );
+
+        let banner = about.banner();
+
+        assert!(banner.starts_with(&format!("Drasi Server {}", env!("CARGO_PKG_VERSION"))));
+        assert!(banner.ends_with("features: persist_index, strict_joins"));
+    }
+}
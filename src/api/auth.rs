@@ -0,0 +1,309 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API key authentication for the management API.
+//!
+//! With `api_keys` set in the config file, every request that can change the
+//! server (anything but `GET`, `HEAD` and `OPTIONS`) must carry one of the
+//! keys as `Authorization: Bearer <key>`. Reads stay open, and so does
+//! `/health`, so probes and dashboards need no credentials. With `jwt` set, a
+//! JWT issued by the configured OIDC provider is accepted as well (see
+//! [`super::jwt`]). With neither the API is open, as before.
+//!
+//! The CPU profiling endpoint always needs credentials, even though it is
+//! read with `GET`.
+//!
+//! With `docs.require_auth`, the Swagger UI and the OpenAPI document need
+//! credentials too. Browsers cannot send a bearer token when opening
+//! `/docs`, so those paths also accept HTTP Basic credentials with the key
+//! or token as the password, and ask the browser for them.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::Engine;
+use std::fmt;
+use std::sync::Arc;
+
+use super::error::{ApiError, ErrorCode};
+use super::jwt::JwtValidator;
+
+/// Paths that never require a key
+const OPEN_PATHS: [&str; 1] = ["/health"];
+
+/// CPU profiling endpoint (`pprof` feature); reading it needs a key too
+pub const PROFILE_PATH: &str = "/debug/pprof/profile";
+
+/// Realm browsers show when asking for credentials to open the docs
+const DOCS_REALM: &str = "Basic realm=\"Drasi Server API docs\"";
+
+/// Resolved API keys; `Debug` does not print them.
+#[derive(Clone, Default)]
+pub struct ApiKeys(Arc<Vec<String>>);
+
+impl ApiKeys {
+    pub fn new(keys: Vec<String>) -> Self {
+        Self(Arc::new(keys))
+    }
+
+    /// No keys are configured, so the API is open
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn accepts(&self, token: &str) -> bool {
+        // Check every key so the time taken does not tell which one matched
+        self.0
+            .iter()
+            .fold(false, |found, key| constant_time_eq(key, token) | found)
+    }
+}
+
+impl fmt::Debug for ApiKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ApiKeys({} key(s))", self.0.len())
+    }
+}
+
+/// Credentials a request changing the server may present
+#[derive(Clone, Default)]
+pub struct Authenticator {
+    keys: ApiKeys,
+    jwt: Option<Arc<JwtValidator>>,
+    /// Reads of the Swagger UI and OpenAPI document need credentials too
+    protect_docs: bool,
+}
+
+impl Authenticator {
+    pub fn new(keys: ApiKeys, jwt: Option<JwtValidator>) -> Self {
+        Self {
+            keys,
+            jwt: jwt.map(Arc::new),
+            protect_docs: false,
+        }
+    }
+
+    pub fn with_protected_docs(mut self, protect_docs: bool) -> Self {
+        self.protect_docs = protect_docs;
+        self
+    }
+
+    /// Neither keys nor JWT validation are configured, so the API is open
+    pub fn is_open(&self) -> bool {
+        self.keys.is_empty() && self.jwt.is_none()
+    }
+
+    /// Why `token` is not accepted, or `None` if it is
+    async fn rejection(&self, token: &str) -> Option<String> {
+        if self.keys.accepts(token) {
+            return None;
+        }
+        match &self.jwt {
+            Some(jwt) => jwt.validate(token).await.err(),
+            None => Some("invalid API key".to_string()),
+        }
+    }
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+fn is_docs_path(path: &str) -> bool {
+    path == "/docs" || path.starts_with("/docs/") || path.starts_with("/api-docs/")
+}
+
+fn requires_key(method: &Method, path: &str, protect_docs: bool) -> bool {
+    if path == PROFILE_PATH || (protect_docs && is_docs_path(path)) {
+        return true;
+    }
+    let read = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+    !read && !OPEN_PATHS.contains(&path)
+}
+
+/// The token in a bearer `Authorization` header, or with `basic`, the
+/// password in a Basic one
+fn credential(value: Option<&HeaderValue>, basic: bool) -> Option<String> {
+    let value = value?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    if scheme.eq_ignore_ascii_case("bearer") {
+        return Some(token.trim().to_string());
+    }
+    if !basic || !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(token.trim())
+        .ok()?;
+    let (_user, password) = String::from_utf8(decoded).ok()?.split_once(':')?;
+    Some(password.to_string())
+}
+
+/// Middleware that rejects requests changing the server without valid
+/// credentials.
+pub async fn auth_middleware(
+    State(auth): State<Authenticator>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if auth.is_open() || !requires_key(request.method(), path, auth.protect_docs) {
+        return next.run(request).await;
+    }
+
+    let docs = is_docs_path(path);
+    let rejection = match credential(request.headers().get(header::AUTHORIZATION), docs) {
+        Some(token) => auth.rejection(&token).await,
+        None => Some("missing credentials".to_string()),
+    };
+    let Some(reason) = rejection else {
+        return next.run(request).await;
+    };
+
+    log::info!(
+        "Rejecting {} {}: {reason}",
+        request.method(),
+        request.uri().path()
+    );
+    let mut response = ApiError::new(
+        ErrorCode::Unauthorized,
+        "A valid API key or token is required: send it as 'Authorization: Bearer <token>'",
+    )
+    .into_response();
+    let challenge = if docs { DOCS_REALM } else { "Bearer" };
+    response.headers_mut().insert(
+        header::WWW_AUTHENTICATE,
+        HeaderValue::from_static(challenge),
+    );
+    response
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::StatusCode,
+        middleware,
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    fn router() -> Router {
+        Router::new()
+            .route("/health", post(|| async { "ok" }))
+            .route(
+                "/sources",
+                get(|| async { "list" }).post(|| async { "created" }),
+            )
+            .route(PROFILE_PATH, get(|| async { "profile" }))
+            .layer(middleware::from_fn_with_state(
+                Authenticator::new(ApiKeys::new(vec!["secret-key".to_string()]), None),
+                auth_middleware,
+            ))
+    }
+
+    async fn status(method: &str, uri: &str, authorization: Option<&str>) -> StatusCode {
+        let mut request = axum::http::Request::builder().method(method).uri(uri);
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+        let response = router()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        response.status()
+    }
+
+    #[tokio::test]
+    async fn test_changes_require_a_valid_key() {
+        assert_eq!(
+            status("POST", "/sources", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status("POST", "/sources", Some("Bearer wrong-key")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status("POST", "/sources", Some("Bearer secret-key")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reads_and_health_stay_open() {
+        assert_eq!(status("GET", "/sources", None).await, StatusCode::OK);
+        assert_eq!(status("POST", "/health", None).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_profiling_requires_a_key() {
+        assert_eq!(
+            status("GET", PROFILE_PATH, None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status("GET", PROFILE_PATH, Some("Bearer secret-key")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_protected_docs_accept_basic_credentials() {
+        let app = Router::new()
+            .route("/docs/", get(|| async { "docs" }))
+            .layer(middleware::from_fn_with_state(
+                Authenticator::new(ApiKeys::new(vec!["secret-key".to_string()]), None)
+                    .with_protected_docs(true),
+                auth_middleware,
+            ));
+        let request = |authorization: Option<&str>| {
+            let mut request = axum::http::Request::builder().uri("/docs/");
+            if let Some(value) = authorization {
+                request = request.header(header::AUTHORIZATION, value);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], DOCS_REALM);
+
+        // "admin:secret-key"
+        let basic = "Basic YWRtaW46c2VjcmV0LWtleQ==";
+        let response = app.clone().oneshot(request(Some(basic))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_debug_does_not_print_keys() {
+        let keys = ApiKeys::new(vec!["secret-key".to_string()]);
+
+        assert_eq!(format!("{keys:?}"), "ApiKeys(1 key(s))");
+    }
+}
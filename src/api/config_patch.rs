@@ -0,0 +1,465 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `PATCH /config`: merge a configuration fragment into the running server.
+//!
+//! The fragment uses the same shape as the config file but only carries what
+//! should change. Components in it are created, or replaced when a component
+//! with the same ID already exists; components not mentioned are left alone.
+//! Replacing a source or query restarts what depends on it, as the `PUT`
+//! endpoints do. Every component is built before anything is changed, so a
+//! fragment with an invalid definition is rejected as a whole, and if applying
+//! it fails part-way the changes already made are rolled back.
+
+use axum::{extract::Extension, response::Json};
+use drasi_lib::{
+    channels::ComponentStatus,
+    plugin_core::{Reaction, Source},
+    QueryConfig,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use super::error::ApiError;
+use super::handlers::{
+    persist_mutations, replace_query_instance, replace_source_instance, ApiResponse,
+};
+use super::joins::{validate_joins, StrictJoins};
+use crate::config::{ReactionConfig, SourceConfig};
+use crate::factories::{create_reaction, create_source};
+use crate::journal::Mutation;
+use crate::persistence::ConfigPersistence;
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+/// A partial server configuration
+///
+/// Server settings that need a restart (`host`, `port`, ...) are not accepted.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigPatch {
+    /// New log level: trace, debug, info, warn or error
+    #[serde(default)]
+    pub log_level: Option<String>,
+    /// Sources to create or replace
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub sources: Vec<SourceConfig>,
+    /// Queries to create or replace
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub queries: Vec<QueryConfig>,
+    /// Reactions to create or replace
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub reactions: Vec<ReactionConfig>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConfigPatchResult {
+    /// One entry per applied change, e.g. `replaced source 'orders-db'`
+    pub changes: Vec<String>,
+}
+
+/// A validated fragment with every component instance already built
+struct PreparedPatch {
+    log_level: Option<(String, log::LevelFilter)>,
+    sources: Vec<(SourceConfig, Box<dyn Source + 'static>)>,
+    queries: Vec<QueryConfig>,
+    reactions: Vec<(ReactionConfig, Box<dyn Reaction + 'static>)>,
+}
+
+/// How to put back a component the fragment changed
+enum Undo {
+    /// Created by the fragment
+    Remove(ComponentKind, String),
+    /// Replaced; `None` when the previous source had no stored definition
+    Source(String, Option<SourceConfig>),
+    /// Replaced a source that had failed to build
+    FailedSource(SourceConfig, String),
+    Query(QueryConfig),
+    Reaction {
+        id: String,
+        previous: Option<ReactionConfig>,
+        was_running: bool,
+    },
+    /// Replaced a reaction that had failed to build
+    FailedReaction(ReactionConfig, String),
+}
+
+fn verb(replaced: bool) -> &'static str {
+    if replaced {
+        "replaced"
+    } else {
+        "created"
+    }
+}
+
+/// Merge a partial configuration into the running server
+#[utoipa::path(
+    patch,
+    path = "/config",
+    request_body = ConfigPatch,
+    responses(
+        (status = 200, description = "Configuration fragment applied", body = ApiResponse),
+        (status = 400, description = "Invalid fragment, or a change failed and the fragment was rolled back; nothing was changed", body = ApiResponse),
+        (status = 409, description = "Server is read-only", body = ApiResponse),
+    ),
+    tag = "Config"
+)]
+pub async fn patch_config(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Extension(StrictJoins(strict_joins)): Extension<StrictJoins>,
+    Json(patch): Json<ConfigPatch>,
+) -> Result<Json<ApiResponse<ConfigPatchResult>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("change configuration"));
+    }
+
+    // Validate and build everything up front so a bad fragment changes nothing
+    let log_level = match &patch.log_level {
+        Some(level) => match log::LevelFilter::from_str(level) {
+            Ok(filter) if filter != log::LevelFilter::Off => Some((level.clone(), filter)),
+            _ => {
+                return Err(ApiError::validation(format!(
+                    "Invalid log level '{level}': must be one of trace, debug, info, warn, error"
+                )));
+            }
+        },
+        None => None,
+    };
+
+    for query in &patch.queries {
+        if let Err(e) = crate::config::types::validate_query_capacities(query)
+            .and_then(|_| crate::config::types::validate_query_language(query))
+        {
+            return Err(ApiError::validation(e.to_string()));
+        }
+        let filter_errors = crate::config::types::validate_subscription_labels(query);
+        if !filter_errors.is_empty() {
+            return Err(ApiError::validation(format!(
+                "Invalid source subscription filters in query '{}': {}",
+                query.id,
+                filter_errors.join("; ")
+            )));
+        }
+        if strict_joins {
+            let joins = query.joins.as_deref().unwrap_or_default();
+            let issues = validate_joins(&query.query, &query.query_language, joins);
+            if !issues.is_empty() {
+                let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+                return Err(ApiError::validation(format!(
+                    "Invalid joins in query '{}': {}",
+                    query.id,
+                    messages.join("; ")
+                )));
+            }
+        }
+    }
+
+    if patch.reactions.iter().any(|r| r.group().is_some()) {
+        let mut reactions = registry.reaction_configs().await;
+        reactions.retain(|r| !patch.reactions.iter().any(|p| p.id() == r.id()));
+        reactions.extend(patch.reactions.iter().cloned());
+        if let Err(e) = crate::config::types::validate_reaction_groups(&reactions) {
+            return Err(ApiError::validation(e.to_string()));
+        }
+    }
+
+    let mut sources = Vec::new();
+    for config in patch.sources {
+        // Started in apply, like a replaced source
+        let mut instance_config = config.clone();
+        instance_config.set_auto_start(false);
+        match create_source(instance_config).await {
+            Ok(instance) => sources.push((config, instance)),
+            Err(e) => {
+                let context = format!("Failed to create source '{}'", config.id());
+                return Err(ApiError::from(e).with_context(context));
+            }
+        }
+    }
+
+    let mut reactions = Vec::new();
+    for config in patch.reactions {
+        let disabled = registry.is_disabled(ComponentKind::Reaction, config.id()).await;
+        let mut instance_config = config.clone();
+        if disabled || config.wait_for_queries_ms().is_some() || config.group().is_some() {
+            instance_config.set_auto_start(false);
+        }
+        match create_reaction(instance_config) {
+            Ok(instance) => reactions.push((config, instance)),
+            Err(e) => {
+                let context = format!("Failed to create reaction '{}'", config.id());
+                return Err(ApiError::from(e).with_context(context));
+            }
+        }
+    }
+
+    let prepared = PreparedPatch {
+        log_level,
+        sources,
+        queries: patch.queries,
+        reactions,
+    };
+
+    let mut changes = Vec::new();
+    let mut mutations = Vec::new();
+    let mut undo = Vec::new();
+    let result = apply(
+        &core,
+        &registry,
+        &config_persistence,
+        prepared,
+        &mut changes,
+        &mut mutations,
+        &mut undo,
+    )
+    .await;
+
+    match result {
+        Ok(()) => {
+            if !mutations.is_empty() {
+                persist_mutations(&config_persistence, &mutations, "patching configuration").await;
+            }
+            Ok(Json(ApiResponse::success(ConfigPatchResult { changes })))
+        }
+        Err(error) => {
+            log::error!("Failed to apply configuration patch, rolling back: {error}");
+            rollback(&core, &registry, undo).await;
+            Err(ApiError::new(
+                error.code,
+                format!("{error} (rolled back: nothing was changed)"),
+            ))
+        }
+    }
+}
+
+/// Apply a prepared fragment, recording each change as it succeeds and how
+/// to undo it.
+async fn apply(
+    core: &Arc<drasi_lib::DrasiLib>,
+    registry: &Arc<ComponentRegistry>,
+    config_persistence: &Option<Arc<ConfigPersistence>>,
+    prepared: PreparedPatch,
+    changes: &mut Vec<String>,
+    mutations: &mut Vec<Mutation>,
+    undo: &mut Vec<Undo>,
+) -> Result<(), ApiError> {
+    for (config, instance) in prepared.sources {
+        let id = config.id().to_string();
+        let disabled = registry.is_disabled(ComponentKind::Source, &id).await;
+        let build_error = registry.build_error(ComponentKind::Source, &id).await;
+        let replaced = build_error.is_some() || core.get_source_status(&id).await.is_ok();
+        mutations.push(Mutation::PutSource {
+            config: config.clone(),
+        });
+        if replaced {
+            let previous = registry.get_source(&id).await;
+            replace_source_instance(core, registry, config, instance).await?;
+            undo.push(match (build_error, previous) {
+                (Some(error), Some(previous)) => Undo::FailedSource(previous, error),
+                (_, previous) => Undo::Source(id.clone(), previous),
+            });
+        } else {
+            core.add_source(instance).await?;
+            undo.push(Undo::Remove(ComponentKind::Source, id.clone()));
+            let auto_start = config.auto_start() && !config.lazy();
+            registry.insert_source(config).await;
+            registry.reset_running(ComponentKind::Source, &id).await;
+            if auto_start && !disabled {
+                core.start_source(&id).await?;
+                registry.mark_started(ComponentKind::Source, &id).await;
+            }
+        }
+        changes.push(format!("{} source '{id}'", verb(replaced)));
+    }
+
+    for mut config in prepared.queries {
+        let id = config.id.clone();
+        mutations.push(Mutation::PutQuery {
+            config: config.clone(),
+        });
+        let replaced = match core.get_query_config(&id).await {
+            Ok(mut previous) => {
+                // Restored with the configured value, not the one turned off
+                // because the query is disabled
+                if registry.is_query_auto_start_suppressed(&id).await {
+                    previous.auto_start = true;
+                }
+                replace_query_instance(core, registry, config).await?;
+                undo.push(Undo::Query(previous));
+                true
+            }
+            Err(_) => {
+                if registry.is_disabled(ComponentKind::Query, &id).await && config.auto_start {
+                    config.auto_start = false;
+                    registry.suppress_query_auto_start(&id).await;
+                }
+                core.add_query(config).await?;
+                undo.push(Undo::Remove(ComponentKind::Query, id.clone()));
+                registry.reset_running(ComponentKind::Query, &id).await;
+                false
+            }
+        };
+        changes.push(format!("{} query '{id}'", verb(replaced)));
+    }
+
+    for (config, instance) in prepared.reactions {
+        let id = config.id().to_string();
+        let disabled = registry.is_disabled(ComponentKind::Reaction, &id).await;
+        let build_error = registry.build_error(ComponentKind::Reaction, &id).await;
+        let status = core.get_reaction_status(&id).await;
+        let replaced = build_error.is_some() || status.is_ok();
+        let previous = registry.get_reaction(&id).await;
+        if status.is_ok() {
+            core.remove_reaction(&id).await?;
+        }
+        undo.push(match (build_error, previous) {
+            (Some(error), Some(previous)) => Undo::FailedReaction(previous, error),
+            (None, previous) if replaced => Undo::Reaction {
+                id: id.clone(),
+                previous,
+                was_running: matches!(
+                    status,
+                    Ok(ComponentStatus::Running | ComponentStatus::Starting)
+                ),
+            },
+            _ => Undo::Remove(ComponentKind::Reaction, id.clone()),
+        });
+        core.add_reaction(instance).await?;
+        // Group members are started by the group supervisor
+        let auto_start = config.auto_start() && config.group().is_none();
+        let wait_for_queries_ms = config.wait_for_queries_ms();
+        let queries = config.queries().to_vec();
+        mutations.push(Mutation::PutReaction {
+            config: config.clone(),
+        });
+        registry.insert_reaction(config).await;
+        registry.reset_running(ComponentKind::Reaction, &id).await;
+        if auto_start && !disabled {
+            match wait_for_queries_ms {
+                Some(wait_ms) => {
+                    crate::reaction_gate::spawn_gated_start(
+                        core.clone(),
+                        registry.clone(),
+                        id.clone(),
+                        queries,
+                        std::time::Duration::from_millis(wait_ms),
+                    );
+                }
+                None => {
+                    core.start_reaction(&id).await?;
+                    registry.mark_started(ComponentKind::Reaction, &id).await;
+                }
+            }
+        }
+        changes.push(format!("{} reaction '{id}'", verb(replaced)));
+    }
+
+    // Applied last: it cannot fail, so there is nothing to roll back
+    if let Some((level, filter)) = prepared.log_level {
+        // The logger's own filter is fixed at startup, so raising verbosity
+        // above it only takes full effect after a restart
+        log::set_max_level(filter);
+        if let Some(persistence) = config_persistence {
+            persistence.set_log_level(level.clone()).await;
+        }
+        changes.push(format!("set log_level to '{level}'"));
+        mutations.push(Mutation::SetLogLevel { level });
+    }
+
+    Ok(())
+}
+
+/// Undo the changes of a fragment that failed part-way, newest first.
+async fn rollback(core: &drasi_lib::DrasiLib, registry: &ComponentRegistry, undo: Vec<Undo>) {
+    for step in undo.into_iter().rev() {
+        let result = match step {
+            Undo::Remove(kind, id) => {
+                let removed = match kind {
+                    ComponentKind::Source => core.remove_source(&id).await,
+                    ComponentKind::Query => core.remove_query(&id).await,
+                    ComponentKind::Reaction => core.remove_reaction(&id).await,
+                };
+                registry.remove(kind, &id).await;
+                removed.map_err(ApiError::from)
+            }
+            Undo::Source(id, None) => Err(ApiError::internal(format!(
+                "Source '{id}' has no stored definition to restore"
+            ))),
+            Undo::Source(_, Some(previous)) => {
+                let mut instance_config = previous.clone();
+                instance_config.set_auto_start(false);
+                match create_source(instance_config).await {
+                    Ok(instance) => {
+                        replace_source_instance(core, registry, previous, instance).await
+                    }
+                    Err(e) => Err(ApiError::from(e)),
+                }
+            }
+            Undo::FailedSource(previous, error) => {
+                let removed = core.remove_source(previous.id()).await;
+                registry.insert_failed_source(previous, error).await;
+                removed.map_err(ApiError::from)
+            }
+            Undo::Query(previous) => replace_query_instance(core, registry, previous).await,
+            Undo::Reaction {
+                id,
+                previous,
+                was_running,
+            } => {
+                // The replacement may not have been added
+                let _ = core.remove_reaction(&id).await;
+                match previous {
+                    Some(previous) => restore_reaction(core, registry, previous, was_running).await,
+                    None => Err(ApiError::internal(format!(
+                        "Reaction '{id}' has no stored definition to restore"
+                    ))),
+                }
+            }
+            Undo::FailedReaction(previous, error) => {
+                let removed = core.remove_reaction(previous.id()).await;
+                registry.insert_failed_reaction(previous, error).await;
+                removed.map_err(ApiError::from)
+            }
+        };
+        if let Err(e) = result {
+            log::error!("Failed to roll back configuration patch: {e}");
+        }
+    }
+}
+
+async fn restore_reaction(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    config: ReactionConfig,
+    start: bool,
+) -> Result<(), ApiError> {
+    let id = config.id().to_string();
+    let mut instance_config = config.clone();
+    instance_config.set_auto_start(false);
+    core.add_reaction(create_reaction(instance_config)?).await?;
+    registry.insert_reaction(config).await;
+    registry.reset_running(ComponentKind::Reaction, &id).await;
+    if start {
+        core.start_reaction(&id).await?;
+        registry.mark_started(ComponentKind::Reaction, &id).await;
+    }
+    Ok(())
+}
@@ -12,159 +12,207 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Errors returned by the management API.
+//!
+//! Every failed request is answered with an error status and an
+//! `ApiResponse` body whose `code` says what went wrong, so clients can
+//! branch on the code instead of matching on `error` messages.
+
 use axum::http::StatusCode;
+use axum::response::{IntoResponse, Json, Response};
 use drasi_lib::DrasiError;
 use serde::Serialize;
+use std::fmt;
 use utoipa::ToSchema;
 
-/// Error codes for API responses
-pub mod error_codes {
-    pub const SOURCE_CREATE_FAILED: &str = "SOURCE_CREATE_FAILED";
-    pub const SOURCE_NOT_FOUND: &str = "SOURCE_NOT_FOUND";
-    pub const SOURCE_START_FAILED: &str = "SOURCE_START_FAILED";
-    pub const SOURCE_STOP_FAILED: &str = "SOURCE_STOP_FAILED";
-    pub const SOURCE_DELETE_FAILED: &str = "SOURCE_DELETE_FAILED";
-
-    pub const QUERY_CREATE_FAILED: &str = "QUERY_CREATE_FAILED";
-    pub const QUERY_NOT_FOUND: &str = "QUERY_NOT_FOUND";
-    pub const QUERY_START_FAILED: &str = "QUERY_START_FAILED";
-    pub const QUERY_STOP_FAILED: &str = "QUERY_STOP_FAILED";
-    pub const QUERY_DELETE_FAILED: &str = "QUERY_DELETE_FAILED";
-    pub const QUERY_RESULTS_UNAVAILABLE: &str = "QUERY_RESULTS_UNAVAILABLE";
-
-    pub const REACTION_CREATE_FAILED: &str = "REACTION_CREATE_FAILED";
-    pub const REACTION_NOT_FOUND: &str = "REACTION_NOT_FOUND";
-    pub const REACTION_START_FAILED: &str = "REACTION_START_FAILED";
-    pub const REACTION_STOP_FAILED: &str = "REACTION_STOP_FAILED";
-    pub const REACTION_DELETE_FAILED: &str = "REACTION_DELETE_FAILED";
-
-    pub const CONFIG_READ_ONLY: &str = "CONFIG_READ_ONLY";
-    pub const DUPLICATE_RESOURCE: &str = "DUPLICATE_RESOURCE";
-    pub const INVALID_REQUEST: &str = "INVALID_REQUEST";
-    pub const INTERNAL_ERROR: &str = "INTERNAL_ERROR";
+use super::handlers::ApiResponse;
+use crate::factories::FactoryError;
+
+/// Error code for programmatic handling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// The component does not exist (404)
+    NotFound,
+    /// The request conflicts with the component's current state (409)
+    Conflict,
+    /// The request or the configuration in it is invalid (400)
+    Validation,
+    /// The server is read-only and cannot change its configuration (409)
+    ReadOnly,
+    /// The component changed since the given ETag was read (412)
+    PreconditionFailed,
+    /// The request changes the server and carries no valid API key (401)
+    Unauthorized,
+    /// Something the component needs, such as its database, could not be
+    /// reached (502)
+    Unreachable,
+    /// The server failed to carry out a valid request (500)
+    InternalError,
 }
 
-/// API error response structure
-#[derive(Debug, Serialize, ToSchema)]
-pub struct ErrorResponse {
-    /// Error code for programmatic handling
-    pub code: String,
-    /// Human-readable error message
-    pub message: String,
-    /// Additional error details
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub details: Option<ErrorDetail>,
+impl ErrorCode {
+    /// HTTP status code responses with this error code are sent with
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Conflict | ErrorCode::ReadOnly => StatusCode::CONFLICT,
+            ErrorCode::Validation => StatusCode::BAD_REQUEST,
+            ErrorCode::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Unreachable => StatusCode::BAD_GATEWAY,
+            ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
 }
 
-/// Additional error details
-#[derive(Debug, Serialize, ToSchema)]
-pub struct ErrorDetail {
-    /// Component type if applicable
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub component_type: Option<String>,
-    /// Component ID if applicable
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub component_id: Option<String>,
-    /// Technical error details
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub technical_details: Option<String>,
+/// A failed API request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    /// Human-readable error message
+    pub message: String,
 }
 
-impl ErrorResponse {
-    /// Create a new error response
-    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
         Self {
-            code: code.into(),
+            code,
             message: message.into(),
-            details: None,
         }
     }
 
-    /// Add details to the error response
-    pub fn with_details(mut self, details: ErrorDetail) -> Self {
-        self.details = Some(details);
-        self
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
     }
 
-    /// Convert to a specific status code
-    pub fn with_status(self) -> (StatusCode, axum::Json<Self>) {
-        let status = status_from_code(&self.code);
-        (status, axum::Json(self))
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Conflict, message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Validation, message)
     }
-}
 
-/// Convert an error code to an HTTP status code
-fn status_from_code(code: &str) -> StatusCode {
-    match code {
-        error_codes::SOURCE_NOT_FOUND
-        | error_codes::QUERY_NOT_FOUND
-        | error_codes::REACTION_NOT_FOUND => StatusCode::NOT_FOUND,
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InternalError, message)
+    }
 
-        error_codes::CONFIG_READ_ONLY | error_codes::DUPLICATE_RESOURCE => StatusCode::CONFLICT,
+    pub fn precondition_failed(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::PreconditionFailed, message)
+    }
 
-        error_codes::INVALID_REQUEST => StatusCode::BAD_REQUEST,
+    /// The server is read-only; `action` is e.g. `create sources`
+    pub fn read_only(action: &str) -> Self {
+        Self::new(
+            ErrorCode::ReadOnly,
+            format!("Server is in read-only mode. Cannot {action}."),
+        )
+    }
 
-        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    /// Prefix the message, e.g. with the operation that failed
+    pub fn with_context(mut self, context: impl fmt::Display) -> Self {
+        self.message = format!("{context}: {}", self.message);
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.code.status()
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
     }
 }
 
-/// Convert DrasiError to ErrorResponse
-impl From<DrasiError> for ErrorResponse {
+impl std::error::Error for ApiError {}
+
+impl From<DrasiError> for ApiError {
     fn from(err: DrasiError) -> Self {
         use DrasiError::*;
 
-        match &err {
-            ComponentNotFound {
-                component_type,
-                component_id,
-            } => {
-                let code = match component_type.as_str() {
-                    "source" => error_codes::SOURCE_NOT_FOUND,
-                    "query" => error_codes::QUERY_NOT_FOUND,
-                    "reaction" => error_codes::REACTION_NOT_FOUND,
-                    _ => error_codes::INTERNAL_ERROR,
-                };
-
-                ErrorResponse::new(code, format!("{component_type} '{component_id}' not found"))
-            }
-            AlreadyExists {
-                component_type,
-                component_id,
-            } => ErrorResponse::new(
-                error_codes::DUPLICATE_RESOURCE,
-                format!("{component_type} '{component_id}' already exists"),
-            ),
-            InvalidConfig { message } => {
-                ErrorResponse::new(error_codes::INVALID_REQUEST, message.clone())
-            }
-            InvalidState { message } => {
-                ErrorResponse::new(error_codes::INVALID_REQUEST, message.clone())
-            }
-            Validation { message } => {
-                ErrorResponse::new(error_codes::INVALID_REQUEST, message.clone())
-            }
-            OperationFailed {
-                component_type,
-                component_id,
-                operation,
-                reason,
-            } => ErrorResponse::new(
-                error_codes::INTERNAL_ERROR,
-                format!("Failed to {operation} {component_type} '{component_id}': {reason}"),
-            ),
-            Internal(ref err) => ErrorResponse::new(error_codes::INTERNAL_ERROR, err.to_string()),
+        let code = match &err {
+            ComponentNotFound { .. } => ErrorCode::NotFound,
+            AlreadyExists { .. } | InvalidState { .. } => ErrorCode::Conflict,
+            InvalidConfig { .. } | Validation { .. } => ErrorCode::Validation,
+            OperationFailed { .. } | Internal(_) => ErrorCode::InternalError,
+        };
+        Self::new(code, err.to_string())
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<DrasiError>() {
+            Ok(err) => err.into(),
+            Err(err) => Self::internal(err.to_string()),
         }
     }
 }
 
-/// Convert DrasiError to HTTP status code
-pub fn drasi_error_to_status(err: &DrasiError) -> StatusCode {
-    use DrasiError::*;
+impl From<FactoryError> for ApiError {
+    fn from(err: FactoryError) -> Self {
+        let code = match &err {
+            FactoryError::Mapping(_) | FactoryError::Validation(_) => ErrorCode::Validation,
+            FactoryError::Connection(_) => ErrorCode::Unreachable,
+        };
+        Self::new(code, err.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let body = ApiResponse::<()>::error(self.message).with_code(self.code);
+        (status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    #[test]
+    fn test_drasi_errors_map_to_codes() {
+        let not_found = DrasiError::ComponentNotFound {
+            component_type: "source".to_string(),
+            component_id: "s1".to_string(),
+        };
+        assert_eq!(ApiError::from(not_found).code, ErrorCode::NotFound);
+
+        let exists = anyhow::Error::new(DrasiError::AlreadyExists {
+            component_type: "query".to_string(),
+            component_id: "q1".to_string(),
+        });
+        assert_eq!(ApiError::from(exists).code, ErrorCode::Conflict);
+
+        let running = DrasiError::InvalidState {
+            message: "source 's1' is not running".to_string(),
+        };
+        let error = ApiError::from(running);
+        assert_eq!(error.code, ErrorCode::Conflict);
+        assert_eq!(error.status(), StatusCode::CONFLICT);
+
+        let other = anyhow::anyhow!("connection refused");
+        assert_eq!(ApiError::from(other).code, ErrorCode::InternalError);
+    }
 
-    match err {
-        ComponentNotFound { .. } => StatusCode::NOT_FOUND,
-        AlreadyExists { .. } => StatusCode::CONFLICT,
-        InvalidConfig { .. } | InvalidState { .. } | Validation { .. } => StatusCode::BAD_REQUEST,
-        OperationFailed { .. } | Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    #[tokio::test]
+    async fn test_error_response_carries_code_and_status() {
+        let response = ApiError::read_only("create sources").into_response();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["success"], false);
+        assert_eq!(json["code"], "READ_ONLY");
+        assert_eq!(
+            json["error"],
+            "Server is in read-only mode. Cannot create sources."
+        );
     }
 }
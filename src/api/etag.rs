@@ -0,0 +1,110 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Entity tags for optimistic concurrency on component definitions.
+//!
+//! ETags are derived from a component's definition, not its runtime state, so a
+//! tag only changes when the component is replaced or reconfigured. GET
+//! endpoints return the tag in the `ETag` header and mutating endpoints honor
+//! `If-Match`, so a client can refuse to overwrite a change it has not seen.
+
+use axum::http::{header, HeaderMap};
+use serde::Serialize;
+
+/// Compute a strong ETag for a serializable definition.
+///
+/// Uses 64-bit FNV-1a over the JSON encoding so tags are stable across builds
+/// and restarts for an unchanged definition.
+pub fn compute_etag<T: Serialize>(definition: &T) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let bytes = serde_json::to_vec(definition).unwrap_or_default();
+    let hash = bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    });
+    format!("\"{hash:016x}\"")
+}
+
+/// Check an `If-Match` header against the current ETag of a component.
+///
+/// `current` is `None` when the component does not exist. Requests without an
+/// `If-Match` header always pass. Tags are compared strongly, as RFC 7232
+/// requires for `If-Match`, so a weak tag (`W/"..."`) never matches.
+pub fn if_match_satisfied(headers: &HeaderMap, current: Option<&str>) -> bool {
+    let Some(if_match) = headers
+        .get(header::IF_MATCH)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return true;
+    };
+    let Some(current) = current else {
+        return false;
+    };
+    if_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag == current)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers_with_if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_compute_etag_is_stable_and_quoted() {
+        let a = compute_etag(&serde_json::json!({"id": "q1", "query": "MATCH (n) RETURN n"}));
+        let b = compute_etag(&serde_json::json!({"id": "q1", "query": "MATCH (n) RETURN n"}));
+        let c = compute_etag(&serde_json::json!({"id": "q1", "query": "MATCH (m) RETURN m"}));
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.starts_with('"') && a.ends_with('"'));
+    }
+
+    #[test]
+    fn test_missing_if_match_always_passes() {
+        assert!(if_match_satisfied(&HeaderMap::new(), Some("\"abc\"")));
+        assert!(if_match_satisfied(&HeaderMap::new(), None));
+    }
+
+    #[test]
+    fn test_if_match_compares_tags() {
+        let current = Some("\"abc\"");
+        assert!(if_match_satisfied(&headers_with_if_match("\"abc\""), current));
+        assert!(if_match_satisfied(&headers_with_if_match("\"x\", \"abc\""), current));
+        assert!(if_match_satisfied(&headers_with_if_match("*"), current));
+        assert!(!if_match_satisfied(&headers_with_if_match("\"other\""), current));
+    }
+
+    #[test]
+    fn test_if_match_rejects_weak_tags() {
+        let current = Some("\"abc\"");
+        let weak = headers_with_if_match("W/\"abc\"");
+        assert!(!if_match_satisfied(&weak, current));
+    }
+
+    #[test]
+    fn test_if_match_fails_for_missing_component() {
+        assert!(!if_match_satisfied(&headers_with_if_match("*"), None));
+    }
+}
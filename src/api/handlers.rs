@@ -13,33 +13,56 @@
 // limitations under the License.
 
 use axum::{
-    extract::{Extension, Path},
-    http::StatusCode,
+    extract::{Extension, Path, Query},
+    http::{header, HeaderMap},
     response::Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
+use crate::api::error::{ApiError, ErrorCode};
+use crate::api::etag::{compute_etag, if_match_satisfied};
+use crate::api::joins::{validate_joins, StrictJoins};
+use crate::api::results_diff::ResultLogs;
 use crate::config::{ReactionConfig, SourceConfig};
 use crate::factories::{create_reaction, create_source};
+use crate::journal::Mutation;
 use crate::persistence::ConfigPersistence;
+use crate::registry::{ComponentKind, ComponentRegistry, StatusEvent};
 use drasi_lib::{
     // Internal types (doc-hidden but accessible)
     channels::ComponentStatus,
-    queries::LabelExtractor, // For join validation
+    plugin_core::Source,
+    queries::LabelExtractor,
     // Public config types
     QueryConfig,
 };
 
 /// Helper function to persist configuration after a successful operation.
 /// Logs errors but does not fail the request - persistence failures are non-fatal.
-async fn persist_after_operation(
+pub(crate) async fn persist_after_operation(
     config_persistence: &Option<Arc<ConfigPersistence>>,
+    mutation: Mutation,
+) {
+    let operation = mutation.describe();
+    persist_mutations(config_persistence, &[mutation], &operation).await;
+}
+
+/// Journal a batch of mutations and save them with a single write.
+pub(crate) async fn persist_mutations(
+    config_persistence: &Option<Arc<ConfigPersistence>>,
+    mutations: &[Mutation],
     operation: &str,
 ) {
     if let Some(persistence) = config_persistence {
-        if let Err(e) = persistence.save().await {
+        for mutation in mutations {
+            if let Err(e) = persistence.record(mutation).await {
+                log::error!("Failed to journal {}: {e}", mutation.describe());
+            }
+        }
+        if let Err(e) = persistence.request_save().await {
             log::error!("Failed to persist configuration after {operation}: {e}");
             // Don't fail the request, just log the error
         }
@@ -60,6 +83,84 @@ pub struct ComponentListItem {
     id: String,
     /// Current status of the component
     status: ComponentStatus,
+    /// Component kind (e.g. `postgres`, `sse`) or query language, when known to the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+    /// Seconds since the server first observed the component in its current run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uptime_seconds: Option<u64>,
+    /// Sources a query reads from
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sources: Vec<String>,
+    /// Queries reading from a source, or queries a reaction subscribes to
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    queries: Vec<String>,
+    /// Reactions subscribed to a query
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    reactions: Vec<String>,
+    /// Whether the component is disabled and will not start until restored
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    disabled: bool,
+    /// Why the component could not be built at startup; it is not running
+    /// and is kept only so it can be fixed or deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// When a source was last seen alive, if liveness checks are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_heartbeat: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether a running source has had no heartbeat for too long
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stale: bool,
+}
+
+impl ComponentListItem {
+    fn new(id: String, status: ComponentStatus) -> Self {
+        Self {
+            id,
+            status,
+            kind: None,
+            uptime_seconds: None,
+            sources: Vec::new(),
+            queries: Vec::new(),
+            reactions: Vec::new(),
+            disabled: false,
+            error: None,
+            last_heartbeat: None,
+            stale: false,
+        }
+    }
+}
+
+/// Query parameters accepted by the delete endpoints
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteParams {
+    /// Stop the component and mark it disabled instead of removing it
+    #[serde(default)]
+    pub soft: bool,
+}
+
+/// Query parameters accepted by `POST /queries`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CreateQueryParams {
+    /// Reject the query if its joins have problems, instead of only logging them
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// Query configuration together with the capacities it actually runs with.
+///
+/// The effective values fall back to the server-wide defaults when the query
+/// does not override them. `null` means DrasiLib's built-in default applies.
+#[derive(Serialize)]
+pub struct QueryDetails {
+    #[serde(flatten)]
+    config: QueryConfig,
+    /// Priority queue capacity in effect for this query
+    effective_priority_queue_capacity: Option<usize>,
+    /// Dispatch buffer capacity in effect for this query
+    effective_dispatch_buffer_capacity: Option<usize>,
 }
 
 #[derive(Serialize)]
@@ -70,6 +171,9 @@ pub struct ApiResponse<T> {
     data: Option<T>,
     /// Error message if unsuccessful
     error: Option<String>,
+    /// Error code if unsuccessful, for clients to branch on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<ErrorCode>,
 }
 
 /// Generic API Response schema for OpenAPI documentation
@@ -82,6 +186,9 @@ pub struct ApiResponseSchema {
     data: Option<serde_json::Value>,
     /// Error message if unsuccessful
     error: Option<String>,
+    /// Error code if unsuccessful, for clients to branch on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<ErrorCode>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -96,6 +203,7 @@ impl<T> ApiResponse<T> {
             success: true,
             data: Some(data),
             error: None,
+            code: None,
         }
     }
 
@@ -104,8 +212,15 @@ impl<T> ApiResponse<T> {
             success: false,
             data: None,
             error: Some(message),
+            code: None,
         }
     }
+
+    /// Set the error code of an error response
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
 }
 
 /// Check server health
@@ -124,6 +239,289 @@ pub async fn health_check() -> Json<HealthResponse> {
     })
 }
 
+/// Map each source ID to the queries that read from it.
+async fn queries_by_source(core: &drasi_lib::DrasiLib) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for (query_id, _) in core.list_queries().await.unwrap_or_default() {
+        if let Ok(config) = core.get_query_config(&query_id).await {
+            for subscription in config.sources {
+                map.entry(subscription.source_id)
+                    .or_default()
+                    .push(query_id.clone());
+            }
+        }
+    }
+    map
+}
+
+async fn source_list_item(
+    registry: &ComponentRegistry,
+    queries_by_source: &mut HashMap<String, Vec<String>>,
+    id: String,
+    status: ComponentStatus,
+) -> ComponentListItem {
+    let mut item = ComponentListItem::new(id, status);
+    item.uptime_seconds = registry
+        .observe_status(ComponentKind::Source, &item.id, &item.status)
+        .await;
+    item.disabled = registry.is_disabled(ComponentKind::Source, &item.id).await;
+    item.kind = registry
+        .get_source(&item.id)
+        .await
+        .map(|config| config.kind().to_string());
+    item.queries = queries_by_source.remove(&item.id).unwrap_or_default();
+    item.last_heartbeat = registry.last_heartbeat(&item.id).await;
+    item.stale = registry.is_stale(&item.id).await;
+    item
+}
+
+async fn query_list_item(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    reactions_by_query: &mut HashMap<String, Vec<String>>,
+    id: String,
+    status: ComponentStatus,
+) -> ComponentListItem {
+    let mut item = ComponentListItem::new(id, status);
+    item.uptime_seconds = registry
+        .observe_status(ComponentKind::Query, &item.id, &item.status)
+        .await;
+    item.disabled = registry.is_disabled(ComponentKind::Query, &item.id).await;
+    if let Ok(config) = core.get_query_config(&item.id).await {
+        item.kind = serde_json::to_value(&config.query_language)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string));
+        item.sources = config.sources.into_iter().map(|s| s.source_id).collect();
+    }
+    item.reactions = reactions_by_query.remove(&item.id).unwrap_or_default();
+    item
+}
+
+async fn reaction_list_item(
+    registry: &ComponentRegistry,
+    id: String,
+    status: ComponentStatus,
+) -> ComponentListItem {
+    let mut item = ComponentListItem::new(id, status);
+    item.uptime_seconds = registry
+        .observe_status(ComponentKind::Reaction, &item.id, &item.status)
+        .await;
+    item.disabled = registry.is_disabled(ComponentKind::Reaction, &item.id).await;
+    if let Some(config) = registry.get_reaction(&item.id).await {
+        item.kind = Some(config.kind().to_string());
+        item.queries = config.queries().to_vec();
+    }
+    item
+}
+
+/// Summary of a source or reaction that failed to build at startup, which
+/// DrasiLib does not know about.
+async fn failed_list_item(
+    registry: &ComponentRegistry,
+    kind: ComponentKind,
+    id: String,
+    error: String,
+) -> ComponentListItem {
+    let mut item = match kind {
+        ComponentKind::Reaction => reaction_list_item(registry, id, ComponentStatus::Error).await,
+        _ => {
+            let mut item = ComponentListItem::new(id, ComponentStatus::Error);
+            item.disabled = registry.is_disabled(kind, &item.id).await;
+            item.kind = registry
+                .get_source(&item.id)
+                .await
+                .map(|config| config.kind().to_string());
+            item
+        }
+    };
+    item.error = Some(error);
+    item
+}
+
+/// Current ETag of a source definition, or `None` if the source does not exist.
+///
+/// Sources added programmatically have no stored definition, so their tag only
+/// covers the ID.
+async fn source_etag(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    id: &str,
+) -> Option<String> {
+    if core.get_source_status(id).await.is_err() {
+        registry.build_error(ComponentKind::Source, id).await?;
+    }
+    Some(match registry.get_source(id).await {
+        Some(config) => compute_etag(&config),
+        None => compute_etag(&serde_json::json!({ "id": id })),
+    })
+}
+
+/// Current ETag of a query definition, or `None` if the query does not exist.
+async fn query_etag(core: &drasi_lib::DrasiLib, id: &str) -> Option<String> {
+    core.get_query_config(id)
+        .await
+        .ok()
+        .map(|config| compute_etag(&config))
+}
+
+/// Current ETag of a reaction definition, or `None` if the reaction does not exist.
+async fn reaction_etag(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    id: &str,
+) -> Option<String> {
+    if core.get_reaction_status(id).await.is_err() {
+        registry.build_error(ComponentKind::Reaction, id).await?;
+    }
+    Some(match registry.get_reaction(id).await {
+        Some(config) => compute_etag(&config),
+        None => compute_etag(&serde_json::json!({ "id": id })),
+    })
+}
+
+/// Current status of a component, or `None` if it does not exist.
+async fn component_status(
+    core: &drasi_lib::DrasiLib,
+    kind: ComponentKind,
+    id: &str,
+) -> Option<ComponentStatus> {
+    match kind {
+        ComponentKind::Source => core.get_source_status(id).await.ok(),
+        ComponentKind::Reaction => core.get_reaction_status(id).await.ok(),
+        ComponentKind::Query => core
+            .list_queries()
+            .await
+            .ok()?
+            .into_iter()
+            .find(|(query_id, _)| query_id == id)
+            .map(|(_, status)| status),
+    }
+}
+
+async fn start_component(
+    core: &drasi_lib::DrasiLib,
+    kind: ComponentKind,
+    id: &str,
+) -> anyhow::Result<()> {
+    match kind {
+        ComponentKind::Source => core.start_source(id).await?,
+        ComponentKind::Query => core.start_query(id).await?,
+        ComponentKind::Reaction => core.start_reaction(id).await?,
+    }
+    Ok(())
+}
+
+async fn stop_component(
+    core: &drasi_lib::DrasiLib,
+    kind: ComponentKind,
+    id: &str,
+) -> anyhow::Result<()> {
+    match kind {
+        ComponentKind::Source => core.stop_source(id).await?,
+        ComponentKind::Query => core.stop_query(id).await?,
+        ComponentKind::Reaction => core.stop_reaction(id).await?,
+    }
+    Ok(())
+}
+
+/// Stop a component and mark it disabled without removing it.
+///
+/// The definition stays in the persisted config under `disabled`, so the
+/// component stays stopped across restarts until it is enabled again. Used by
+/// both `disable` and soft delete.
+async fn disable_component(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    config_persistence: &Option<Arc<ConfigPersistence>>,
+    kind: ComponentKind,
+    id: &str,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    let label = kind.label();
+    let status = component_status(core, kind, id)
+        .await
+        .ok_or_else(|| ApiError::not_found(format!("{label} '{id}' not found")))?;
+
+    if matches!(status, ComponentStatus::Running | ComponentStatus::Starting) {
+        if let Err(e) = stop_component(core, kind, id).await {
+            log::error!("Failed to stop {label} '{id}' while disabling: {e}");
+            return Err(e.into());
+        }
+        registry.reset_running(kind, id).await;
+    }
+
+    registry.set_disabled(kind, id, true).await;
+    let mutation = Mutation::SetDisabled {
+        kind,
+        id: id.to_string(),
+        disabled: true,
+    };
+    persist_after_operation(config_persistence, mutation).await;
+
+    Ok(Json(ApiResponse::success(StatusResponse {
+        message: format!(
+            "Disabled {label} '{id}'; enable it with POST /{}/{id}/enable",
+            kind.collection()
+        ),
+    })))
+}
+
+/// Clear the disabled flag of a component and start it if it is configured to
+/// start automatically. Used by both `enable` and `restore`.
+async fn enable_component(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    config_persistence: &Option<Arc<ConfigPersistence>>,
+    kind: ComponentKind,
+    id: &str,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    let label = kind.label();
+    let status = component_status(core, kind, id)
+        .await
+        .ok_or_else(|| ApiError::not_found(format!("{label} '{id}' not found")))?;
+
+    let auto_start = match kind {
+        ComponentKind::Source => registry
+            .get_source(id)
+            .await
+            .is_some_and(|config| config.auto_start()),
+        ComponentKind::Reaction => registry
+            .get_reaction(id)
+            .await
+            .is_some_and(|config| config.auto_start()),
+        ComponentKind::Query => {
+            registry.is_query_auto_start_suppressed(id).await
+                || core
+                    .get_query_config(id)
+                    .await
+                    .is_ok_and(|config| config.auto_start)
+        }
+    };
+
+    registry.set_disabled(kind, id, false).await;
+    registry.set_stopped_by_api(kind, id, false).await;
+    let mutation = Mutation::SetDisabled {
+        kind,
+        id: id.to_string(),
+        disabled: false,
+    };
+
+    if auto_start && !matches!(status, ComponentStatus::Running | ComponentStatus::Starting) {
+        if let Err(e) = start_component(core, kind, id).await {
+            // The component is no longer disabled, so a plain start can retry
+            log::error!("Failed to start enabled {label} '{id}': {e}");
+            persist_after_operation(config_persistence, mutation).await;
+            return Err(e.into());
+        }
+        registry.mark_started(kind, id).await;
+    }
+
+    persist_after_operation(config_persistence, mutation).await;
+
+    Ok(Json(ApiResponse::success(StatusResponse {
+        message: format!("Enabled {label} '{id}'"),
+    })))
+}
+
 /// List all sources
 #[utoipa::path(
     get,
@@ -135,12 +533,17 @@ pub async fn health_check() -> Json<HealthResponse> {
 )]
 pub async fn list_sources(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
 ) -> Json<ApiResponse<Vec<ComponentListItem>>> {
     let sources = core.list_sources().await.unwrap_or_default();
-    let items: Vec<ComponentListItem> = sources
-        .into_iter()
-        .map(|(id, status)| ComponentListItem { id, status })
-        .collect();
+    let mut subscribers = queries_by_source(&core).await;
+    let mut items = Vec::with_capacity(sources.len());
+    for (id, status) in sources {
+        items.push(source_list_item(&registry, &mut subscribers, id, status).await);
+    }
+    for (id, error) in registry.failed(ComponentKind::Source).await {
+        items.push(failed_list_item(&registry, ComponentKind::Source, id, error).await);
+    }
 
     Json(ApiResponse::success(items))
 }
@@ -167,6 +570,7 @@ pub async fn list_sources(
     responses(
         (status = 200, description = "Source created successfully", body = ApiResponse),
         (status = 400, description = "Invalid source configuration"),
+        (status = 409, description = "Source already exists, or the server is read-only"),
         (status = 500, description = "Internal server error"),
     ),
     tag = "Sources"
@@ -175,36 +579,38 @@ pub async fn create_source_handler(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
     Extension(read_only): Extension<Arc<bool>>,
     Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Json(config_json): Json<serde_json::Value>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
     if *read_only {
-        return Ok(Json(ApiResponse::error(
-            "Server is in read-only mode. Cannot create sources.".to_string(),
-        )));
+        return Err(ApiError::read_only("create sources"));
     }
 
     // Parse the JSON into SourceConfig (tagged enum)
-    let config: SourceConfig = match serde_json::from_value(config_json) {
+    let mut config: SourceConfig = match serde_json::from_value(config_json) {
         Ok(c) => c,
         Err(e) => {
             log::error!("Failed to parse source config: {e}");
-            return Ok(Json(ApiResponse::error(format!(
+            return Err(ApiError::validation(format!(
                 "Invalid source configuration: {e}"
-            ))));
+            )));
         }
     };
 
     let source_id = config.id().to_string();
-    let auto_start = config.auto_start();
+    // Lazy sources are started by lazy_sources once a query subscribes
+    let auto_start = config.auto_start() && !config.lazy();
+    let registry_config = config.clone();
+    if config.lazy() {
+        config.set_auto_start(false);
+    }
 
     // Create the source instance using the factory function
     let source = match create_source(config).await {
         Ok(s) => s,
         Err(e) => {
             log::error!("Failed to create source instance: {e}");
-            return Ok(Json(ApiResponse::error(format!(
-                "Failed to create source: {e}"
-            ))));
+            return Err(ApiError::from(e).with_context("Failed to create source"));
         }
     };
 
@@ -212,6 +618,7 @@ pub async fn create_source_handler(
     match core.add_source(source).await {
         Ok(_) => {
             log::info!("Source '{source_id}' created successfully");
+            registry.insert_source(registry_config.clone()).await;
 
             // Auto-start if configured
             if auto_start {
@@ -220,30 +627,33 @@ pub async fn create_source_handler(
                 }
             }
 
-            persist_after_operation(&config_persistence, "creating source").await;
+            let mutation = Mutation::PutSource {
+                config: registry_config,
+            };
+            persist_after_operation(&config_persistence, mutation).await;
 
             Ok(Json(ApiResponse::success(StatusResponse {
                 message: format!("Source '{source_id}' created successfully"),
             })))
         }
         Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("already exists") {
+            let error = ApiError::from(e);
+            if error.code == ErrorCode::Conflict {
                 log::info!("Source '{source_id}' already exists");
-                return Ok(Json(ApiResponse::success(StatusResponse {
-                    message: format!("Source '{source_id}' already exists"),
-                })));
+                return Err(ApiError::conflict(format!(
+                    "Source '{source_id}' already exists; replace it with PUT /sources/{source_id}"
+                )));
             }
-            log::error!("Failed to add source: {e}");
-            Ok(Json(ApiResponse::error(error_msg)))
+            log::error!("Failed to add source: {error}");
+            Err(error)
         }
     }
 }
 
 /// Get source status by ID
 ///
-/// Note: Source configs are not stored - sources are instances.
-/// This endpoint returns the source status instead.
+/// Returns the same summary as the list endpoint: status, kind, uptime and the
+/// queries reading from the source.
 #[utoipa::path(
     get,
     path = "/sources/{id}",
@@ -251,18 +661,266 @@ pub async fn create_source_handler(
         ("id" = String, Path, description = "Source ID")
     ),
     responses(
-        (status = 200, description = "Source found", body = ApiResponse),
+        (status = 200, description = "Source found", body = ApiResponse,
+            headers(("ETag" = String, description = "Revision of the source definition"))),
         (status = 404, description = "Source not found"),
     ),
     tag = "Sources"
 )]
 pub async fn get_source(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<ComponentListItem>>, StatusCode> {
+) -> Result<([(header::HeaderName, String); 1], Json<ApiResponse<ComponentListItem>>), ApiError>
+{
     match core.get_source_status(&id).await {
-        Ok(status) => Ok(Json(ApiResponse::success(ComponentListItem { id, status }))),
-        Err(_) => Err(StatusCode::NOT_FOUND),
+        Ok(status) => {
+            let etag = source_etag(&core, &registry, &id)
+                .await
+                .ok_or_else(|| ApiError::not_found(format!("Source '{id}' not found")))?;
+            let mut subscribers = queries_by_source(&core).await;
+            let item = source_list_item(&registry, &mut subscribers, id, status).await;
+            Ok(([(header::ETAG, etag)], Json(ApiResponse::success(item))))
+        }
+        Err(_) => match registry.build_error(ComponentKind::Source, &id).await {
+            Some(error) => {
+                let etag = source_etag(&core, &registry, &id)
+                    .await
+                    .ok_or_else(|| ApiError::not_found(format!("Source '{id}' not found")))?;
+                let item = failed_list_item(&registry, ComponentKind::Source, id, error).await;
+                Ok(([(header::ETAG, etag)], Json(ApiResponse::success(item))))
+            }
+            None => Err(ApiError::not_found(format!("Source '{id}' not found"))),
+        },
+    }
+}
+
+/// Get a source's status history
+///
+/// Lists recent events for the source, oldest first, such as restarts under
+/// its `reconnect` policy. Only the last 50 events are kept.
+#[utoipa::path(
+    get,
+    path = "/sources/{id}/history",
+    params(
+        ("id" = String, Path, description = "Source ID")
+    ),
+    responses(
+        (status = 200, description = "Status history of the source", body = ApiResponse),
+        (status = 404, description = "Source not found"),
+    ),
+    tag = "Sources"
+)]
+pub async fn get_source_history(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<Vec<StatusEvent>>>, ApiError> {
+    if core.get_source_status(&id).await.is_err()
+        && registry
+            .build_error(ComponentKind::Source, &id)
+            .await
+            .is_none()
+    {
+        return Err(ApiError::not_found(format!("Source '{id}' not found")));
+    }
+    Ok(Json(ApiResponse::success(registry.history(&id).await)))
+}
+
+/// Replace a source
+///
+/// Takes a full source configuration, like `POST /sources`, and replaces the
+/// existing source with the same ID: the source is stopped, removed and
+/// recreated, and started again if it was running. Running queries that read
+/// from the source are restarted so they subscribe to the new instance. If the
+/// new source cannot be added, the previous definition is restored.
+///
+/// A source that failed to build at startup (`tolerate_component_errors`) can
+/// be replaced too; once it is added, the queries that were held back because
+/// of it are started.
+#[utoipa::path(
+    put,
+    path = "/sources/{id}",
+    params(
+        ("id" = String, Path, description = "Source ID"),
+        ("If-Match" = Option<String>, Header, description = "Only replace if the source still has this ETag")
+    ),
+    request_body = serde_json::Value,
+    responses(
+        (status = 200, description = "Source replaced successfully", body = ApiResponse),
+        (status = 400, description = "Invalid source configuration", body = ApiResponse),
+        (status = 404, description = "Source not found", body = ApiResponse),
+        (status = 409, description = "Server is read-only", body = ApiResponse),
+        (status = 412, description = "Source changed since the given ETag was read", body = ApiResponse),
+    ),
+    tag = "Sources"
+)]
+pub async fn replace_source(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(config_json): Json<serde_json::Value>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("replace sources"));
+    }
+
+    let config: SourceConfig = serde_json::from_value(config_json)
+        .map_err(|e| ApiError::validation(format!("Invalid source configuration: {e}")))?;
+    if config.id() != id {
+        return Err(ApiError::validation(format!(
+            "Source ID '{}' in the body does not match '{id}' in the path",
+            config.id()
+        )));
+    }
+
+    // Held until the change is applied, so a concurrent request cannot slip in
+    // between the If-Match check and the change
+    let _definition = registry.lock_definition(ComponentKind::Source, &id).await;
+
+    // A source that failed to build is registered but not in DrasiLib
+    if core.get_source_status(&id).await.is_err()
+        && registry
+            .build_error(ComponentKind::Source, &id)
+            .await
+            .is_none()
+    {
+        return Err(ApiError::not_found(format!("Source '{id}' not found")));
+    }
+    if headers.contains_key(header::IF_MATCH) {
+        let current = source_etag(&core, &registry, &id).await;
+        if !if_match_satisfied(&headers, current.as_deref()) {
+            log::info!("Rejecting replace of source '{id}': If-Match precondition failed");
+            return Err(ApiError::precondition_failed(format!(
+                "Source '{id}' changed since the given ETag was read"
+            )));
+        }
+    }
+
+    // Build the new instance before touching the running source
+    let mut instance_config = config.clone();
+    instance_config.set_auto_start(false);
+    let source = create_source(instance_config).await.map_err(|e| {
+        log::error!("Failed to create source instance: {e}");
+        ApiError::from(e).with_context("Failed to create source")
+    })?;
+    replace_source_instance(&core, &registry, config.clone(), source).await?;
+
+    log::info!("Source '{id}' replaced successfully");
+    persist_after_operation(&config_persistence, Mutation::PutSource { config }).await;
+
+    Ok(Json(ApiResponse::success(StatusResponse {
+        message: format!("Source '{id}' replaced successfully"),
+    })))
+}
+
+/// Swap a source for a new instance built from `config`, keeping the queries
+/// that read from it attached.
+///
+/// The source is stopped, removed and added again, and started again if it
+/// was running. Running queries that read from the source are restarted so
+/// they subscribe to the new instance. A source that failed to build is added
+/// for the first time, and the queries held back because of it are started.
+/// If the new instance cannot be added, the previous definition is restored.
+pub(crate) async fn replace_source_instance(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    config: SourceConfig,
+    source: Box<dyn Source + 'static>,
+) -> Result<(), ApiError> {
+    let id = config.id().to_string();
+    let failed = registry
+        .build_error(ComponentKind::Source, &id)
+        .await
+        .is_some();
+    let disabled = registry.is_disabled(ComponentKind::Source, &id).await;
+    let was_running = matches!(
+        core.get_source_status(&id).await,
+        Ok(ComponentStatus::Running | ComponentStatus::Starting)
+    );
+
+    let readers = queries_by_source(core).await.remove(&id).unwrap_or_default();
+    let mut running_readers = Vec::new();
+    let mut held_back = Vec::new();
+    for query_id in readers {
+        let status = component_status(core, ComponentKind::Query, &query_id).await;
+        if matches!(status, Some(ComponentStatus::Running | ComponentStatus::Starting)) {
+            running_readers.push(query_id);
+        } else if failed
+            && registry.is_query_auto_start_suppressed(&query_id).await
+            && !registry.is_disabled(ComponentKind::Query, &query_id).await
+        {
+            held_back.push(query_id);
+        }
+    }
+
+    let previous = registry.get_source(&id).await;
+    if !failed {
+        core.remove_source(&id).await.map_err(|e| {
+            log::error!("Failed to remove source '{id}' for replacement: {e}");
+            ApiError::from(e)
+        })?;
+    }
+    if let Err(e) = core.add_source(source).await {
+        let error = ApiError::from(e);
+        log::error!("Failed to add replacement for source '{id}': {error}");
+        if let Some(previous) = previous.filter(|_| !failed) {
+            restore_source_instance(core, previous, was_running).await;
+        }
+        return Err(error.with_context(format!("Failed to replace source '{id}'")));
+    }
+    registry.insert_source(config.clone()).await;
+    registry.reset_running(ComponentKind::Source, &id).await;
+
+    // A lazy source is started again by lazy_sources once a query subscribes
+    let start = was_running || (config.auto_start() && !config.lazy());
+    if start && !disabled {
+        match core.start_source(&id).await {
+            Ok(_) => registry.mark_started(ComponentKind::Source, &id).await,
+            Err(e) => log::warn!("Failed to start replaced source '{id}': {e}"),
+        }
+    }
+    for query_id in running_readers {
+        let restarted = match core.stop_query(&query_id).await {
+            Ok(_) => core.start_query(&query_id).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = restarted {
+            log::warn!("Failed to restart query '{query_id}' after replacing source '{id}': {e}");
+            continue;
+        }
+        registry.mark_started(ComponentKind::Query, &query_id).await;
+    }
+    for query_id in held_back {
+        if let Err(e) = core.start_query(&query_id).await {
+            log::warn!("Failed to start query '{query_id}' held back by source '{id}': {e}");
+            continue;
+        }
+        registry.mark_started(ComponentKind::Query, &query_id).await;
+    }
+    Ok(())
+}
+
+/// Put a source back after its replacement could not be added.
+async fn restore_source_instance(core: &drasi_lib::DrasiLib, config: SourceConfig, start: bool) {
+    let id = config.id().to_string();
+    let mut instance_config = config;
+    instance_config.set_auto_start(false);
+    let restored = match create_source(instance_config).await {
+        Ok(source) => core.add_source(source).await.map_err(anyhow::Error::from),
+        Err(e) => Err(anyhow::Error::from(e)),
+    };
+    if let Err(e) = restored {
+        log::error!("Failed to restore source '{id}': {e}");
+        return;
+    }
+    if start {
+        if let Err(e) = core.start_source(&id).await {
+            log::warn!("Failed to start restored source '{id}': {e}");
+        }
     }
 }
 
@@ -271,10 +929,14 @@ pub async fn get_source(
     delete,
     path = "/sources/{id}",
     params(
-        ("id" = String, Path, description = "Source ID")
+        ("id" = String, Path, description = "Source ID"),
+        ("If-Match" = Option<String>, Header, description = "Only delete if the source still has this ETag"),
+        DeleteParams
     ),
     responses(
         (status = 200, description = "Source deleted successfully", body = ApiResponse),
+        (status = 404, description = "Source not found"),
+        (status = 412, description = "Source changed since the given ETag was read"),
     ),
     tag = "Sources"
 )]
@@ -282,17 +944,63 @@ pub async fn delete_source(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
     Extension(read_only): Extension<Arc<bool>>,
     Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+    Query(params): Query<DeleteParams>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
     if *read_only {
-        return Ok(Json(ApiResponse::error(
-            "Server is in read-only mode. Cannot delete sources.".to_string(),
-        )));
+        return Err(ApiError::read_only("delete sources"));
+    }
+
+    let _definition = registry.lock_definition(ComponentKind::Source, &id).await;
+
+    if headers.contains_key(header::IF_MATCH) {
+        let current = source_etag(&core, &registry, &id).await;
+        if !if_match_satisfied(&headers, current.as_deref()) {
+            log::info!("Rejecting delete of source '{id}': If-Match precondition failed");
+            return Err(ApiError::precondition_failed(format!(
+                "Source '{id}' changed since the given ETag was read"
+            )));
+        }
+    }
+
+    if params.soft {
+        return disable_component(
+            &core,
+            &registry,
+            &config_persistence,
+            ComponentKind::Source,
+            &id,
+        )
+        .await;
+    }
+
+    // A source that failed to build was never added to DrasiLib
+    if registry
+        .build_error(ComponentKind::Source, &id)
+        .await
+        .is_some()
+    {
+        registry.remove(ComponentKind::Source, &id).await;
+        let mutation = Mutation::Remove {
+            kind: ComponentKind::Source,
+            id: id.clone(),
+        };
+        persist_after_operation(&config_persistence, mutation).await;
+        return Ok(Json(ApiResponse::success(StatusResponse {
+            message: "Source deleted successfully".to_string(),
+        })));
     }
 
     match core.remove_source(&id).await {
         Ok(_) => {
-            persist_after_operation(&config_persistence, "deleting source").await;
+            registry.remove(ComponentKind::Source, &id).await;
+            let mutation = Mutation::Remove {
+                kind: ComponentKind::Source,
+                id: id.clone(),
+            };
+            persist_after_operation(&config_persistence, mutation).await;
 
             Ok(Json(ApiResponse::success(StatusResponse {
                 message: "Source deleted successfully".to_string(),
@@ -300,7 +1008,7 @@ pub async fn delete_source(
         }
         Err(e) => {
             log::error!("Failed to delete source: {e}");
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(e.into())
         }
     }
 }
@@ -315,26 +1023,30 @@ pub async fn delete_source(
     responses(
         (status = 200, description = "Source started successfully", body = ApiResponse),
         (status = 404, description = "Source not found"),
+        (status = 409, description = "Source is disabled"),
         (status = 500, description = "Internal server error"),
     ),
     tag = "Sources"
 )]
 pub async fn start_source(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if registry.is_disabled(ComponentKind::Source, &id).await {
+        return Err(ApiError::conflict(format!(
+            "Source '{id}' is disabled; enable it with POST /sources/{id}/enable"
+        )));
+    }
+
     match core.start_source(&id).await {
-        Ok(_) => Ok(Json(ApiResponse::success(StatusResponse {
-            message: "Source started successfully".to_string(),
-        }))),
-        Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("not found") {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                Ok(Json(ApiResponse::error(error_msg)))
-            }
+        Ok(_) => {
+            registry.mark_started(ComponentKind::Source, &id).await;
+            Ok(Json(ApiResponse::success(StatusResponse {
+                message: "Source started successfully".to_string(),
+            })))
         }
+        Err(e) => Err(e.into()),
     }
 }
 
@@ -354,23 +1066,122 @@ pub async fn start_source(
 )]
 pub async fn stop_source(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
     match core.stop_source(&id).await {
-        Ok(_) => Ok(Json(ApiResponse::success(StatusResponse {
-            message: "Source stopped successfully".to_string(),
-        }))),
-        Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("not found") {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                Ok(Json(ApiResponse::error(error_msg)))
-            }
+        Ok(_) => {
+            registry.reset_running(ComponentKind::Source, &id).await;
+            Ok(Json(ApiResponse::success(StatusResponse {
+                message: "Source stopped successfully".to_string(),
+            })))
         }
+        Err(e) => Err(e.into()),
     }
 }
 
+/// Restore a soft-deleted source
+#[utoipa::path(
+    post,
+    path = "/sources/{id}/restore",
+    params(
+        ("id" = String, Path, description = "Source ID")
+    ),
+    responses(
+        (status = 200, description = "Source restored successfully", body = ApiResponse),
+        (status = 404, description = "Source not found"),
+    ),
+    tag = "Sources"
+)]
+pub async fn restore_source(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("restore sources"));
+    }
+
+    enable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Source,
+        &id,
+    )
+    .await
+}
+
+/// Clear the disabled flag of a source and start it if auto_start is set
+#[utoipa::path(
+    post,
+    path = "/sources/{id}/enable",
+    params(
+        ("id" = String, Path, description = "Source ID")
+    ),
+    responses(
+        (status = 200, description = "Source enabled successfully", body = ApiResponse),
+        (status = 404, description = "Source not found"),
+    ),
+    tag = "Sources"
+)]
+pub async fn enable_source(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("enable sources"));
+    }
+
+    enable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Source,
+        &id,
+    )
+    .await
+}
+
+/// Stop a source and keep it from starting, including across restarts
+#[utoipa::path(
+    post,
+    path = "/sources/{id}/disable",
+    params(
+        ("id" = String, Path, description = "Source ID")
+    ),
+    responses(
+        (status = 200, description = "Source disabled successfully", body = ApiResponse),
+        (status = 404, description = "Source not found"),
+    ),
+    tag = "Sources"
+)]
+pub async fn disable_source(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("disable sources"));
+    }
+
+    disable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Source,
+        &id,
+    )
+    .await
+}
+
 // Query endpoints
 /// List all queries
 #[utoipa::path(
@@ -383,24 +1194,43 @@ pub async fn stop_source(
 )]
 pub async fn list_queries(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
 ) -> Json<ApiResponse<Vec<ComponentListItem>>> {
     let queries = core.list_queries().await.unwrap_or_default();
-    let items: Vec<ComponentListItem> = queries
-        .into_iter()
-        .map(|(id, status)| ComponentListItem { id, status })
-        .collect();
+    let mut subscribers = registry.reactions_by_query().await;
+    let mut items = Vec::with_capacity(queries.len());
+    for (id, status) in queries {
+        items.push(query_list_item(&core, &registry, &mut subscribers, id, status).await);
+    }
 
     Json(ApiResponse::success(items))
 }
 
 /// Create a new query
+///
+/// Each entry in `sources` may restrict which labels are delivered to the query
+/// through its `nodes` and `relations` lists. Changes for labels not listed are
+/// dropped before they reach the query, which keeps high-volume sources cheap
+/// when a query only needs a subset of their data. Empty lists mean no filtering.
+/// Every filtered label must appear in the query pattern, otherwise the query is
+/// rejected.
+///
+/// `query_language` selects how `query` is parsed: `Cypher` (the default) or
+/// `GQL`. A query that only parses in the other language is rejected with a
+/// hint to change it.
+///
+/// Problems with `joins` are logged as warnings. With `strict=true`, or the
+/// `strict_joins` server setting, they are rejected with 400 instead.
 #[utoipa::path(
     post,
     path = "/queries",
+    params(CreateQueryParams),
     request_body = QueryConfig,
     responses(
         (status = 200, description = "Query created successfully", body = ApiResponse),
-        (status = 500, description = "Internal server error"),
+        (status = 400, description = "Invalid query or invalid joins in strict mode", body = ApiResponse),
+        (status = 409, description = "Query already exists, or the server is read-only", body = ApiResponse),
+        (status = 500, description = "Internal server error", body = ApiResponse),
     ),
     tag = "Queries"
 )]
@@ -408,79 +1238,99 @@ pub async fn create_query(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
     Extension(read_only): Extension<Arc<bool>>,
     Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
-    Json(config): Json<QueryConfig>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
-    if *read_only {
-        return Ok(Json(ApiResponse::error(
-            "Server is in read-only mode. Cannot create queries.".to_string(),
-        )));
-    }
-
-    let query_id = config.id.clone();
-
-    // Pre-flight join validation/logging (non-fatal warnings)
-    if let Some(joins) = &config.joins {
-        if !joins.is_empty() {
-            match LabelExtractor::extract_labels(&config.query, &config.query_language) {
-                Ok(labels) => {
-                    let rel_labels: std::collections::HashSet<String> =
-                        labels.relation_labels.into_iter().collect();
-                    for j in joins {
-                        if !rel_labels.contains(&j.id) {
-                            log::warn!("[JOIN-VALIDATION] Query '{query_id}' defines join id '{}' which does not appear as a relationship label in the Cypher pattern.", j.id);
-                        }
-                        for key in &j.keys {
-                            if key.label.trim().is_empty() || key.property.trim().is_empty() {
-                                log::warn!("[JOIN-VALIDATION] Query '{query_id}' join '{}' has an empty label or property (label='{}', property='{}').", j.id, key.label, key.property);
-                            }
-                        }
-                    }
-                    log::info!(
-                        "Registering query '{query_id}' with {} synthetic join(s)",
-                        joins.len()
-                    );
-                }
-                Err(e) => {
-                    log::warn!(
-                        "[JOIN-VALIDATION] Failed to parse query '{query_id}' for join validation: {e}"
-                    );
-                }
-            }
-        } else {
-            log::debug!("Registering query '{query_id}' with no synthetic joins");
-        }
-    } else {
-        log::debug!("Registering query '{query_id}' with no synthetic joins");
+    Extension(StrictJoins(strict_joins)): Extension<StrictJoins>,
+    Query(params): Query<CreateQueryParams>,
+    Json(config): Json<QueryConfig>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("create queries"));
     }
 
+    let query_id = config.id.clone();
+    validate_query_config(&config, strict_joins || params.strict)?;
+
     // Use DrasiLib's public API to create query
     match core.add_query(config.clone()).await {
         Ok(_) => {
             log::info!("Query '{query_id}' created successfully");
-            persist_after_operation(&config_persistence, "creating query").await;
+            persist_after_operation(&config_persistence, Mutation::PutQuery { config }).await;
 
             Ok(Json(ApiResponse::success(StatusResponse {
                 message: "Query created successfully".to_string(),
             })))
         }
         Err(e) => {
-            // Check if the query already exists
-            let error_msg = e.to_string();
-            if error_msg.contains("already exists") || error_msg.contains("duplicate") {
-                log::info!("Query '{query_id}' already exists, skipping creation");
-                // Return success since the query exists (idempotent behavior)
-                return Ok(Json(ApiResponse::success(StatusResponse {
-                    message: format!("Query '{query_id}' already exists"),
-                })));
+            let error = ApiError::from(e);
+            if error.code == ErrorCode::Conflict {
+                log::info!("Query '{query_id}' already exists");
+                return Err(ApiError::conflict(format!(
+                    "Query '{query_id}' already exists; replace it with PUT /queries/{query_id}"
+                )));
             }
 
-            log::error!("Failed to create query: {e}");
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            log::error!("Failed to create query: {error}");
+            Err(error.with_context(format!("Failed to create query '{query_id}'")))
+        }
+    }
+}
+
+/// Checks shared by creating and replacing a query: joins, capacities, query
+/// language and source subscription filters.
+///
+/// Problems with joins are only logged unless `strict` is set.
+fn validate_query_config(config: &QueryConfig, strict: bool) -> Result<(), ApiError> {
+    let query_id = &config.id;
+
+    // Pre-flight join validation/logging (non-fatal warnings)
+    match config.joins.as_deref() {
+        Some(joins) if !joins.is_empty() => {
+            let issues = validate_joins(&config.query, &config.query_language, joins);
+            if !issues.is_empty() && strict {
+                let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+                log::warn!(
+                    "Rejecting query '{query_id}' with invalid joins: {}",
+                    messages.join("; ")
+                );
+                return Err(ApiError::validation(format!(
+                    "Invalid joins in query '{query_id}': {}",
+                    messages.join("; ")
+                )));
+            }
+            for issue in issues {
+                log::warn!("[JOIN-VALIDATION] Query '{query_id}': {}", issue.message);
+            }
+            log::info!(
+                "Registering query '{query_id}' with {} synthetic join(s)",
+                joins.len()
+            );
         }
+        _ => log::debug!("Registering query '{query_id}' with no synthetic joins"),
+    }
+
+    if let Err(e) = crate::config::types::validate_query_capacities(config)
+        .and_then(|_| crate::config::types::validate_query_language(config))
+    {
+        return Err(ApiError::validation(e.to_string()));
+    }
+
+    let filter_errors = crate::config::types::validate_subscription_labels(config);
+    if !filter_errors.is_empty() {
+        log::warn!(
+            "Rejecting query '{query_id}' with invalid source subscription filters: {}",
+            filter_errors.join("; ")
+        );
+        return Err(ApiError::validation(format!(
+            "Invalid source subscription filters: {}",
+            filter_errors.join("; ")
+        )));
     }
+    Ok(())
 }
 
 /// Get query by name
+///
+/// Returns the query configuration plus `effective_priority_queue_capacity` and
+/// `effective_dispatch_buffer_capacity`, resolved against the server defaults.
 #[utoipa::path(
     get,
     path = "/queries/{id}",
@@ -488,7 +1338,8 @@ pub async fn create_query(
         ("id" = String, Path, description = "Query ID")
     ),
     responses(
-        (status = 200, description = "Query found", body = ApiResponse),
+        (status = 200, description = "Query found", body = ApiResponse,
+            headers(("ETag" = String, description = "Revision of the query definition"))),
         (status = 404, description = "Query not found"),
     ),
     tag = "Queries"
@@ -496,11 +1347,210 @@ pub async fn create_query(
 pub async fn get_query(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<QueryConfig>>, StatusCode> {
-    match core.get_query_config(&id).await {
-        Ok(config) => Ok(Json(ApiResponse::success(config))),
-        Err(_) => Err(StatusCode::NOT_FOUND),
+) -> Result<([(header::HeaderName, String); 1], Json<ApiResponse<QueryDetails>>), ApiError> {
+    let config = match core.get_query_config(&id).await {
+        Ok(config) => config,
+        Err(_) => return Err(ApiError::not_found(format!("Query '{id}' not found"))),
+    };
+    let etag = compute_etag(&config);
+
+    let (default_priority, default_dispatch) = match core.get_current_config().await {
+        Ok(lib_config) => (
+            lib_config.priority_queue_capacity,
+            lib_config.dispatch_buffer_capacity,
+        ),
+        Err(e) => {
+            log::warn!("Failed to read server capacity defaults: {e}");
+            (None, None)
+        }
+    };
+
+    Ok((
+        [(header::ETAG, etag)],
+        Json(ApiResponse::success(QueryDetails {
+            effective_priority_queue_capacity: config.priority_queue_capacity.or(default_priority),
+            effective_dispatch_buffer_capacity: config
+                .dispatch_buffer_capacity
+                .or(default_dispatch),
+            config,
+        })),
+    ))
+}
+
+/// Replace a query
+///
+/// Takes a full query configuration, like `POST /queries`, and replaces the
+/// existing query with the same ID. The new query is validated first, so an
+/// invalid definition leaves the old one running. Running reactions that
+/// subscribe to the query are stopped while the old query is stopped and
+/// removed, and started again once the new query is registered, so they stay
+/// attached. The new query starts again from bootstrap. If it cannot be
+/// added, the previous definition is restored.
+#[utoipa::path(
+    put,
+    path = "/queries/{id}",
+    params(
+        ("id" = String, Path, description = "Query ID"),
+        ("If-Match" = Option<String>, Header, description = "Only replace if the query still has this ETag"),
+        CreateQueryParams
+    ),
+    request_body = QueryConfig,
+    responses(
+        (status = 200, description = "Query replaced successfully", body = ApiResponse),
+        (status = 400, description = "Invalid query or invalid joins in strict mode", body = ApiResponse),
+        (status = 404, description = "Query not found", body = ApiResponse),
+        (status = 409, description = "Server is read-only", body = ApiResponse),
+        (status = 412, description = "Query changed since the given ETag was read", body = ApiResponse),
+    ),
+    tag = "Queries"
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn replace_query(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Extension(StrictJoins(strict_joins)): Extension<StrictJoins>,
+    Extension(result_logs): Extension<ResultLogs>,
+    Path(id): Path<String>,
+    Query(params): Query<CreateQueryParams>,
+    headers: HeaderMap,
+    Json(config): Json<QueryConfig>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("replace queries"));
+    }
+
+    if config.id != id {
+        return Err(ApiError::validation(format!(
+            "Query ID '{}' in the body does not match '{id}' in the path",
+            config.id
+        )));
+    }
+    if let Err(e) = LabelExtractor::extract_labels(&config.query, &config.query_language) {
+        return Err(ApiError::validation(format!("Invalid query '{id}': {e}")));
+    }
+    validate_query_config(&config, strict_joins || params.strict)?;
+
+    let _definition = registry.lock_definition(ComponentKind::Query, &id).await;
+    if core.get_query_config(&id).await.is_err() {
+        return Err(ApiError::not_found(format!("Query '{id}' not found")));
+    }
+    if headers.contains_key(header::IF_MATCH) {
+        let current = query_etag(&core, &id).await;
+        if !if_match_satisfied(&headers, current.as_deref()) {
+            log::info!("Rejecting replace of query '{id}': If-Match precondition failed");
+            return Err(ApiError::precondition_failed(format!(
+                "Query '{id}' changed since the given ETag was read"
+            )));
+        }
+    }
+
+    replace_query_instance(&core, &registry, config.clone()).await?;
+    // Rows of the old definition must not be diffed against the new one
+    result_logs.evict(&id).await;
+
+    log::info!("Query '{id}' replaced successfully");
+    persist_after_operation(&config_persistence, Mutation::PutQuery { config }).await;
+
+    Ok(Json(ApiResponse::success(StatusResponse {
+        message: format!("Query '{id}' replaced successfully"),
+    })))
+}
+
+/// Swap a query for a new definition, keeping the reactions that subscribe
+/// to it attached.
+///
+/// Running subscribers are stopped while the query is removed and added
+/// again, and started again once the new query is registered. The query is
+/// started again if it was running. If the new definition cannot be added,
+/// the previous one is restored.
+pub(crate) async fn replace_query_instance(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    config: QueryConfig,
+) -> Result<(), ApiError> {
+    let id = config.id.clone();
+    let previous = core
+        .get_query_config(&id)
+        .await
+        .map_err(|_| ApiError::not_found(format!("Query '{id}' not found")))?;
+    let was_running = matches!(
+        component_status(core, ComponentKind::Query, &id).await,
+        Some(ComponentStatus::Running | ComponentStatus::Starting)
+    );
+    let subscribers = registry.reactions_by_query().await.remove(&id).unwrap_or_default();
+    let mut running_subscribers = Vec::new();
+    for reaction_id in subscribers {
+        let status = component_status(core, ComponentKind::Reaction, &reaction_id).await;
+        if matches!(status, Some(ComponentStatus::Running | ComponentStatus::Starting)) {
+            running_subscribers.push(reaction_id);
+        }
+    }
+    for reaction_id in &running_subscribers {
+        if let Err(e) = core.stop_reaction(reaction_id).await {
+            log::warn!("Failed to stop reaction '{reaction_id}' while replacing query '{id}': {e}");
+        }
+    }
+
+    // auto_start may only be off because the query is disabled; the file
+    // keeps the configured value
+    let disabled = registry.is_disabled(ComponentKind::Query, &id).await;
+    let mut instance_config = config.clone();
+    if disabled {
+        instance_config.auto_start = false;
+    }
+
+    if was_running {
+        if let Err(e) = core.stop_query(&id).await {
+            log::warn!("Failed to stop query '{id}' before replacing it: {e}");
+        }
+    }
+    let replaced = match core.remove_query(&id).await {
+        Ok(_) => core.add_query(instance_config).await,
+        Err(e) => Err(e),
+    };
+    let result = match replaced {
+        Ok(_) => {
+            if disabled && config.auto_start {
+                registry.suppress_query_auto_start(&id).await;
+            }
+            Ok(())
+        }
+        Err(e) => {
+            log::error!("Failed to replace query '{id}': {e}");
+            if core.get_query_config(&id).await.is_err() {
+                if let Err(e) = core.add_query(previous).await {
+                    log::error!("Failed to restore query '{id}': {e}");
+                }
+            }
+            Err(ApiError::from(e).with_context(format!("Failed to replace query '{id}'")))
+        }
+    };
+    registry.reset_running(ComponentKind::Query, &id).await;
+
+    let running = matches!(
+        component_status(core, ComponentKind::Query, &id).await,
+        Some(ComponentStatus::Running | ComponentStatus::Starting)
+    );
+    if was_running && !running && !disabled {
+        match core.start_query(&id).await {
+            Ok(_) => registry.mark_started(ComponentKind::Query, &id).await,
+            Err(e) => log::warn!("Failed to start query '{id}' after replacing it: {e}"),
+        }
+    }
+    for reaction_id in running_subscribers {
+        if let Err(e) = core.start_reaction(&reaction_id).await {
+            log::warn!(
+                "Failed to restart reaction '{reaction_id}' after replacing query '{id}': {e}"
+            );
+            continue;
+        }
+        registry
+            .mark_started(ComponentKind::Reaction, &reaction_id)
+            .await;
     }
+    result
 }
 
 /// Delete a query
@@ -508,28 +1558,64 @@ pub async fn get_query(
     delete,
     path = "/queries/{id}",
     params(
-        ("id" = String, Path, description = "Query ID")
+        ("id" = String, Path, description = "Query ID"),
+        ("If-Match" = Option<String>, Header, description = "Only delete if the query still has this ETag"),
+        DeleteParams
     ),
     responses(
         (status = 200, description = "Query deleted successfully", body = ApiResponse),
+        (status = 404, description = "Query not found"),
+        (status = 412, description = "Query changed since the given ETag was read"),
     ),
     tag = "Queries"
 )]
+#[allow(clippy::too_many_arguments)]
 pub async fn delete_query(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
     Extension(read_only): Extension<Arc<bool>>,
     Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Extension(result_logs): Extension<ResultLogs>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+    Query(params): Query<DeleteParams>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
     if *read_only {
-        return Ok(Json(ApiResponse::error(
-            "Server is in read-only mode. Cannot delete queries.".to_string(),
-        )));
+        return Err(ApiError::read_only("delete queries"));
+    }
+
+    let _definition = registry.lock_definition(ComponentKind::Query, &id).await;
+
+    if headers.contains_key(header::IF_MATCH) {
+        let current = query_etag(&core, &id).await;
+        if !if_match_satisfied(&headers, current.as_deref()) {
+            log::info!("Rejecting delete of query '{id}': If-Match precondition failed");
+            return Err(ApiError::precondition_failed(format!(
+                "Query '{id}' changed since the given ETag was read"
+            )));
+        }
+    }
+
+    if params.soft {
+        return disable_component(
+            &core,
+            &registry,
+            &config_persistence,
+            ComponentKind::Query,
+            &id,
+        )
+        .await;
     }
 
     match core.remove_query(&id).await {
         Ok(_) => {
-            persist_after_operation(&config_persistence, "deleting query").await;
+            registry.remove(ComponentKind::Query, &id).await;
+            result_logs.evict(&id).await;
+            let mutation = Mutation::Remove {
+                kind: ComponentKind::Query,
+                id: id.clone(),
+            };
+            persist_after_operation(&config_persistence, mutation).await;
 
             Ok(Json(ApiResponse::success(StatusResponse {
                 message: "Query deleted successfully".to_string(),
@@ -537,7 +1623,7 @@ pub async fn delete_query(
         }
         Err(e) => {
             log::error!("Failed to delete query: {e}");
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(e.into())
         }
     }
 }
@@ -552,26 +1638,30 @@ pub async fn delete_query(
     responses(
         (status = 200, description = "Query started successfully", body = ApiResponse),
         (status = 404, description = "Query not found"),
+        (status = 409, description = "Query is disabled"),
         (status = 500, description = "Internal server error"),
     ),
     tag = "Queries"
 )]
 pub async fn start_query(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if registry.is_disabled(ComponentKind::Query, &id).await {
+        return Err(ApiError::conflict(format!(
+            "Query '{id}' is disabled; enable it with POST /queries/{id}/enable"
+        )));
+    }
+
     match core.start_query(&id).await {
-        Ok(_) => Ok(Json(ApiResponse::success(StatusResponse {
-            message: "Query started successfully".to_string(),
-        }))),
-        Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("not found") {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                Ok(Json(ApiResponse::error(error_msg)))
-            }
+        Ok(_) => {
+            registry.mark_started(ComponentKind::Query, &id).await;
+            Ok(Json(ApiResponse::success(StatusResponse {
+                message: "Query started successfully".to_string(),
+            })))
         }
+        Err(e) => Err(e.into()),
     }
 }
 
@@ -591,21 +1681,120 @@ pub async fn start_query(
 )]
 pub async fn stop_query(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
     match core.stop_query(&id).await {
-        Ok(_) => Ok(Json(ApiResponse::success(StatusResponse {
-            message: "Query stopped successfully".to_string(),
-        }))),
-        Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("not found") {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                Ok(Json(ApiResponse::error(error_msg)))
-            }
+        Ok(_) => {
+            registry.reset_running(ComponentKind::Query, &id).await;
+            Ok(Json(ApiResponse::success(StatusResponse {
+                message: "Query stopped successfully".to_string(),
+            })))
         }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Restore a soft-deleted query
+#[utoipa::path(
+    post,
+    path = "/queries/{id}/restore",
+    params(
+        ("id" = String, Path, description = "Query ID")
+    ),
+    responses(
+        (status = 200, description = "Query restored successfully", body = ApiResponse),
+        (status = 404, description = "Query not found"),
+    ),
+    tag = "Queries"
+)]
+pub async fn restore_query(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("restore queries"));
+    }
+
+    enable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Query,
+        &id,
+    )
+    .await
+}
+
+/// Clear the disabled flag of a query and start it if auto_start is set
+#[utoipa::path(
+    post,
+    path = "/queries/{id}/enable",
+    params(
+        ("id" = String, Path, description = "Query ID")
+    ),
+    responses(
+        (status = 200, description = "Query enabled successfully", body = ApiResponse),
+        (status = 404, description = "Query not found"),
+    ),
+    tag = "Queries"
+)]
+pub async fn enable_query(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("enable queries"));
+    }
+
+    enable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Query,
+        &id,
+    )
+    .await
+}
+
+/// Stop a query and keep it from starting, including across restarts
+#[utoipa::path(
+    post,
+    path = "/queries/{id}/disable",
+    params(
+        ("id" = String, Path, description = "Query ID")
+    ),
+    responses(
+        (status = 200, description = "Query disabled successfully", body = ApiResponse),
+        (status = 404, description = "Query not found"),
+    ),
+    tag = "Queries"
+)]
+pub async fn disable_query(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("disable queries"));
     }
+
+    disable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Query,
+        &id,
+    )
+    .await
 }
 
 /// Get current results of a query
@@ -625,17 +1814,20 @@ pub async fn stop_query(
 pub async fn get_query_results(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<Vec<serde_json::Value>>>, StatusCode> {
+) -> Result<Json<ApiResponse<Vec<serde_json::Value>>>, ApiError> {
     match core.get_query_results(&id).await {
         Ok(results) => Ok(Json(ApiResponse::success(results))),
-        Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("not found") {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                Ok(Json(ApiResponse::error(error_msg)))
-            }
-        }
+        Err(e) => Err(query_results_error(e)),
+    }
+}
+
+/// Reading the results of a query that exists only fails because it is not
+/// running, which the caller can fix.
+pub(crate) fn query_results_error(e: impl Into<ApiError>) -> ApiError {
+    let error = e.into();
+    match error.code {
+        ErrorCode::NotFound => error,
+        _ => ApiError::validation(error.message),
     }
 }
 
@@ -651,12 +1843,16 @@ pub async fn get_query_results(
 )]
 pub async fn list_reactions(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
 ) -> Json<ApiResponse<Vec<ComponentListItem>>> {
     let reactions = core.list_reactions().await.unwrap_or_default();
-    let items: Vec<ComponentListItem> = reactions
-        .into_iter()
-        .map(|(id, status)| ComponentListItem { id, status })
-        .collect();
+    let mut items = Vec::with_capacity(reactions.len());
+    for (id, status) in reactions {
+        items.push(reaction_list_item(&registry, id, status).await);
+    }
+    for (id, error) in registry.failed(ComponentKind::Reaction).await {
+        items.push(failed_list_item(&registry, ComponentKind::Reaction, id, error).await);
+    }
 
     Json(ApiResponse::success(items))
 }
@@ -683,6 +1879,7 @@ pub async fn list_reactions(
     responses(
         (status = 200, description = "Reaction created successfully", body = ApiResponse),
         (status = 400, description = "Invalid reaction configuration"),
+        (status = 409, description = "Reaction already exists, or the server is read-only"),
         (status = 500, description = "Internal server error"),
     ),
     tag = "Reactions"
@@ -691,12 +1888,11 @@ pub async fn create_reaction_handler(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
     Extension(read_only): Extension<Arc<bool>>,
     Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Json(config_json): Json<serde_json::Value>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
     if *read_only {
-        return Ok(Json(ApiResponse::error(
-            "Server is in read-only mode. Cannot create reactions.".to_string(),
-        )));
+        return Err(ApiError::read_only("create reactions"));
     }
 
     // Parse the JSON into ReactionConfig (tagged enum)
@@ -704,23 +1900,37 @@ pub async fn create_reaction_handler(
         Ok(c) => c,
         Err(e) => {
             log::error!("Failed to parse reaction config: {e}");
-            return Ok(Json(ApiResponse::error(format!(
+            return Err(ApiError::validation(format!(
                 "Invalid reaction configuration: {e}"
-            ))));
+            )));
         }
     };
 
+    if config.group().is_some() {
+        let mut reactions = registry.reaction_configs().await;
+        reactions.retain(|r| r.id() != config.id());
+        reactions.push(config.clone());
+        if let Err(e) = crate::config::types::validate_reaction_groups(&reactions) {
+            return Err(ApiError::validation(e.to_string()));
+        }
+    }
+
     let reaction_id = config.id().to_string();
-    let auto_start = config.auto_start();
+    // Group members are started by the group supervisor
+    let auto_start = config.auto_start() && config.group().is_none();
+    let registry_config = config.clone();
+    let mut config = config;
+    if config.wait_for_queries_ms().is_some() || config.group().is_some() {
+        // Started below once its queries are running, or by the group supervisor
+        config.set_auto_start(false);
+    }
 
     // Create the reaction instance using the factory function
     let reaction = match create_reaction(config) {
         Ok(r) => r,
         Err(e) => {
             log::error!("Failed to create reaction instance: {e}");
-            return Ok(Json(ApiResponse::error(format!(
-                "Failed to create reaction: {e}"
-            ))));
+            return Err(ApiError::from(e).with_context("Failed to create reaction"));
         }
     };
 
@@ -728,38 +1938,52 @@ pub async fn create_reaction_handler(
     match core.add_reaction(reaction).await {
         Ok(_) => {
             log::info!("Reaction '{reaction_id}' created successfully");
+            let wait_for_queries_ms = registry_config.wait_for_queries_ms();
+            let queries = registry_config.queries().to_vec();
+            registry.insert_reaction(registry_config.clone()).await;
 
             // Auto-start if configured
             if auto_start {
-                if let Err(e) = core.start_reaction(&reaction_id).await {
+                if let Some(wait_ms) = wait_for_queries_ms {
+                    crate::reaction_gate::spawn_gated_start(
+                        core.clone(),
+                        registry.clone(),
+                        reaction_id.clone(),
+                        queries,
+                        std::time::Duration::from_millis(wait_ms),
+                    );
+                } else if let Err(e) = core.start_reaction(&reaction_id).await {
                     log::warn!("Failed to auto-start reaction '{reaction_id}': {e}");
                 }
             }
 
-            persist_after_operation(&config_persistence, "creating reaction").await;
+            let mutation = Mutation::PutReaction {
+                config: registry_config,
+            };
+            persist_after_operation(&config_persistence, mutation).await;
 
             Ok(Json(ApiResponse::success(StatusResponse {
                 message: format!("Reaction '{reaction_id}' created successfully"),
             })))
         }
         Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("already exists") {
+            let error = ApiError::from(e);
+            if error.code == ErrorCode::Conflict {
                 log::info!("Reaction '{reaction_id}' already exists");
-                return Ok(Json(ApiResponse::success(StatusResponse {
-                    message: format!("Reaction '{reaction_id}' already exists"),
-                })));
+                return Err(ApiError::conflict(format!(
+                    "Reaction '{reaction_id}' already exists; delete it first to recreate it"
+                )));
             }
-            log::error!("Failed to add reaction: {e}");
-            Ok(Json(ApiResponse::error(error_msg)))
+            log::error!("Failed to add reaction: {error}");
+            Err(error)
         }
     }
 }
 
 /// Get reaction status by ID
 ///
-/// Note: Reaction configs are not stored - reactions are instances.
-/// This endpoint returns the reaction status instead.
+/// Returns the same summary as the list endpoint: status, kind, uptime and the
+/// queries the reaction subscribes to.
 #[utoipa::path(
     get,
     path = "/reactions/{id}",
@@ -767,18 +1991,36 @@ pub async fn create_reaction_handler(
         ("id" = String, Path, description = "Reaction ID")
     ),
     responses(
-        (status = 200, description = "Reaction found", body = ApiResponse),
+        (status = 200, description = "Reaction found", body = ApiResponse,
+            headers(("ETag" = String, description = "Revision of the reaction definition"))),
         (status = 404, description = "Reaction not found"),
     ),
     tag = "Reactions"
 )]
 pub async fn get_reaction(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<ComponentListItem>>, StatusCode> {
+) -> Result<([(header::HeaderName, String); 1], Json<ApiResponse<ComponentListItem>>), ApiError>
+{
     match core.get_reaction_status(&id).await {
-        Ok(status) => Ok(Json(ApiResponse::success(ComponentListItem { id, status }))),
-        Err(_) => Err(StatusCode::NOT_FOUND),
+        Ok(status) => {
+            let etag = reaction_etag(&core, &registry, &id)
+                .await
+                .ok_or_else(|| ApiError::not_found(format!("Reaction '{id}' not found")))?;
+            let item = reaction_list_item(&registry, id, status).await;
+            Ok(([(header::ETAG, etag)], Json(ApiResponse::success(item))))
+        }
+        Err(_) => match registry.build_error(ComponentKind::Reaction, &id).await {
+            Some(error) => {
+                let etag = reaction_etag(&core, &registry, &id)
+                    .await
+                    .ok_or_else(|| ApiError::not_found(format!("Reaction '{id}' not found")))?;
+                let item = failed_list_item(&registry, ComponentKind::Reaction, id, error).await;
+                Ok(([(header::ETAG, etag)], Json(ApiResponse::success(item))))
+            }
+            None => Err(ApiError::not_found(format!("Reaction '{id}' not found"))),
+        },
     }
 }
 
@@ -787,10 +2029,14 @@ pub async fn get_reaction(
     delete,
     path = "/reactions/{id}",
     params(
-        ("id" = String, Path, description = "Reaction ID")
+        ("id" = String, Path, description = "Reaction ID"),
+        ("If-Match" = Option<String>, Header, description = "Only delete if the reaction still has this ETag"),
+        DeleteParams
     ),
     responses(
         (status = 200, description = "Reaction deleted successfully", body = ApiResponse),
+        (status = 404, description = "Reaction not found"),
+        (status = 412, description = "Reaction changed since the given ETag was read"),
     ),
     tag = "Reactions"
 )]
@@ -798,17 +2044,63 @@ pub async fn delete_reaction(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
     Extension(read_only): Extension<Arc<bool>>,
     Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+    Query(params): Query<DeleteParams>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
     if *read_only {
-        return Ok(Json(ApiResponse::error(
-            "Server is in read-only mode. Cannot delete reactions.".to_string(),
-        )));
+        return Err(ApiError::read_only("delete reactions"));
+    }
+
+    let _definition = registry.lock_definition(ComponentKind::Reaction, &id).await;
+
+    if headers.contains_key(header::IF_MATCH) {
+        let current = reaction_etag(&core, &registry, &id).await;
+        if !if_match_satisfied(&headers, current.as_deref()) {
+            log::info!("Rejecting delete of reaction '{id}': If-Match precondition failed");
+            return Err(ApiError::precondition_failed(format!(
+                "Reaction '{id}' changed since the given ETag was read"
+            )));
+        }
+    }
+
+    if params.soft {
+        return disable_component(
+            &core,
+            &registry,
+            &config_persistence,
+            ComponentKind::Reaction,
+            &id,
+        )
+        .await;
+    }
+
+    // A reaction that failed to build was never added to DrasiLib
+    if registry
+        .build_error(ComponentKind::Reaction, &id)
+        .await
+        .is_some()
+    {
+        registry.remove(ComponentKind::Reaction, &id).await;
+        let mutation = Mutation::Remove {
+            kind: ComponentKind::Reaction,
+            id: id.clone(),
+        };
+        persist_after_operation(&config_persistence, mutation).await;
+        return Ok(Json(ApiResponse::success(StatusResponse {
+            message: "Reaction deleted successfully".to_string(),
+        })));
     }
 
     match core.remove_reaction(&id).await {
         Ok(_) => {
-            persist_after_operation(&config_persistence, "deleting reaction").await;
+            registry.remove(ComponentKind::Reaction, &id).await;
+            let mutation = Mutation::Remove {
+                kind: ComponentKind::Reaction,
+                id: id.clone(),
+            };
+            persist_after_operation(&config_persistence, mutation).await;
 
             Ok(Json(ApiResponse::success(StatusResponse {
                 message: "Reaction deleted successfully".to_string(),
@@ -816,7 +2108,7 @@ pub async fn delete_reaction(
         }
         Err(e) => {
             log::error!("Failed to delete reaction: {e}");
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(e.into())
         }
     }
 }
@@ -831,26 +2123,33 @@ pub async fn delete_reaction(
     responses(
         (status = 200, description = "Reaction started successfully", body = ApiResponse),
         (status = 404, description = "Reaction not found"),
+        (status = 409, description = "Reaction is disabled"),
         (status = 500, description = "Internal server error"),
     ),
     tag = "Reactions"
 )]
 pub async fn start_reaction(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if registry.is_disabled(ComponentKind::Reaction, &id).await {
+        return Err(ApiError::conflict(format!(
+            "Reaction '{id}' is disabled; enable it with POST /reactions/{id}/enable"
+        )));
+    }
+
     match core.start_reaction(&id).await {
-        Ok(_) => Ok(Json(ApiResponse::success(StatusResponse {
-            message: "Reaction started successfully".to_string(),
-        }))),
-        Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("not found") {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                Ok(Json(ApiResponse::error(error_msg)))
-            }
+        Ok(_) => {
+            registry.mark_started(ComponentKind::Reaction, &id).await;
+            registry
+                .set_stopped_by_api(ComponentKind::Reaction, &id, false)
+                .await;
+            Ok(Json(ApiResponse::success(StatusResponse {
+                message: "Reaction started successfully".to_string(),
+            })))
         }
+        Err(e) => Err(e.into()),
     }
 }
 
@@ -870,19 +2169,122 @@ pub async fn start_reaction(
 )]
 pub async fn stop_reaction(
     Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
     Path(id): Path<String>,
-) -> Result<Json<ApiResponse<StatusResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
     match core.stop_reaction(&id).await {
-        Ok(_) => Ok(Json(ApiResponse::success(StatusResponse {
-            message: "Reaction stopped successfully".to_string(),
-        }))),
-        Err(e) => {
-            let error_msg = e.to_string();
-            if error_msg.contains("not found") {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                Ok(Json(ApiResponse::error(error_msg)))
-            }
+        Ok(_) => {
+            registry.reset_running(ComponentKind::Reaction, &id).await;
+            // Keeps a reaction group from starting it again
+            registry
+                .set_stopped_by_api(ComponentKind::Reaction, &id, true)
+                .await;
+            Ok(Json(ApiResponse::success(StatusResponse {
+                message: "Reaction stopped successfully".to_string(),
+            })))
         }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Restore a soft-deleted reaction
+#[utoipa::path(
+    post,
+    path = "/reactions/{id}/restore",
+    params(
+        ("id" = String, Path, description = "Reaction ID")
+    ),
+    responses(
+        (status = 200, description = "Reaction restored successfully", body = ApiResponse),
+        (status = 404, description = "Reaction not found"),
+    ),
+    tag = "Reactions"
+)]
+pub async fn restore_reaction(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("restore reactions"));
+    }
+
+    enable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Reaction,
+        &id,
+    )
+    .await
+}
+
+/// Clear the disabled flag of a reaction and start it if auto_start is set
+#[utoipa::path(
+    post,
+    path = "/reactions/{id}/enable",
+    params(
+        ("id" = String, Path, description = "Reaction ID")
+    ),
+    responses(
+        (status = 200, description = "Reaction enabled successfully", body = ApiResponse),
+        (status = 404, description = "Reaction not found"),
+    ),
+    tag = "Reactions"
+)]
+pub async fn enable_reaction(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("enable reactions"));
+    }
+
+    enable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Reaction,
+        &id,
+    )
+    .await
+}
+
+/// Stop a reaction and keep it from starting, including across restarts
+#[utoipa::path(
+    post,
+    path = "/reactions/{id}/disable",
+    params(
+        ("id" = String, Path, description = "Reaction ID")
+    ),
+    responses(
+        (status = 200, description = "Reaction disabled successfully", body = ApiResponse),
+        (status = 404, description = "Reaction not found"),
+    ),
+    tag = "Reactions"
+)]
+pub async fn disable_reaction(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<StatusResponse>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("disable reactions"));
     }
+
+    disable_component(
+        &core,
+        &registry,
+        &config_persistence,
+        ComponentKind::Reaction,
+        &id,
+    )
+    .await
 }
@@ -0,0 +1,354 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `Idempotency-Key` support for the create endpoints.
+//!
+//! A client that retries `POST /sources`, `POST /queries` or `POST /reactions`
+//! with the same `Idempotency-Key` header gets the original response back
+//! instead of the request being executed again. Reusing a key with a different
+//! request body is rejected, as is a retry that arrives while the first request
+//! is still being processed.
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use super::error::ApiError;
+use super::handlers::ApiResponse;
+
+/// Request header carrying the client-chosen idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Response header set on responses replayed from the cache
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Paths whose POST requests honor idempotency keys
+const CREATE_PATHS: [&str; 3] = ["/sources", "/queries", "/reactions"];
+/// Upper bound on request/response bodies buffered by the middleware
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+struct CachedResponse {
+    status: StatusCode,
+    content_type: Option<HeaderValue>,
+    body: Bytes,
+}
+
+struct Entry {
+    request_body: Bytes,
+    created_at: Instant,
+    /// `None` while the original request is still in flight
+    response: Option<CachedResponse>,
+}
+
+enum Begin {
+    Proceed,
+    Replay(Response),
+    Reject(StatusCode, ApiError),
+}
+
+/// In-memory store of responses keyed by path and idempotency key.
+#[derive(Clone)]
+pub struct IdempotencyStore {
+    entries: Arc<Mutex<HashMap<(String, String), Entry>>>,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+impl Default for IdempotencyStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL, DEFAULT_MAX_ENTRIES)
+    }
+}
+
+impl IdempotencyStore {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+            max_entries,
+        }
+    }
+
+    async fn begin(&self, key: &(String, String), request_body: &Bytes) -> Begin {
+        let mut entries = self.entries.lock().await;
+        let ttl = self.ttl;
+        entries.retain(|_, entry| entry.created_at.elapsed() < ttl);
+
+        if let Some(entry) = entries.get(key) {
+            if entry.request_body != *request_body {
+                return Begin::Reject(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    ApiError::validation(format!(
+                        "Idempotency key '{}' was already used with a different request body",
+                        key.1
+                    )),
+                );
+            }
+            return match &entry.response {
+                Some(cached) => {
+                    let mut response = Response::new(Body::from(cached.body.clone()));
+                    *response.status_mut() = cached.status;
+                    if let Some(content_type) = &cached.content_type {
+                        response
+                            .headers_mut()
+                            .insert(header::CONTENT_TYPE, content_type.clone());
+                    }
+                    response.headers_mut().insert(
+                        IDEMPOTENT_REPLAYED_HEADER,
+                        HeaderValue::from_static("true"),
+                    );
+                    Begin::Replay(response)
+                }
+                None => Begin::Reject(
+                    StatusCode::CONFLICT,
+                    ApiError::conflict(format!(
+                        "A request with idempotency key '{}' is still being processed",
+                        key.1
+                    )),
+                ),
+            };
+        }
+
+        if entries.len() >= self.max_entries {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.created_at)
+                .map(|(k, _)| k.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key.clone(),
+            Entry {
+                request_body: request_body.clone(),
+                created_at: Instant::now(),
+                response: None,
+            },
+        );
+        Begin::Proceed
+    }
+
+    async fn complete(&self, key: &(String, String), response: CachedResponse) {
+        if let Some(entry) = self.entries.lock().await.get_mut(key) {
+            entry.response = Some(response);
+        }
+    }
+
+    async fn abandon(&self, key: &(String, String)) {
+        self.entries.lock().await.remove(key);
+    }
+}
+
+/// Error response with a status other than the one implied by its code
+fn reject(status: StatusCode, error: ApiError) -> Response {
+    let body = ApiResponse::<()>::error(error.message).with_code(error.code);
+    (status, Json(body)).into_response()
+}
+
+/// Middleware that replays create responses for repeated idempotency keys.
+///
+/// Server errors (5xx) are not cached, so a retry after a transient failure
+/// executes the request again.
+pub async fn idempotency_middleware(
+    State(store): State<IdempotencyStore>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST || !CREATE_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+    let key = match request
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(key) if !key.trim().is_empty() => key.trim().to_string(),
+        _ => return next.run(request).await,
+    };
+
+    let (parts, body) = request.into_parts();
+    let request_body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return reject(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                ApiError::validation(format!("Failed to read request body: {e}")),
+            )
+        }
+    };
+    let cache_key = (parts.uri.path().to_string(), key);
+
+    match store.begin(&cache_key, &request_body).await {
+        Begin::Proceed => {}
+        Begin::Replay(response) => {
+            log::debug!(
+                "Replaying response for idempotency key '{}' on {}",
+                cache_key.1,
+                cache_key.0
+            );
+            return response;
+        }
+        Begin::Reject(status, error) => return reject(status, error),
+    }
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(request_body)))
+        .await;
+
+    let (response_parts, response_body) = response.into_parts();
+    let body = match to_bytes(response_body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            store.abandon(&cache_key).await;
+            log::error!("Failed to buffer response for idempotency key: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    if response_parts.status.is_server_error() {
+        store.abandon(&cache_key).await;
+    } else {
+        store
+            .complete(
+                &cache_key,
+                CachedResponse {
+                    status: response_parts.status,
+                    content_type: response_parts.headers.get(header::CONTENT_TYPE).cloned(),
+                    body: body.clone(),
+                },
+            )
+            .await;
+    }
+
+    Response::from_parts(response_parts, Body::from(body))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::post, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    fn counting_router(counter: Arc<AtomicUsize>) -> Router {
+        Router::new()
+            .route(
+                "/sources",
+                post(move |body: String| {
+                    let counter = counter.clone();
+                    async move {
+                        let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                        Json(serde_json::json!({ "call": n, "body": body }))
+                    }
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                IdempotencyStore::default(),
+                idempotency_middleware,
+            ))
+    }
+
+    fn post_request(key: Option<&str>, body: &str) -> Request {
+        let mut builder = axum::http::Request::builder()
+            .method("POST")
+            .uri("/sources");
+        if let Some(key) = key {
+            builder = builder.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_same_key_replays_original_response() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let router = counting_router(counter.clone());
+
+        let first = router
+            .clone()
+            .oneshot(post_request(Some("abc"), "{}"))
+            .await
+            .unwrap();
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        assert_eq!(body_json(first).await["call"], 1);
+
+        let second = router
+            .clone()
+            .oneshot(post_request(Some("abc"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(second.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(body_json(second).await["call"], 1);
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_key_are_not_cached() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let router = counting_router(counter.clone());
+
+        router.clone().oneshot(post_request(None, "{}")).await.unwrap();
+        router.clone().oneshot(post_request(None, "{}")).await.unwrap();
+
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_key_reuse_with_different_body_is_rejected() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let router = counting_router(counter.clone());
+
+        router
+            .clone()
+            .oneshot(post_request(Some("abc"), r#"{"id":"a"}"#))
+            .await
+            .unwrap();
+        let response = router
+            .clone()
+            .oneshot(post_request(Some("abc"), r#"{"id":"b"}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let json = body_json(response).await;
+        assert_eq!(json["success"], false);
+        assert_eq!(json["code"], "VALIDATION");
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_keys_are_executed_again() {
+        let store = IdempotencyStore::new(Duration::from_millis(0), 10);
+        let key = ("/sources".to_string(), "abc".to_string());
+        let body = Bytes::from_static(b"{}");
+
+        assert!(matches!(store.begin(&key, &body).await, Begin::Proceed));
+        assert!(matches!(store.begin(&key, &body).await, Begin::Proceed));
+    }
+}
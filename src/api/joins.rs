@@ -0,0 +1,352 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation of a query's synthetic joins.
+//!
+//! A join's `id` is the relationship type it creates between nodes whose key
+//! properties match, so a join is only useful if the query pattern matches
+//! that relationship type, and every key needs both a label and a property.
+
+use axum::{
+    extract::{Extension, Path},
+    http::HeaderMap,
+    response::Json,
+};
+use drasi_lib::config::{QueryJoinConfig, QueryLanguage};
+use drasi_lib::queries::LabelExtractor;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use super::error::ApiError;
+use super::etag::{compute_etag, if_match_satisfied};
+use super::handlers::{persist_after_operation, ApiResponse};
+use crate::api::models::QueryLanguageDto;
+use crate::journal::Mutation;
+use crate::persistence::ConfigPersistence;
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+/// The `strict_joins` server setting: reject queries whose joins have issues
+/// instead of only logging them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictJoins(pub bool);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JoinIssueSeverity {
+    /// The join has no effect on the query
+    Warning,
+    /// The join or the query cannot work as written
+    Error,
+}
+
+/// A problem found in a query's joins
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JoinIssue {
+    pub severity: JoinIssueSeverity,
+    /// The join concerned; absent for problems with the query itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub join_id: Option<String>,
+    pub message: String,
+}
+
+/// A query text and the joins to check against it
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ValidateJoinsRequest {
+    pub query: String,
+    #[serde(default, alias = "queryLanguage")]
+    pub query_language: QueryLanguageDto,
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub joins: Vec<QueryJoinConfig>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JoinValidationResult {
+    /// False if any issue is an error
+    pub valid: bool,
+    pub issues: Vec<JoinIssue>,
+}
+
+impl From<Vec<JoinIssue>> for JoinValidationResult {
+    fn from(issues: Vec<JoinIssue>) -> Self {
+        Self {
+            valid: !issues
+                .iter()
+                .any(|issue| issue.severity == JoinIssueSeverity::Error),
+            issues,
+        }
+    }
+}
+
+/// Check `joins` against the query they belong to.
+pub fn validate_joins(
+    query: &str,
+    language: &QueryLanguage,
+    joins: &[QueryJoinConfig],
+) -> Vec<JoinIssue> {
+    let mut issues = Vec::new();
+    if joins.is_empty() {
+        return issues;
+    }
+
+    let rel_labels: HashSet<String> = match LabelExtractor::extract_labels(query, language) {
+        Ok(labels) => labels.relation_labels.into_iter().collect(),
+        Err(e) => {
+            issues.push(JoinIssue {
+                severity: JoinIssueSeverity::Error,
+                join_id: None,
+                message: format!("Failed to parse query for join validation: {e}"),
+            });
+            return issues;
+        }
+    };
+
+    for join in joins {
+        if !rel_labels.contains(&join.id) {
+            issues.push(JoinIssue {
+                severity: JoinIssueSeverity::Warning,
+                join_id: Some(join.id.clone()),
+                message: format!(
+                    "Join id '{}' does not appear as a relationship label in the query pattern",
+                    join.id
+                ),
+            });
+        }
+        for key in &join.keys {
+            if key.label.trim().is_empty() || key.property.trim().is_empty() {
+                issues.push(JoinIssue {
+                    severity: JoinIssueSeverity::Error,
+                    join_id: Some(join.id.clone()),
+                    message: format!(
+                        "Join '{}' has an empty label or property (label='{}', property='{}')",
+                        join.id, key.label, key.property
+                    ),
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Validate join definitions against a query without creating it
+///
+/// Meant for editors that check joins while a query is being written. Nothing
+/// is changed on the server.
+#[utoipa::path(
+    post,
+    path = "/queries/validate-joins",
+    request_body = ValidateJoinsRequest,
+    responses(
+        (status = 200, description = "Validation result", body = ApiResponse),
+    ),
+    tag = "Queries"
+)]
+pub async fn validate_joins_handler(
+    Json(request): Json<ValidateJoinsRequest>,
+) -> Json<ApiResponse<JoinValidationResult>> {
+    let issues = validate_joins(
+        &request.query,
+        &request.query_language.into(),
+        &request.joins,
+    );
+    Json(ApiResponse::success(issues.into()))
+}
+
+/// Get the joins of a query
+#[utoipa::path(
+    get,
+    path = "/queries/{id}/joins",
+    params(
+        ("id" = String, Path, description = "Query ID")
+    ),
+    responses(
+        (status = 200, description = "The query's joins", body = ApiResponse),
+        (status = 404, description = "Query not found"),
+    ),
+    tag = "Queries"
+)]
+pub async fn get_query_joins(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<Vec<QueryJoinConfig>>>, ApiError> {
+    match core.get_query_config(&id).await {
+        Ok(config) => Ok(Json(ApiResponse::success(config.joins.unwrap_or_default()))),
+        Err(_) => Err(ApiError::not_found(format!("Query '{id}' not found"))),
+    }
+}
+
+/// Replace the joins of a query
+///
+/// The rest of the query definition is kept. The query is recreated with the
+/// new joins, as when it is replaced through `PATCH /config`, so it starts
+/// again from bootstrap. An empty list removes all joins. Problems with the
+/// joins are logged, or rejected with 400 under the `strict_joins` setting.
+/// Joins are part of the query definition, so `If-Match` takes the query's
+/// ETag from `GET /queries/{id}`.
+#[utoipa::path(
+    put,
+    path = "/queries/{id}/joins",
+    params(
+        ("id" = String, Path, description = "Query ID"),
+        ("If-Match" = Option<String>, Header, description = "Only replace the joins if the query still has this ETag"),
+    ),
+    request_body = Vec<Object>,
+    responses(
+        (status = 200, description = "Joins replaced", body = ApiResponse),
+        (status = 400, description = "Invalid joins in strict mode", body = ApiResponse),
+        (status = 404, description = "Query not found", body = ApiResponse),
+        (status = 409, description = "Server is read-only", body = ApiResponse),
+        (status = 412, description = "Query changed since the given ETag was read", body = ApiResponse),
+        (status = 500, description = "Internal server error", body = ApiResponse),
+    ),
+    tag = "Queries"
+)]
+pub async fn replace_query_joins(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(read_only): Extension<Arc<bool>>,
+    Extension(config_persistence): Extension<Option<Arc<ConfigPersistence>>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Extension(StrictJoins(strict_joins)): Extension<StrictJoins>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(joins): Json<Vec<QueryJoinConfig>>,
+) -> Result<Json<ApiResponse<Vec<QueryJoinConfig>>>, ApiError> {
+    if *read_only {
+        return Err(ApiError::read_only("change queries"));
+    }
+
+    let _definition = registry.lock_definition(ComponentKind::Query, &id).await;
+
+    let previous = match core.get_query_config(&id).await {
+        Ok(config) => config,
+        Err(_) => {
+            return Err(ApiError::not_found(format!("Query '{id}' not found")));
+        }
+    };
+    if !if_match_satisfied(&headers, Some(&compute_etag(&previous))) {
+        log::info!("Rejecting joins of query '{id}': If-Match precondition failed");
+        return Err(ApiError::precondition_failed(format!(
+            "Query '{id}' changed since the given ETag was read"
+        )));
+    }
+
+    let issues = validate_joins(&previous.query, &previous.query_language, &joins);
+    if !issues.is_empty() {
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        if strict_joins {
+            return Err(ApiError::validation(format!(
+                "Invalid joins in query '{id}': {}",
+                messages.join("; ")
+            )));
+        }
+        log::warn!("[JOIN-VALIDATION] Query '{id}': {}", messages.join("; "));
+    }
+
+    let mut config = previous.clone();
+    config.joins = Some(joins.clone()).filter(|joins| !joins.is_empty());
+    // auto_start may only be off because the query is disabled; the file
+    // keeps the configured value
+    let suppressed = registry.is_query_auto_start_suppressed(&id).await;
+    let mut journaled = config.clone();
+    if suppressed {
+        journaled.auto_start = true;
+    }
+
+    let replaced = match core.remove_query(&id).await {
+        Ok(_) => core.add_query(config).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = replaced {
+        log::error!("Failed to replace joins of query '{id}': {e}");
+        if core.get_query_config(&id).await.is_err() {
+            if let Err(e) = core.add_query(previous).await {
+                log::error!("Failed to restore query '{id}': {e}");
+            }
+        }
+        let context = format!("Failed to replace joins of query '{id}'");
+        return Err(ApiError::from(e).with_context(context));
+    }
+    registry.reset_running(ComponentKind::Query, &id).await;
+    if suppressed {
+        registry.suppress_query_auto_start(&id).await;
+    }
+
+    log::info!("Replaced joins of query '{id}' ({} join(s))", joins.len());
+    persist_after_operation(&config_persistence, Mutation::PutQuery { config: journaled }).await;
+    Ok(Json(ApiResponse::success(joins)))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use drasi_lib::config::QueryJoinKeyConfig;
+
+    fn join(id: &str, keys: &[(&str, &str)]) -> QueryJoinConfig {
+        QueryJoinConfig {
+            id: id.to_string(),
+            keys: keys
+                .iter()
+                .map(|(label, property)| QueryJoinKeyConfig {
+                    label: label.to_string(),
+                    property: property.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    const QUERY: &str = "MATCH (o:Order)-[:ORDER_TO_DRIVER]->(d:Driver) RETURN o.id, d.name";
+
+    #[test]
+    fn test_valid_join_has_no_issues() {
+        let joins = [join(
+            "ORDER_TO_DRIVER",
+            &[("Order", "driverId"), ("Driver", "id")],
+        )];
+
+        let issues = validate_joins(QUERY, &QueryLanguageDto::Cypher.into(), &joins);
+
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_unused_join_and_empty_key_are_reported() {
+        let joins = [
+            join("ORDER_TO_DRIVRE", &[("Order", "driverId"), ("Driver", "id")]),
+            join("ORDER_TO_DRIVER", &[("Order", ""), ("Driver", "id")]),
+        ];
+
+        let result: JoinValidationResult =
+            validate_joins(QUERY, &QueryLanguageDto::Cypher.into(), &joins).into();
+
+        assert!(!result.valid);
+        assert_eq!(result.issues.len(), 2);
+        assert_eq!(result.issues[0].severity, JoinIssueSeverity::Warning);
+        assert_eq!(result.issues[0].join_id.as_deref(), Some("ORDER_TO_DRIVRE"));
+        assert_eq!(result.issues[1].severity, JoinIssueSeverity::Error);
+    }
+
+    #[test]
+    fn test_unparseable_query_is_an_error() {
+        let joins = [join("J", &[("A", "x"), ("B", "y")])];
+
+        let result: JoinValidationResult =
+            validate_joins("MATCH (", &QueryLanguageDto::Cypher.into(), &joins).into();
+
+        assert!(!result.valid);
+        assert_eq!(result.issues[0].join_id, None);
+    }
+}
@@ -15,7 +15,9 @@
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod api_query_joins_tests {
+    use crate::api::error::ErrorCode;
     use crate::api::handlers::*;
+    use crate::api::joins::{get_query_joins, replace_query_joins, StrictJoins};
     use crate::persistence::ConfigPersistence;
     use axum::{Extension, Json};
     use drasi_lib::{
@@ -78,6 +80,8 @@ mod api_query_joins_tests {
             Extension(core.clone()),
             Extension(read_only),
             Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
             Json(query_config.clone()),
         )
         .await;
@@ -136,6 +140,8 @@ mod api_query_joins_tests {
             Extension(core.clone()),
             Extension(read_only),
             Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
             Json(query_config.clone()),
         )
         .await;
@@ -163,6 +169,8 @@ mod api_query_joins_tests {
             Extension(core.clone()),
             Extension(read_only),
             Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
             Json(query_config.clone()),
         )
         .await;
@@ -191,6 +199,8 @@ mod api_query_joins_tests {
             Extension(core.clone()),
             Extension(read_only),
             Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
             Json(query_config.clone()),
         )
         .await;
@@ -234,6 +244,8 @@ mod api_query_joins_tests {
             Extension(core.clone()),
             Extension(read_only),
             Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
             Json(query_config.clone()),
         )
         .await
@@ -248,7 +260,7 @@ mod api_query_joins_tests {
 
         assert!(get_result.is_ok());
 
-        let response = get_result.unwrap();
+        let (_, response) = get_result.unwrap();
         // Verify the response contains joins
         let json_response = serde_json::to_value(&response.0).unwrap();
         assert_eq!(json_response["success"], true);
@@ -352,19 +364,117 @@ mod api_query_joins_tests {
             Extension(core.clone()),
             Extension(read_only),
             Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
             Json(query_config),
         )
         .await;
 
-        assert!(result.is_ok());
-        let response = result.unwrap();
         // Should fail due to read-only mode
-        let json_response = serde_json::to_value(&response.0).unwrap();
-        assert_eq!(json_response["success"], false);
-        assert!(json_response["error"].is_string());
-        assert!(json_response["error"]
-            .as_str()
-            .unwrap()
-            .contains("read-only mode"));
+        let error = result.unwrap_err();
+        assert_eq!(error.code, ErrorCode::ReadOnly);
+        assert!(error.message.contains("read-only mode"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_mode_rejects_unused_join() {
+        let (core, read_only, config_persistence) = create_test_environment().await;
+
+        let join_config = QueryJoinConfig {
+            id: "NOT_IN_PATTERN".to_string(),
+            keys: vec![
+                QueryJoinKeyConfig {
+                    label: "NodeA".to_string(),
+                    property: "prop".to_string(),
+                },
+                QueryJoinKeyConfig {
+                    label: "NodeB".to_string(),
+                    property: "prop".to_string(),
+                },
+            ],
+        };
+
+        let query_config = Query::cypher("strict-joins-query")
+            .query("MATCH (a:NodeA)-[:TEST_JOIN]->(b:NodeB) RETURN a, b")
+            .from_source("source1")
+            .auto_start(false)
+            .with_joins(vec![join_config])
+            .build();
+
+        let result = create_query(
+            Extension(core.clone()),
+            Extension(read_only),
+            Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams { strict: true }),
+            Json(query_config),
+        )
+        .await;
+
+        let Err(error) = result else {
+            panic!("query with an unused join should be rejected in strict mode");
+        };
+        assert_eq!(error.status(), axum::http::StatusCode::BAD_REQUEST);
+        assert!(error.message.contains("NOT_IN_PATTERN"));
+        assert!(core.get_query_config("strict-joins-query").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replace_query_joins_via_api() {
+        let (core, read_only, config_persistence) = create_test_environment().await;
+        let registry = Arc::new(crate::registry::ComponentRegistry::new());
+
+        let query_config = Query::cypher("replace-joins-query")
+            .query("MATCH (a:NodeA)-[:TEST_JOIN]->(b:NodeB) RETURN a, b")
+            .from_source("source1")
+            .auto_start(false)
+            .build();
+        create_query(
+            Extension(core.clone()),
+            Extension(read_only.clone()),
+            Extension(config_persistence.clone()),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
+            Json(query_config),
+        )
+        .await
+        .unwrap();
+
+        let join_config = QueryJoinConfig {
+            id: "TEST_JOIN".to_string(),
+            keys: vec![
+                QueryJoinKeyConfig {
+                    label: "NodeA".to_string(),
+                    property: "key".to_string(),
+                },
+                QueryJoinKeyConfig {
+                    label: "NodeB".to_string(),
+                    property: "key".to_string(),
+                },
+            ],
+        };
+        let replaced = replace_query_joins(
+            Extension(core.clone()),
+            Extension(read_only),
+            Extension(config_persistence),
+            Extension(registry),
+            Extension(StrictJoins(true)),
+            axum::extract::Path("replace-joins-query".to_string()),
+            Json(vec![join_config]),
+        )
+        .await;
+        assert!(replaced.is_ok());
+
+        let joins = get_query_joins(
+            Extension(core.clone()),
+            axum::extract::Path("replace-joins-query".to_string()),
+        )
+        .await
+        .unwrap();
+        let json_response = serde_json::to_value(&joins.0).unwrap();
+        assert_eq!(json_response["data"][0]["id"], "TEST_JOIN");
+
+        let config = core.get_query_config("replace-joins-query").await.unwrap();
+        assert_eq!(config.query, "MATCH (a:NodeA)-[:TEST_JOIN]->(b:NodeB) RETURN a, b");
     }
 }
@@ -14,7 +14,10 @@
 
 //! DTO to domain model mapping service with value resolution.
 
-use super::resolver::{EnvironmentVariableResolver, ResolverError, SecretResolver, ValueResolver};
+use super::resolver::{
+    evaluate, EnvironmentVariableResolver, InterpolationResolver, ResolverError, SecretResolver,
+    ValueResolver,
+};
 use crate::api::models::ConfigValue;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -55,6 +58,7 @@ impl DtoMapper {
         let mut resolvers: HashMap<&'static str, Box<dyn ValueResolver>> = HashMap::new();
         resolvers.insert("EnvironmentVariable", Box::new(EnvironmentVariableResolver));
         resolvers.insert("Secret", Box::new(SecretResolver));
+        resolvers.insert("Interpolation", Box::new(InterpolationResolver));
 
         Self { resolvers }
     }
@@ -78,6 +82,14 @@ impl DtoMapper {
                 })?;
                 resolver.resolve_to_string(value)
             }
+
+            ConfigValue::Interpolation { .. } => {
+                let resolver = self
+                    .resolvers
+                    .get("Interpolation")
+                    .ok_or_else(|| ResolverError::NoResolverFound("Interpolation".to_string()))?;
+                resolver.resolve_to_string(value)
+            }
         }
     }
 
@@ -111,6 +123,12 @@ impl DtoMapper {
                     ResolverError::ParseError(format!("Failed to parse env var '{name}': {e}"))
                 })
             }
+
+            ConfigValue::Interpolation { expression } => {
+                evaluate(expression)?.parse::<T>().map_err(|e| {
+                    ResolverError::ParseError(format!("Failed to parse '${{{expression}}}': {e}"))
+                })
+            }
         }
     }
 
@@ -14,6 +14,7 @@
 
 //! Value resolvers for different ConfigValue reference types.
 
+use crate::api::models::config_value::is_function_call;
 use crate::api::models::ConfigValue;
 use thiserror::Error;
 
@@ -34,6 +35,9 @@ pub enum ResolverError {
 
     #[error("Failed to parse value: {0}")]
     ParseError(String),
+
+    #[error("Failed to evaluate '${{{0}}}': {1}")]
+    InterpolationError(String, String),
 }
 
 /// Trait for resolving a specific type of ConfigValue variant
@@ -74,6 +78,146 @@ impl ValueResolver for SecretResolver {
     }
 }
 
+/// Resolver for function calls: `${concat:a,b,...}` joins its arguments,
+/// `${base64:text}` base64-encodes its argument, and `${json:FILE#path}` reads
+/// a value from a JSON file, where `path` is a dot-separated list of keys and
+/// array indexes. Arguments may contain `${VAR}`, `${VAR:-default}` and
+/// further function calls.
+pub struct InterpolationResolver;
+
+impl ValueResolver for InterpolationResolver {
+    fn resolve_to_string(&self, value: &ConfigValue<String>) -> Result<String, ResolverError> {
+        match value {
+            ConfigValue::Interpolation { expression } => evaluate(expression),
+            _ => Err(ResolverError::WrongResolverType),
+        }
+    }
+}
+
+/// Evaluate the text inside a `${...}` reference.
+pub(crate) fn evaluate(inner: &str) -> Result<String, ResolverError> {
+    if !is_function_call(inner) {
+        let (name, default) = match inner.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (inner, None),
+        };
+        return std::env::var(name).or_else(|_| {
+            default
+                .map(str::to_string)
+                .ok_or_else(|| ResolverError::EnvVarNotFound(name.to_string()))
+        });
+    }
+
+    let fail = |message: String| ResolverError::InterpolationError(inner.to_string(), message);
+    let (function, arguments) = inner.split_once(':').unwrap_or((inner, ""));
+    match function {
+        "concat" => split_arguments(arguments)
+            .into_iter()
+            .map(expand)
+            .collect::<Result<Vec<_>, _>>()
+            .map(|parts| parts.concat()),
+        "base64" => Ok(base64_encode(expand(arguments)?.as_bytes())),
+        "json" => {
+            let argument = expand(arguments)?;
+            let (file, path) = argument.rsplit_once('#').unwrap_or((argument.as_str(), ""));
+            let text = std::fs::read_to_string(file)
+                .map_err(|e| fail(format!("cannot read '{file}': {e}")))?;
+            let document: serde_json::Value = serde_json::from_str(&text)
+                .map_err(|e| fail(format!("'{file}' is not valid JSON: {e}")))?;
+            let mut value = &document;
+            for key in path.split('.').filter(|key| !key.is_empty()) {
+                value = match value {
+                    serde_json::Value::Array(items) => {
+                        key.parse::<usize>().ok().and_then(|i| items.get(i))
+                    }
+                    _ => value.get(key),
+                }
+                .ok_or_else(|| fail(format!("'{path}' not found in '{file}'")))?;
+            }
+            Ok(match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+        }
+        _ => Err(fail("unknown function".to_string())),
+    }
+}
+
+/// Replace every `${...}` reference in `text` with its value.
+fn expand(text: &str) -> Result<String, ResolverError> {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let end = closing_brace(&rest[start..])
+            .ok_or_else(|| ResolverError::ParseError(format!("unterminated '${{' in '{text}'")))?;
+        result.push_str(&evaluate(&rest[start + 2..start + end])?);
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Byte offset of the `}` closing the reference that `text` starts with.
+fn closing_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split function arguments on commas that are not inside a nested reference.
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&arguments[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&arguments[start..]);
+    parts
+}
+
+/// Standard base64 with padding
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +280,64 @@ mod tests {
             ResolverError::NotImplemented(_)
         ));
     }
+
+    fn interpolation(expression: &str) -> ConfigValue<String> {
+        ConfigValue::Interpolation {
+            expression: expression.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_concat_expands_nested_references() {
+        std::env::set_var("TEST_INTERP_HOST", "db.example.com");
+
+        let value =
+            interpolation("concat:postgres://,${TEST_INTERP_HOST},:,${TEST_INTERP_PORT:-5432}");
+        let result = InterpolationResolver.resolve_to_string(&value).unwrap();
+        assert_eq!(result, "postgres://db.example.com:5432");
+
+        std::env::remove_var("TEST_INTERP_HOST");
+    }
+
+    #[test]
+    fn test_base64_encodes_argument() {
+        let value = interpolation("base64:${concat:user,:,pass}");
+        let result = InterpolationResolver.resolve_to_string(&value).unwrap();
+        assert_eq!(result, "dXNlcjpwYXNz");
+
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"a"), "YQ==");
+        assert_eq!(base64_encode(b"ab"), "YWI=");
+    }
+
+    #[test]
+    fn test_json_reads_path_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("creds.json");
+        std::fs::write(&file, r#"{"db": {"hosts": ["a", "b"], "port": 5432}}"#).unwrap();
+        let file = file.display();
+
+        let host = interpolation(&format!("json:{file}#db.hosts.1"));
+        assert_eq!(InterpolationResolver.resolve_to_string(&host).unwrap(), "b");
+        let port = interpolation(&format!("json:{file}#db.port"));
+        assert_eq!(
+            InterpolationResolver.resolve_to_string(&port).unwrap(),
+            "5432"
+        );
+
+        let missing = interpolation(&format!("json:{file}#db.user"));
+        assert!(matches!(
+            InterpolationResolver.resolve_to_string(&missing),
+            Err(ResolverError::InterpolationError(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_missing_nested_var_fails() {
+        let value = interpolation("concat:a,${NONEXISTENT_VAR_24680}");
+        assert!(matches!(
+            InterpolationResolver.resolve_to_string(&value),
+            Err(ResolverError::EnvVarNotFound(_))
+        ));
+    }
 }
@@ -29,7 +29,10 @@ pub mod core {
     pub mod resolver;
 
     pub use mapper::{ConfigMapper, DtoMapper, MappingError};
-    pub use resolver::{EnvironmentVariableResolver, ResolverError, SecretResolver, ValueResolver};
+    pub use resolver::{
+        EnvironmentVariableResolver, InterpolationResolver, ResolverError, SecretResolver,
+        ValueResolver,
+    };
 }
 
 // Server settings mapper
@@ -0,0 +1,93 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mapper for the `batching` block shared by batching reactions.
+
+use crate::api::mappings::{ConfigMapper, DtoMapper, MappingError};
+use crate::api::models::{BatchingConfigDto, ConfigValue};
+
+/// Resolved `batching` block; knobs left unset fall back to the reaction's
+/// own fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Batching {
+    pub max_size: Option<usize>,
+    pub max_wait_ms: Option<u64>,
+}
+
+impl Batching {
+    /// Whether the block set anything
+    pub fn is_set(&self) -> bool {
+        self.max_size.is_some() || self.max_wait_ms.is_some()
+    }
+
+    pub fn max_size(
+        &self,
+        fallback: &ConfigValue<usize>,
+        resolver: &DtoMapper,
+    ) -> Result<usize, MappingError> {
+        match self.max_size {
+            Some(max_size) => Ok(max_size),
+            None => Ok(resolver.resolve_typed(fallback)?),
+        }
+    }
+
+    pub fn max_wait_ms(
+        &self,
+        fallback: &ConfigValue<u64>,
+        resolver: &DtoMapper,
+    ) -> Result<u64, MappingError> {
+        match self.max_wait_ms {
+            Some(max_wait_ms) => Ok(max_wait_ms),
+            None => Ok(resolver.resolve_typed(fallback)?),
+        }
+    }
+}
+
+pub struct BatchingConfigMapper;
+
+impl ConfigMapper<BatchingConfigDto, Batching> for BatchingConfigMapper {
+    fn map(&self, dto: &BatchingConfigDto, resolver: &DtoMapper) -> Result<Batching, MappingError> {
+        Ok(Batching {
+            max_size: resolver.resolve_optional(&dto.max_size)?,
+            max_wait_ms: resolver.resolve_optional(&dto.max_wait_ms)?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batching_overrides_reaction_fields() {
+        let dto: BatchingConfigDto = serde_yaml::from_str("max_size: 50").unwrap();
+        let resolver = DtoMapper::new();
+
+        let batching = BatchingConfigMapper.map(&dto, &resolver).unwrap();
+
+        assert_eq!(
+            batching
+                .max_size(&ConfigValue::Static(100), &resolver)
+                .unwrap(),
+            50
+        );
+        assert_eq!(
+            batching
+                .max_wait_ms(&ConfigValue::Static(1000), &resolver)
+                .unwrap(),
+            1000
+        );
+    }
+}
@@ -14,6 +14,7 @@
 
 //! gRPC adaptive reaction configuration mapper.
 
+use super::BatchingConfigMapper;
 use crate::api::mappings::{ConfigMapper, DtoMapper, MappingError};
 use crate::api::models::*;
 use drasi_lib::reactions::common::AdaptiveBatchConfig;
@@ -30,14 +31,15 @@ impl ConfigMapper<GrpcAdaptiveReactionConfigDto, GrpcAdaptiveReactionConfig>
         dto: &GrpcAdaptiveReactionConfigDto,
         resolver: &DtoMapper,
     ) -> Result<GrpcAdaptiveReactionConfig, MappingError> {
+        let batching = BatchingConfigMapper.map(&dto.batching, resolver)?;
         let adaptive = AdaptiveBatchConfig {
             adaptive_min_batch_size: resolver
                 .resolve_typed(&dto.adaptive.adaptive_min_batch_size)?,
-            adaptive_max_batch_size: resolver
-                .resolve_typed(&dto.adaptive.adaptive_max_batch_size)?,
+            adaptive_max_batch_size: batching
+                .max_size(&dto.adaptive.adaptive_max_batch_size, resolver)?,
             adaptive_window_size: resolver.resolve_typed(&dto.adaptive.adaptive_window_size)?,
-            adaptive_batch_timeout_ms: resolver
-                .resolve_typed(&dto.adaptive.adaptive_batch_timeout_ms)?,
+            adaptive_batch_timeout_ms: batching
+                .max_wait_ms(&dto.adaptive.adaptive_batch_timeout_ms, resolver)?,
         };
 
         Ok(GrpcAdaptiveReactionConfig {
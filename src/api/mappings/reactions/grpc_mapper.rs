@@ -14,6 +14,7 @@
 
 //! gRPC reaction configuration mapper.
 
+use super::BatchingConfigMapper;
 use crate::api::mappings::{ConfigMapper, DtoMapper, MappingError};
 use crate::api::models::*;
 use drasi_reaction_grpc::GrpcReactionConfig;
@@ -27,11 +28,13 @@ impl ConfigMapper<GrpcReactionConfigDto, GrpcReactionConfig> for GrpcReactionCon
         dto: &GrpcReactionConfigDto,
         resolver: &DtoMapper,
     ) -> Result<GrpcReactionConfig, MappingError> {
+        let batching = BatchingConfigMapper.map(&dto.batching, resolver)?;
+
         Ok(GrpcReactionConfig {
             endpoint: resolver.resolve_string(&dto.endpoint)?,
             timeout_ms: resolver.resolve_typed(&dto.timeout_ms)?,
-            batch_size: resolver.resolve_typed(&dto.batch_size)?,
-            batch_flush_timeout_ms: resolver.resolve_typed(&dto.batch_flush_timeout_ms)?,
+            batch_size: batching.max_size(&dto.batch_size, resolver)?,
+            batch_flush_timeout_ms: batching.max_wait_ms(&dto.batch_flush_timeout_ms, resolver)?,
             max_retries: resolver.resolve_typed(&dto.max_retries)?,
             connection_retry_attempts: resolver.resolve_typed(&dto.connection_retry_attempts)?,
             initial_connection_timeout_ms: resolver
@@ -14,12 +14,12 @@
 
 //! HTTP adaptive reaction configuration mapper.
 
+use super::http_mapper::map_routes;
+use super::BatchingConfigMapper;
 use crate::api::mappings::{ConfigMapper, DtoMapper, MappingError};
 use crate::api::models::*;
 use drasi_lib::reactions::common::AdaptiveBatchConfig;
-use drasi_reaction_http::{CallSpec, QueryConfig};
 use drasi_reaction_http_adaptive::HttpAdaptiveReactionConfig;
-use std::collections::HashMap;
 
 pub struct HttpAdaptiveReactionConfigMapper;
 
@@ -31,79 +31,23 @@ impl ConfigMapper<HttpAdaptiveReactionConfigDto, HttpAdaptiveReactionConfig>
         dto: &HttpAdaptiveReactionConfigDto,
         resolver: &DtoMapper,
     ) -> Result<HttpAdaptiveReactionConfig, MappingError> {
-        let mut routes = HashMap::new();
-        for (key, query_dto) in &dto.routes {
-            let added = if let Some(call_dto) = &query_dto.added {
-                Some(CallSpec {
-                    url: resolver.resolve_string(&call_dto.url)?,
-                    method: resolver.resolve_string(&call_dto.method)?,
-                    body: resolver.resolve_string(&call_dto.body)?,
-                    headers: resolve_hashmap(&call_dto.headers, resolver)?,
-                })
-            } else {
-                None
-            };
-
-            let updated = if let Some(call_dto) = &query_dto.updated {
-                Some(CallSpec {
-                    url: resolver.resolve_string(&call_dto.url)?,
-                    method: resolver.resolve_string(&call_dto.method)?,
-                    body: resolver.resolve_string(&call_dto.body)?,
-                    headers: resolve_hashmap(&call_dto.headers, resolver)?,
-                })
-            } else {
-                None
-            };
-
-            let deleted = if let Some(call_dto) = &query_dto.deleted {
-                Some(CallSpec {
-                    url: resolver.resolve_string(&call_dto.url)?,
-                    method: resolver.resolve_string(&call_dto.method)?,
-                    body: resolver.resolve_string(&call_dto.body)?,
-                    headers: resolve_hashmap(&call_dto.headers, resolver)?,
-                })
-            } else {
-                None
-            };
-
-            routes.insert(
-                key.clone(),
-                QueryConfig {
-                    added,
-                    updated,
-                    deleted,
-                },
-            );
-        }
-
+        let batching = BatchingConfigMapper.map(&dto.batching, resolver)?;
         let adaptive = AdaptiveBatchConfig {
             adaptive_min_batch_size: resolver
                 .resolve_typed(&dto.adaptive.adaptive_min_batch_size)?,
-            adaptive_max_batch_size: resolver
-                .resolve_typed(&dto.adaptive.adaptive_max_batch_size)?,
+            adaptive_max_batch_size: batching
+                .max_size(&dto.adaptive.adaptive_max_batch_size, resolver)?,
             adaptive_window_size: resolver.resolve_typed(&dto.adaptive.adaptive_window_size)?,
-            adaptive_batch_timeout_ms: resolver
-                .resolve_typed(&dto.adaptive.adaptive_batch_timeout_ms)?,
+            adaptive_batch_timeout_ms: batching
+                .max_wait_ms(&dto.adaptive.adaptive_batch_timeout_ms, resolver)?,
         };
 
         Ok(HttpAdaptiveReactionConfig {
             base_url: resolver.resolve_string(&dto.base_url)?,
             token: resolver.resolve_optional(&dto.token)?,
             timeout_ms: resolver.resolve_typed(&dto.timeout_ms)?,
-            routes,
+            routes: map_routes(&dto.routes, resolver)?,
             adaptive,
         })
     }
 }
-
-// Helper function to resolve HashMap<String, ConfigValue<String>>
-fn resolve_hashmap(
-    map: &HashMap<String, ConfigValue<String>>,
-    resolver: &DtoMapper,
-) -> Result<HashMap<String, String>, MappingError> {
-    let mut result = HashMap::new();
-    for (key, value) in map {
-        result.insert(key.clone(), resolver.resolve_string(value)?);
-    }
-    Ok(result)
-}
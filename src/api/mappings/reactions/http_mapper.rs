@@ -21,62 +21,53 @@ use std::collections::HashMap;
 
 pub struct HttpReactionConfigMapper;
 
+fn map_call_spec(dto: &CallSpecDto, resolver: &DtoMapper) -> Result<CallSpec, MappingError> {
+    Ok(CallSpec {
+        url: resolver.resolve_string(&dto.url)?,
+        method: resolver.resolve_string(&dto.method)?.to_uppercase(),
+        body: resolver.resolve_string(&dto.body)?,
+        headers: resolve_hashmap(&dto.headers, resolver)?,
+    })
+}
+
+fn map_query_config(
+    dto: &QueryConfigDto,
+    resolver: &DtoMapper,
+) -> Result<QueryConfig, MappingError> {
+    let map = |call: &Option<CallSpecDto>| {
+        call.as_ref()
+            .map(|call| map_call_spec(call, resolver))
+            .transpose()
+    };
+    Ok(QueryConfig {
+        added: map(&dto.added)?,
+        updated: map(&dto.updated)?,
+        deleted: map(&dto.deleted)?,
+    })
+}
+
+/// Map per-query routes; shared with the adaptive HTTP reaction.
+pub(super) fn map_routes(
+    routes: &HashMap<String, QueryConfigDto>,
+    resolver: &DtoMapper,
+) -> Result<HashMap<String, QueryConfig>, MappingError> {
+    routes
+        .iter()
+        .map(|(query_id, dto)| Ok((query_id.clone(), map_query_config(dto, resolver)?)))
+        .collect()
+}
+
 impl ConfigMapper<HttpReactionConfigDto, HttpReactionConfig> for HttpReactionConfigMapper {
     fn map(
         &self,
         dto: &HttpReactionConfigDto,
         resolver: &DtoMapper,
     ) -> Result<HttpReactionConfig, MappingError> {
-        let mut routes = HashMap::new();
-        for (key, query_dto) in &dto.routes {
-            let added = if let Some(call_dto) = &query_dto.added {
-                Some(CallSpec {
-                    url: resolver.resolve_string(&call_dto.url)?,
-                    method: resolver.resolve_string(&call_dto.method)?,
-                    body: resolver.resolve_string(&call_dto.body)?,
-                    headers: resolve_hashmap(&call_dto.headers, resolver)?,
-                })
-            } else {
-                None
-            };
-
-            let updated = if let Some(call_dto) = &query_dto.updated {
-                Some(CallSpec {
-                    url: resolver.resolve_string(&call_dto.url)?,
-                    method: resolver.resolve_string(&call_dto.method)?,
-                    body: resolver.resolve_string(&call_dto.body)?,
-                    headers: resolve_hashmap(&call_dto.headers, resolver)?,
-                })
-            } else {
-                None
-            };
-
-            let deleted = if let Some(call_dto) = &query_dto.deleted {
-                Some(CallSpec {
-                    url: resolver.resolve_string(&call_dto.url)?,
-                    method: resolver.resolve_string(&call_dto.method)?,
-                    body: resolver.resolve_string(&call_dto.body)?,
-                    headers: resolve_hashmap(&call_dto.headers, resolver)?,
-                })
-            } else {
-                None
-            };
-
-            routes.insert(
-                key.clone(),
-                QueryConfig {
-                    added,
-                    updated,
-                    deleted,
-                },
-            );
-        }
-
         Ok(HttpReactionConfig {
             base_url: resolver.resolve_string(&dto.base_url)?,
             token: resolver.resolve_optional(&dto.token)?,
             timeout_ms: resolver.resolve_typed(&dto.timeout_ms)?,
-            routes,
+            routes: map_routes(&dto.routes, resolver)?,
         })
     }
 }
@@ -92,3 +83,41 @@ fn resolve_hashmap(
     }
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_query_routes_with_templates() {
+        let dto: HttpReactionConfigDto = serde_yaml::from_str(
+            r#"
+            base_url: https://alerts.example.com
+            routes:
+              low-stock:
+                added:
+                  url: /items/{{after.id}}
+                  body: '{"item": "{{after.name}}", "stock": {{after.stock}}}'
+                  headers:
+                    Content-Type: application/json
+                deleted:
+                  url: /items/{{before.id}}
+                  method: delete
+            "#,
+        )
+        .unwrap();
+
+        let config = HttpReactionConfigMapper
+            .map(&dto, &DtoMapper::new())
+            .unwrap();
+        let route = &config.routes["low-stock"];
+
+        let added = route.added.as_ref().unwrap();
+        assert_eq!(added.url, "/items/{{after.id}}");
+        assert_eq!(added.method, "POST");
+        assert!(added.body.contains("{{after.stock}}"));
+        assert_eq!(added.headers["Content-Type"], "application/json");
+        assert!(route.updated.is_none());
+        assert_eq!(route.deleted.as_ref().unwrap().method, "DELETE");
+    }
+}
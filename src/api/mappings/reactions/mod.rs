@@ -14,6 +14,7 @@
 
 //! Reaction configuration mappers.
 
+mod batching_mapper;
 mod grpc_adaptive_mapper;
 mod grpc_mapper;
 mod http_adaptive_mapper;
@@ -23,6 +24,7 @@ mod platform_mapper;
 mod profiler_mapper;
 mod sse_mapper;
 
+pub use batching_mapper::{Batching, BatchingConfigMapper};
 pub use grpc_adaptive_mapper::GrpcAdaptiveReactionConfigMapper;
 pub use grpc_mapper::GrpcReactionConfigMapper;
 pub use http_adaptive_mapper::HttpAdaptiveReactionConfigMapper;
@@ -14,6 +14,7 @@
 
 //! Platform reaction configuration mapper.
 
+use super::BatchingConfigMapper;
 use crate::api::mappings::{ConfigMapper, DtoMapper, MappingError};
 use crate::api::models::PlatformReactionConfigDto;
 use drasi_reaction_platform::PlatformReactionConfig;
@@ -28,15 +29,17 @@ impl ConfigMapper<PlatformReactionConfigDto, PlatformReactionConfig>
         dto: &PlatformReactionConfigDto,
         resolver: &DtoMapper,
     ) -> Result<PlatformReactionConfig, MappingError> {
+        let batching = BatchingConfigMapper.map(&dto.batching, resolver)?;
+
         Ok(PlatformReactionConfig {
             redis_url: resolver.resolve_string(&dto.redis_url)?,
             pubsub_name: resolver.resolve_optional(&dto.pubsub_name)?,
             source_name: resolver.resolve_optional(&dto.source_name)?,
             max_stream_length: resolver.resolve_optional(&dto.max_stream_length)?,
             emit_control_events: resolver.resolve_typed(&dto.emit_control_events)?,
-            batch_enabled: resolver.resolve_typed(&dto.batch_enabled)?,
-            batch_max_size: resolver.resolve_typed(&dto.batch_max_size)?,
-            batch_max_wait_ms: resolver.resolve_typed(&dto.batch_max_wait_ms)?,
+            batch_enabled: batching.is_set() || resolver.resolve_typed(&dto.batch_enabled)?,
+            batch_max_size: batching.max_size(&dto.batch_max_size, resolver)?,
+            batch_max_wait_ms: batching.max_wait_ms(&dto.batch_max_wait_ms, resolver)?,
         })
     }
 }
@@ -14,17 +14,25 @@
 
 //! Server settings mapper
 
+use crate::api::auth::ApiKeys;
+use crate::api::jwt::JwtConfig;
 use crate::api::mappings::core::mapper::DtoMapper;
-use crate::config::types::DrasiServerConfig;
+use crate::api::openapi::OpenApiMetadata;
+use crate::api::tls::TlsConfig;
+use crate::config::types::{BindHosts, DrasiServerConfig};
 use anyhow::Result;
 
 /// Resolved server settings with actual values (no ConfigValue wrappers)
 #[derive(Debug, Clone)]
 pub struct ResolvedServerSettings {
-    pub host: String,
+    pub host: BindHosts,
     pub port: u16,
     pub log_level: String,
     pub disable_persistence: bool,
+    pub api_keys: ApiKeys,
+    pub jwt: Option<JwtConfig>,
+    pub tls: Option<TlsConfig>,
+    pub openapi: OpenApiMetadata,
 }
 
 /// Maps DrasiServerConfig to ResolvedServerSettings domain model
@@ -37,5 +45,60 @@ pub fn map_server_settings(
         port: mapper.resolve_typed(&config.port)?,
         log_level: mapper.resolve_typed(&config.log_level)?,
         disable_persistence: config.disable_persistence,
+        api_keys: ApiKeys::new(
+            config
+                .api_keys
+                .iter()
+                .map(|key| mapper.resolve_typed(key))
+                .collect::<Result<_, _>>()?,
+        ),
+        jwt: config
+            .jwt
+            .as_ref()
+            .map(|jwt| -> Result<JwtConfig> {
+                Ok(JwtConfig {
+                    issuer: mapper.resolve_typed(&jwt.issuer)?,
+                    audience: mapper.resolve_typed(&jwt.audience)?,
+                    jwks_url: mapper.resolve_typed(&jwt.jwks_url)?,
+                })
+            })
+            .transpose()?,
+        tls: config
+            .tls
+            .as_ref()
+            .map(|tls| -> Result<TlsConfig> {
+                Ok(TlsConfig {
+                    cert_path: mapper.resolve_typed(&tls.cert_path)?.into(),
+                    key_path: mapper.resolve_typed(&tls.key_path)?.into(),
+                    client_ca_path: mapper
+                        .resolve_optional(&tls.client_ca_path)?
+                        .map(Into::into),
+                    require_client_cert: tls.require_client_cert,
+                })
+            })
+            .transpose()?,
+        openapi: match &config.openapi {
+            Some(openapi) => {
+                let contact = openapi.contact.clone().unwrap_or_default();
+                OpenApiMetadata {
+                    title: mapper.resolve_optional(&openapi.title)?,
+                    description: mapper.resolve_optional(&openapi.description)?,
+                    contact_name: mapper.resolve_optional(&contact.name)?,
+                    contact_email: mapper.resolve_optional(&contact.email)?,
+                    contact_url: mapper.resolve_optional(&contact.url)?,
+                    servers: openapi
+                        .servers
+                        .iter()
+                        .map(|server| -> Result<(String, Option<String>)> {
+                            Ok((
+                                mapper.resolve_typed(&server.url)?,
+                                mapper.resolve_optional(&server.description)?,
+                            ))
+                        })
+                        .collect::<Result<_>>()?,
+                }
+            }
+            None => OpenApiMetadata::default(),
+        },
     })
 }
@@ -17,11 +17,25 @@
 //! This module provides the HTTP API endpoints for managing sources, queries, and reactions.
 //! It also includes the data models (DTOs) and mappings used for API serialization/deserialization.
 
+pub mod about;
+pub mod auth;
+pub mod config_patch;
 pub mod error;
+pub mod etag;
 pub mod handlers;
+pub mod idempotency;
+pub mod joins;
+pub mod jwt;
 pub mod mappings;
 pub mod models;
 pub mod openapi;
+#[cfg(all(feature = "pprof", unix))]
+pub mod pprof;
+pub mod request_id;
+pub mod results_diff;
+pub mod startup_report;
+pub mod tls;
+pub mod usage;
 
 #[cfg(test)]
 mod tests;
@@ -29,6 +43,9 @@ mod tests;
 #[cfg(test)]
 mod joins_tests;
 
+#[cfg(test)]
+mod subscription_filter_tests;
+
 pub use error::*;
 pub use handlers::*;
 pub use models::*;
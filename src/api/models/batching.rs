@@ -0,0 +1,40 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batching settings shared by the reaction kinds that batch deliveries.
+
+use crate::api::models::ConfigValue;
+use serde::{Deserialize, Serialize};
+
+/// How a reaction groups results into batches before delivering them.
+///
+/// Accepted as a `batching` block by the gRPC, gRPC adaptive, HTTP adaptive
+/// and platform reactions. A knob set here takes precedence over the
+/// reaction's own field for it (`batch_size`, `adaptive_max_batch_size`,
+/// `batch_max_size`, ...), which are still accepted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BatchingConfigDto {
+    /// Most results delivered in one batch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<ConfigValue<usize>>,
+    /// Longest time, in milliseconds, a result waits for its batch to fill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_wait_ms: Option<ConfigValue<u64>>,
+}
+
+impl BatchingConfigDto {
+    pub fn is_empty(&self) -> bool {
+        self.max_size.is_none() && self.max_wait_ms.is_none()
+    }
+}
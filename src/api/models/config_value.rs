@@ -31,10 +31,26 @@ where
         default: Option<String>,
     },
 
+    /// A function call such as `${concat:a,b}`; `expression` is the text
+    /// between the braces. Resolves to a string, then parsed to T.
+    Interpolation { expression: String },
+
     /// A static value of type T
     Static(T),
 }
 
+/// Functions that can be called in a `${name:arguments}` reference
+pub(crate) const INTERPOLATION_FUNCTIONS: [&str; 3] = ["base64", "concat", "json"];
+
+/// Whether the text inside `${...}` calls a function rather than naming an
+/// environment variable. `${json:-x}` is still the variable `json` with a
+/// default.
+pub(crate) fn is_function_call(inner: &str) -> bool {
+    inner.split_once(':').is_some_and(|(name, arguments)| {
+        INTERPOLATION_FUNCTIONS.contains(&name) && !arguments.starts_with('-')
+    })
+}
+
 // Type aliases for common cases
 pub type ConfigValueString = ConfigValue<String>;
 pub type ConfigValueU16 = ConfigValue<u16>;
@@ -69,6 +85,9 @@ where
                 }
                 map.end()
             }
+            ConfigValue::Interpolation { expression } => {
+                serializer.serialize_str(&format!("${{{expression}}}"))
+            }
             ConfigValue::Static(value) => value.serialize(serializer),
         }
     }
@@ -137,7 +156,8 @@ where
     }
 }
 
-/// Parse POSIX-style environment variable reference like ${VAR:-default} or ${VAR}
+/// Parse POSIX-style environment variable reference like ${VAR:-default} or ${VAR},
+/// or a function call like ${concat:a,b}
 fn parse_posix_env_var<T>(s: &str) -> Option<ConfigValue<T>>
 where
     T: Clone + Serialize + DeserializeOwned,
@@ -149,6 +169,12 @@ where
 
     let inner = &s[2..s.len() - 1];
 
+    if is_function_call(inner) {
+        return Some(ConfigValue::Interpolation {
+            expression: inner.to_string(),
+        });
+    }
+
     // Check for default value syntax: VAR:-default
     if let Some(colon_pos) = inner.find(":-") {
         let name = inner[..colon_pos].to_string();
@@ -219,6 +245,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_function_call() {
+        let json = r#""${concat:${DB_HOST},:,${DB_PORT:-5432}}""#;
+        let value: ConfigValue<String> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            value,
+            ConfigValue::Interpolation {
+                expression: "concat:${DB_HOST},:,${DB_PORT:-5432}".to_string()
+            }
+        );
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+    }
+
+    #[test]
+    fn test_function_name_with_default_is_env_var() {
+        let json = r#""${json:-fallback}""#;
+        let value: ConfigValue<String> = serde_json::from_str(json).unwrap();
+        assert!(matches!(value, ConfigValue::EnvironmentVariable { .. }));
+    }
+
     #[test]
     fn test_deserialize_structured_env_var() {
         let json = r#"{"kind": "EnvironmentVariable", "name": "DB_PASSWORD", "default": "secret"}"#;
@@ -0,0 +1,94 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dispatch configuration DTOs shared by all source kinds.
+
+use drasi_lib::channels::DispatchMode;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// How a source hands events to its subscribed queries.
+///
+/// - `channel`: each subscriber gets its own bounded channel. A slow query only
+///   backs up its own channel, at the cost of one copy per subscriber.
+/// - `broadcast`: all subscribers share a single broadcast channel. Cheaper with
+///   many subscribers, but a subscriber that falls behind misses events once
+///   the buffer wraps.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DispatchModeDto {
+    #[default]
+    Channel,
+    Broadcast,
+}
+
+impl FromStr for DispatchModeDto {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "channel" => Ok(DispatchModeDto::Channel),
+            "broadcast" => Ok(DispatchModeDto::Broadcast),
+            _ => Err(format!(
+                "Invalid dispatch mode: {s} (expected 'channel' or 'broadcast')"
+            )),
+        }
+    }
+}
+
+impl From<DispatchModeDto> for DispatchMode {
+    fn from(dto: DispatchModeDto) -> Self {
+        match dto {
+            DispatchModeDto::Channel => DispatchMode::Channel,
+            DispatchModeDto::Broadcast => DispatchMode::Broadcast,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispatch_mode_deserializes_lowercase() {
+        let mode: DispatchModeDto = serde_json::from_str("\"broadcast\"").unwrap();
+        assert_eq!(mode, DispatchModeDto::Broadcast);
+        let mode: DispatchModeDto = serde_json::from_str("\"channel\"").unwrap();
+        assert_eq!(mode, DispatchModeDto::Channel);
+    }
+
+    #[test]
+    fn test_dispatch_mode_rejects_unknown_value() {
+        assert!(serde_json::from_str::<DispatchModeDto>("\"partitioned\"").is_err());
+        assert!("partitioned".parse::<DispatchModeDto>().is_err());
+    }
+
+    #[test]
+    fn test_dispatch_mode_from_str_is_case_insensitive() {
+        assert_eq!(
+            "Broadcast".parse::<DispatchModeDto>().unwrap(),
+            DispatchModeDto::Broadcast
+        );
+    }
+
+    #[test]
+    fn test_dispatch_mode_into_domain() {
+        assert!(matches!(
+            DispatchMode::from(DispatchModeDto::Broadcast),
+            DispatchMode::Broadcast
+        ));
+    }
+}
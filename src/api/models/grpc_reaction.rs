@@ -20,6 +20,7 @@ use std::collections::HashMap;
 
 // Re-use adaptive config from http_reaction
 use super::http_reaction::AdaptiveBatchConfigDto;
+use super::BatchingConfigDto;
 
 /// Local copy of gRPC reaction configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -40,6 +41,9 @@ pub struct GrpcReactionConfigDto {
     pub initial_connection_timeout_ms: ConfigValue<u64>,
     #[serde(default)]
     pub metadata: HashMap<String, ConfigValue<String>>,
+    /// Shared batching settings, overriding the fields above
+    #[serde(default, skip_serializing_if = "BatchingConfigDto::is_empty")]
+    pub batching: BatchingConfigDto,
 }
 
 fn default_grpc_endpoint() -> ConfigValue<String> {
@@ -87,4 +91,7 @@ pub struct GrpcAdaptiveReactionConfigDto {
     pub metadata: HashMap<String, ConfigValue<String>>,
     #[serde(flatten)]
     pub adaptive: AdaptiveBatchConfigDto,
+    /// Shared batching settings, overriding the fields above
+    #[serde(default, skip_serializing_if = "BatchingConfigDto::is_empty")]
+    pub batching: BatchingConfigDto,
 }
@@ -14,7 +14,7 @@
 
 //! HTTP reaction configuration DTOs.
 
-use crate::api::models::ConfigValue;
+use crate::api::models::{BatchingConfigDto, ConfigValue};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -39,26 +39,42 @@ fn default_reaction_timeout_ms() -> ConfigValue<u64> {
     ConfigValue::Static(5000)
 }
 
+/// Requests to make for one query's results, keyed by query ID in `routes`
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct QueryConfigDto {
+    /// Request for ADD operations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub added: Option<CallSpecDto>,
+    /// Request for UPDATE operations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated: Option<CallSpecDto>,
+    /// Request for DELETE operations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted: Option<CallSpecDto>,
 }
 
+/// A templated HTTP request
+///
+/// `url` and `body` are Handlebars templates rendered against the change,
+/// e.g. `/items/{{after.id}}`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CallSpecDto {
+    /// Request path, appended to `base_url`
     pub url: ConfigValue<String>,
+    /// HTTP method (default: POST)
+    #[serde(default = "default_call_method")]
     pub method: ConfigValue<String>,
+    /// Request body template
     #[serde(default)]
     pub body: ConfigValue<String>,
     #[serde(default)]
     pub headers: HashMap<String, ConfigValue<String>>,
 }
 
+fn default_call_method() -> ConfigValue<String> {
+    ConfigValue::Static("POST".to_string())
+}
+
 /// Local copy of HTTP adaptive reaction configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HttpAdaptiveReactionConfigDto {
@@ -72,6 +88,9 @@ pub struct HttpAdaptiveReactionConfigDto {
     pub routes: HashMap<String, QueryConfigDto>,
     #[serde(flatten)]
     pub adaptive: AdaptiveBatchConfigDto,
+    /// Shared batching settings, overriding the fields above
+    #[serde(default, skip_serializing_if = "BatchingConfigDto::is_empty")]
+    pub batching: BatchingConfigDto,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
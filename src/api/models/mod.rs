@@ -26,6 +26,7 @@
 //!   - `grpc_source` - gRPC source
 //!   - `mock` - Mock source for testing
 //!   - `platform_source` - Platform/Redis source
+//!   - `reconnect` - Reconnect policy shared by network-backed sources
 //!
 //! - **Reactions**: DTOs for reaction configurations
 //!   - `http_reaction` - HTTP and HTTP Adaptive reactions
@@ -34,20 +35,29 @@
 //!   - `log` - Log reaction
 //!   - `platform_reaction` - Platform reaction
 //!   - `profiler` - Profiler reaction
+//!   - `batching` - Batching settings shared by batching reactions
 
 use serde::{Deserialize, Serialize};
 
 // Config value module
 pub mod config_value;
 
+// Shared source dispatch settings
+pub mod dispatch;
+
+// Query language selection
+pub mod query_language;
+
 // Source modules
 pub mod grpc_source;
 pub mod http_source;
 pub mod mock;
 pub mod platform_source;
 pub mod postgres;
+pub mod reconnect;
 
 // Reaction modules
+pub mod batching;
 pub mod grpc_reaction;
 pub mod http_reaction;
 pub mod log;
@@ -61,7 +71,9 @@ pub use http_source::*;
 pub use mock::*;
 pub use platform_source::*;
 pub use postgres::*;
+pub use reconnect::*;
 
+pub use batching::*;
 pub use grpc_reaction::*;
 pub use http_reaction::*;
 // Note: log and sse modules have types with similar names (QueryConfigDto, TemplateSpecDto)
@@ -73,6 +85,8 @@ pub use sse::SseReactionConfigDto;
 
 // Config value types
 pub use config_value::*;
+pub use dispatch::*;
+pub use query_language::*;
 
 // =============================================================================
 // Configuration Enums (Top-level aggregates)
@@ -102,6 +116,12 @@ fn default_true() -> bool {
 ///     id: http-source
 ///     host: "0.0.0.0"
 ///     port: 9000
+///     dispatch_mode: broadcast        # optional: channel (default) or broadcast
+///     dispatch_buffer_capacity: 2000  # optional: per-source dispatch buffer size
+///     lazy: true                      # optional: start once a running query subscribes
+///     reconnect:                      # optional: restart the source when it fails
+///       initial_delay_ms: 1000
+///       max_delay_ms: 60000
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind")]
@@ -114,6 +134,12 @@ pub enum SourceConfig {
         auto_start: bool,
         #[serde(skip_serializing_if = "Option::is_none")]
         bootstrap_provider: Option<drasi_lib::bootstrap::BootstrapProviderConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_mode: Option<DispatchModeDto>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_buffer_capacity: Option<usize>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        lazy: bool,
         #[serde(flatten)]
         config: MockSourceConfigDto,
     },
@@ -125,6 +151,14 @@ pub enum SourceConfig {
         auto_start: bool,
         #[serde(skip_serializing_if = "Option::is_none")]
         bootstrap_provider: Option<drasi_lib::bootstrap::BootstrapProviderConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_mode: Option<DispatchModeDto>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_buffer_capacity: Option<usize>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        lazy: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reconnect: Option<ReconnectPolicyDto>,
         #[serde(flatten)]
         config: HttpSourceConfigDto,
     },
@@ -136,6 +170,14 @@ pub enum SourceConfig {
         auto_start: bool,
         #[serde(skip_serializing_if = "Option::is_none")]
         bootstrap_provider: Option<drasi_lib::bootstrap::BootstrapProviderConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_mode: Option<DispatchModeDto>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_buffer_capacity: Option<usize>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        lazy: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reconnect: Option<ReconnectPolicyDto>,
         #[serde(flatten)]
         config: GrpcSourceConfigDto,
     },
@@ -147,6 +189,14 @@ pub enum SourceConfig {
         auto_start: bool,
         #[serde(skip_serializing_if = "Option::is_none")]
         bootstrap_provider: Option<drasi_lib::bootstrap::BootstrapProviderConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_mode: Option<DispatchModeDto>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_buffer_capacity: Option<usize>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        lazy: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reconnect: Option<ReconnectPolicyDto>,
         #[serde(flatten)]
         config: PostgresSourceConfigDto,
     },
@@ -158,6 +208,14 @@ pub enum SourceConfig {
         auto_start: bool,
         #[serde(skip_serializing_if = "Option::is_none")]
         bootstrap_provider: Option<drasi_lib::bootstrap::BootstrapProviderConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_mode: Option<DispatchModeDto>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dispatch_buffer_capacity: Option<usize>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        lazy: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reconnect: Option<ReconnectPolicyDto>,
         #[serde(flatten)]
         config: PlatformSourceConfigDto,
     },
@@ -175,6 +233,20 @@ impl SourceConfig {
         }
     }
 
+    /// Every value accepted in a source's `kind` field
+    pub const KINDS: [&'static str; 5] = ["mock", "http", "grpc", "postgres", "platform"];
+
+    /// Get the source kind as written in the `kind` field
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SourceConfig::Mock { .. } => "mock",
+            SourceConfig::Http { .. } => "http",
+            SourceConfig::Grpc { .. } => "grpc",
+            SourceConfig::Postgres { .. } => "postgres",
+            SourceConfig::Platform { .. } => "platform",
+        }
+    }
+
     /// Check if auto_start is enabled
     pub fn auto_start(&self) -> bool {
         match self {
@@ -186,6 +258,76 @@ impl SourceConfig {
         }
     }
 
+    /// Whether the source is only started once a running query subscribes to it
+    pub fn lazy(&self) -> bool {
+        match self {
+            SourceConfig::Mock { lazy, .. }
+            | SourceConfig::Http { lazy, .. }
+            | SourceConfig::Grpc { lazy, .. }
+            | SourceConfig::Postgres { lazy, .. }
+            | SourceConfig::Platform { lazy, .. } => *lazy,
+        }
+    }
+
+    /// How the server restarts the source when it fails, if at all
+    pub fn reconnect(&self) -> Option<&ReconnectPolicyDto> {
+        match self {
+            SourceConfig::Mock { .. } => None,
+            SourceConfig::Http { reconnect, .. }
+            | SourceConfig::Grpc { reconnect, .. }
+            | SourceConfig::Postgres { reconnect, .. }
+            | SourceConfig::Platform { reconnect, .. } => reconnect.as_ref(),
+        }
+    }
+
+    /// Override auto_start, e.g. to keep a disabled source from starting
+    pub fn set_auto_start(&mut self, value: bool) {
+        match self {
+            SourceConfig::Mock { auto_start, .. }
+            | SourceConfig::Http { auto_start, .. }
+            | SourceConfig::Grpc { auto_start, .. }
+            | SourceConfig::Postgres { auto_start, .. }
+            | SourceConfig::Platform { auto_start, .. } => *auto_start = value,
+        }
+    }
+
+    /// Get the dispatch mode override if any
+    pub fn dispatch_mode(&self) -> Option<DispatchModeDto> {
+        match self {
+            SourceConfig::Mock { dispatch_mode, .. } => *dispatch_mode,
+            SourceConfig::Http { dispatch_mode, .. } => *dispatch_mode,
+            SourceConfig::Grpc { dispatch_mode, .. } => *dispatch_mode,
+            SourceConfig::Postgres { dispatch_mode, .. } => *dispatch_mode,
+            SourceConfig::Platform { dispatch_mode, .. } => *dispatch_mode,
+        }
+    }
+
+    /// Get the dispatch buffer capacity override if any
+    pub fn dispatch_buffer_capacity(&self) -> Option<usize> {
+        match self {
+            SourceConfig::Mock {
+                dispatch_buffer_capacity,
+                ..
+            } => *dispatch_buffer_capacity,
+            SourceConfig::Http {
+                dispatch_buffer_capacity,
+                ..
+            } => *dispatch_buffer_capacity,
+            SourceConfig::Grpc {
+                dispatch_buffer_capacity,
+                ..
+            } => *dispatch_buffer_capacity,
+            SourceConfig::Postgres {
+                dispatch_buffer_capacity,
+                ..
+            } => *dispatch_buffer_capacity,
+            SourceConfig::Platform {
+                dispatch_buffer_capacity,
+                ..
+            } => *dispatch_buffer_capacity,
+        }
+    }
+
     /// Get the bootstrap provider configuration if any
     pub fn bootstrap_provider(&self) -> Option<&drasi_lib::bootstrap::BootstrapProviderConfig> {
         match self {
@@ -222,6 +364,10 @@ pub enum ReactionConfig {
         queries: Vec<String>,
         #[serde(default = "default_true")]
         auto_start: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_for_queries_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
         #[serde(flatten)]
         config: LogReactionConfigDto,
     },
@@ -232,6 +378,10 @@ pub enum ReactionConfig {
         queries: Vec<String>,
         #[serde(default = "default_true")]
         auto_start: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_for_queries_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
         #[serde(flatten)]
         config: HttpReactionConfigDto,
     },
@@ -242,6 +392,10 @@ pub enum ReactionConfig {
         queries: Vec<String>,
         #[serde(default = "default_true")]
         auto_start: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_for_queries_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
         #[serde(flatten)]
         config: HttpAdaptiveReactionConfigDto,
     },
@@ -252,6 +406,10 @@ pub enum ReactionConfig {
         queries: Vec<String>,
         #[serde(default = "default_true")]
         auto_start: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_for_queries_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
         #[serde(flatten)]
         config: GrpcReactionConfigDto,
     },
@@ -262,6 +420,10 @@ pub enum ReactionConfig {
         queries: Vec<String>,
         #[serde(default = "default_true")]
         auto_start: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_for_queries_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
         #[serde(flatten)]
         config: GrpcAdaptiveReactionConfigDto,
     },
@@ -272,6 +434,10 @@ pub enum ReactionConfig {
         queries: Vec<String>,
         #[serde(default = "default_true")]
         auto_start: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_for_queries_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
         #[serde(flatten)]
         config: SseReactionConfigDto,
     },
@@ -282,6 +448,10 @@ pub enum ReactionConfig {
         queries: Vec<String>,
         #[serde(default = "default_true")]
         auto_start: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_for_queries_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
         #[serde(flatten)]
         config: PlatformReactionConfigDto,
     },
@@ -292,6 +462,10 @@ pub enum ReactionConfig {
         queries: Vec<String>,
         #[serde(default = "default_true")]
         auto_start: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wait_for_queries_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        group: Option<String>,
         #[serde(flatten)]
         config: ProfilerReactionConfigDto,
     },
@@ -312,6 +486,32 @@ impl ReactionConfig {
         }
     }
 
+    /// Every value accepted in a reaction's `kind` field
+    pub const KINDS: [&'static str; 8] = [
+        "log",
+        "http",
+        "http-adaptive",
+        "grpc",
+        "grpc-adaptive",
+        "sse",
+        "platform",
+        "profiler",
+    ];
+
+    /// Get the reaction kind as written in the `kind` field
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ReactionConfig::Log { .. } => "log",
+            ReactionConfig::Http { .. } => "http",
+            ReactionConfig::HttpAdaptive { .. } => "http-adaptive",
+            ReactionConfig::Grpc { .. } => "grpc",
+            ReactionConfig::GrpcAdaptive { .. } => "grpc-adaptive",
+            ReactionConfig::Sse { .. } => "sse",
+            ReactionConfig::Platform { .. } => "platform",
+            ReactionConfig::Profiler { .. } => "profiler",
+        }
+    }
+
     /// Get the query IDs this reaction subscribes to
     pub fn queries(&self) -> &[String] {
         match self {
@@ -339,4 +539,71 @@ impl ReactionConfig {
             ReactionConfig::Profiler { auto_start, .. } => *auto_start,
         }
     }
+
+    /// How long auto-start waits for the subscribed queries to be running, if
+    /// the reaction is gated on them
+    pub fn wait_for_queries_ms(&self) -> Option<u64> {
+        match self {
+            ReactionConfig::Log {
+                wait_for_queries_ms,
+                ..
+            }
+            | ReactionConfig::Http {
+                wait_for_queries_ms,
+                ..
+            }
+            | ReactionConfig::HttpAdaptive {
+                wait_for_queries_ms,
+                ..
+            }
+            | ReactionConfig::Grpc {
+                wait_for_queries_ms,
+                ..
+            }
+            | ReactionConfig::GrpcAdaptive {
+                wait_for_queries_ms,
+                ..
+            }
+            | ReactionConfig::Sse {
+                wait_for_queries_ms,
+                ..
+            }
+            | ReactionConfig::Platform {
+                wait_for_queries_ms,
+                ..
+            }
+            | ReactionConfig::Profiler {
+                wait_for_queries_ms,
+                ..
+            } => *wait_for_queries_ms,
+        }
+    }
+
+    /// The failover group the reaction belongs to, if any
+    pub fn group(&self) -> Option<&str> {
+        match self {
+            ReactionConfig::Log { group, .. }
+            | ReactionConfig::Http { group, .. }
+            | ReactionConfig::HttpAdaptive { group, .. }
+            | ReactionConfig::Grpc { group, .. }
+            | ReactionConfig::GrpcAdaptive { group, .. }
+            | ReactionConfig::Sse { group, .. }
+            | ReactionConfig::Platform { group, .. }
+            | ReactionConfig::Profiler { group, .. } => group.as_deref(),
+        }
+    }
+
+    /// Override auto_start, e.g. to keep a disabled reaction from starting
+    pub fn set_auto_start(&mut self, value: bool) {
+        match self {
+            ReactionConfig::Log { auto_start, .. }
+            | ReactionConfig::Http { auto_start, .. }
+            | ReactionConfig::HttpAdaptive { auto_start, .. }
+            | ReactionConfig::Grpc { auto_start, .. }
+            | ReactionConfig::GrpcAdaptive { auto_start, .. }
+            | ReactionConfig::Sse { auto_start, .. }
+            | ReactionConfig::Platform { auto_start, .. }
+            | ReactionConfig::Profiler { auto_start, .. } => *auto_start = value,
+        }
+    }
 }
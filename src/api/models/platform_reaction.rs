@@ -14,7 +14,7 @@
 
 //! Platform reaction configuration DTOs.
 
-use crate::api::models::ConfigValue;
+use crate::api::models::{BatchingConfigDto, ConfigValue};
 use serde::{Deserialize, Serialize};
 
 /// Local copy of platform reaction configuration
@@ -35,6 +35,10 @@ pub struct PlatformReactionConfigDto {
     pub batch_max_size: ConfigValue<usize>,
     #[serde(default = "default_batch_wait_ms")]
     pub batch_max_wait_ms: ConfigValue<u64>,
+    /// Shared batching settings, overriding the fields above; setting them
+    /// enables batching
+    #[serde(default, skip_serializing_if = "BatchingConfigDto::is_empty")]
+    pub batching: BatchingConfigDto,
 }
 
 fn default_batch_size() -> ConfigValue<usize> {
@@ -0,0 +1,95 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Query language selection for continuous queries.
+
+use drasi_lib::config::QueryLanguage;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Language a query's `query` text is written in, set by its `query_language`
+/// field.
+///
+/// - `Cypher` (default): openCypher, e.g. `MATCH (n:Item) RETURN n.name`
+/// - `GQL`: ISO GQL
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
+pub enum QueryLanguageDto {
+    #[default]
+    Cypher,
+    #[serde(rename = "GQL")]
+    Gql,
+}
+
+impl QueryLanguageDto {
+    pub const ALL: [QueryLanguageDto; 2] = [QueryLanguageDto::Cypher, QueryLanguageDto::Gql];
+}
+
+impl std::fmt::Display for QueryLanguageDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryLanguageDto::Cypher => write!(f, "Cypher"),
+            QueryLanguageDto::Gql => write!(f, "GQL"),
+        }
+    }
+}
+
+impl FromStr for QueryLanguageDto {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cypher" => Ok(QueryLanguageDto::Cypher),
+            "gql" => Ok(QueryLanguageDto::Gql),
+            _ => Err(format!(
+                "Invalid query language: {s} (expected 'Cypher' or 'GQL')"
+            )),
+        }
+    }
+}
+
+impl From<QueryLanguageDto> for QueryLanguage {
+    fn from(dto: QueryLanguageDto) -> Self {
+        match dto {
+            QueryLanguageDto::Cypher => QueryLanguage::Cypher,
+            QueryLanguageDto::Gql => QueryLanguage::GQL,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_language_matches_query_config_format() {
+        for dto in QueryLanguageDto::ALL {
+            let json = serde_json::to_value(dto).unwrap();
+            let language: QueryLanguage = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(serde_json::to_value(language).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn test_query_language_from_str_is_case_insensitive() {
+        assert_eq!("gql".parse::<QueryLanguageDto>(), Ok(QueryLanguageDto::Gql));
+        assert_eq!(
+            "CYPHER".parse::<QueryLanguageDto>(),
+            Ok(QueryLanguageDto::Cypher)
+        );
+        assert!("sql".parse::<QueryLanguageDto>().is_err());
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reconnect policy shared by the network-backed source kinds.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How the server restarts a source that fails.
+///
+/// Accepted as a `reconnect` block by the HTTP, gRPC, Postgres and platform
+/// sources. A source in `Error` is restarted after `initial_delay_ms`, and
+/// the delay doubles with every further attempt up to `max_delay_ms`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReconnectPolicyDto {
+    #[serde(default = "default_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// Give up after this many attempts in a row (default: never)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+}
+
+impl ReconnectPolicyDto {
+    /// Delay before the given attempt, counting from zero
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u64.checked_shl(attempt).unwrap_or(u64::MAX);
+        Duration::from_millis(
+            self.initial_delay_ms
+                .saturating_mul(factor)
+                .min(self.max_delay_ms),
+        )
+    }
+
+    pub fn validate(&self, source_id: &str) -> anyhow::Result<()> {
+        if self.initial_delay_ms == 0 {
+            return Err(anyhow::anyhow!(
+                "Invalid reconnect.initial_delay_ms 0 for source '{source_id}': must be greater than zero"
+            ));
+        }
+        if self.max_delay_ms < self.initial_delay_ms {
+            return Err(anyhow::anyhow!(
+                "Invalid reconnect.max_delay_ms for source '{source_id}': must be at least initial_delay_ms ({})",
+                self.initial_delay_ms
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_initial_delay_ms() -> u64 {
+    1000
+}
+
+fn default_max_delay_ms() -> u64 {
+    60_000
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_up_to_the_maximum() {
+        let policy = ReconnectPolicyDto {
+            initial_delay_ms: 500,
+            max_delay_ms: 3000,
+            max_attempts: None,
+        };
+
+        let delays: Vec<u128> = (0..5).map(|n| policy.delay(n).as_millis()).collect();
+        assert_eq!(delays, vec![500, 1000, 2000, 3000, 3000]);
+        assert_eq!(policy.delay(200).as_millis(), 3000);
+    }
+
+    #[test]
+    fn test_max_delay_must_cover_initial_delay() {
+        let policy = ReconnectPolicyDto {
+            initial_delay_ms: 5000,
+            max_delay_ms: 1000,
+            max_attempts: Some(3),
+        };
+
+        let err = policy.validate("orders").unwrap_err().to_string();
+        assert!(err.contains("source 'orders'"));
+    }
+}
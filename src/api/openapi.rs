@@ -14,8 +14,16 @@
 
 use utoipa::OpenApi;
 
-use crate::api::error::{ErrorDetail, ErrorResponse};
+use crate::api::about::{AboutFeatures, AboutLimits, AboutResponse};
+use crate::api::config_patch::{ConfigPatch, ConfigPatchResult};
+use crate::api::error::ErrorCode;
 use crate::api::handlers::{ApiResponseSchema, ComponentListItem, HealthResponse, StatusResponse};
+use crate::api::joins::{JoinIssue, JoinIssueSeverity, JoinValidationResult, ValidateJoinsRequest};
+use crate::api::models::{DispatchModeDto, QueryLanguageDto};
+use crate::api::results_diff::ResultsDiff;
+use crate::api::startup_report::{StartupFailure, StartupReport};
+use crate::api::usage::{ComponentCounts, UsageResponse};
+use crate::registry::StatusEvent;
 // Note: Config types from drasi_lib are imported but not used in schema
 // as they don't implement ToSchema trait
 #[allow(unused_imports)]
@@ -32,34 +40,70 @@ use crate::config::{ReactionConfig, SourceConfig};
 #[openapi(
     paths(
         crate::api::handlers::health_check,
+        crate::api::about::about,
+        crate::api::startup_report::startup_report,
+        crate::api::usage::usage,
         crate::api::handlers::list_sources,
         crate::api::handlers::create_source_handler,
         crate::api::handlers::get_source,
+        crate::api::handlers::get_source_history,
+        crate::api::handlers::replace_source,
         crate::api::handlers::delete_source,
         crate::api::handlers::start_source,
         crate::api::handlers::stop_source,
+        crate::api::handlers::restore_source,
+        crate::api::handlers::enable_source,
+        crate::api::handlers::disable_source,
         crate::api::handlers::list_queries,
         crate::api::handlers::create_query,
+        crate::api::joins::validate_joins_handler,
+        crate::api::joins::get_query_joins,
+        crate::api::joins::replace_query_joins,
         crate::api::handlers::get_query,
+        crate::api::handlers::replace_query,
         crate::api::handlers::delete_query,
         crate::api::handlers::start_query,
         crate::api::handlers::stop_query,
+        crate::api::handlers::restore_query,
+        crate::api::handlers::enable_query,
+        crate::api::handlers::disable_query,
         crate::api::handlers::get_query_results,
+        crate::api::results_diff::get_query_results_diff,
         crate::api::handlers::list_reactions,
         crate::api::handlers::create_reaction_handler,
         crate::api::handlers::get_reaction,
         crate::api::handlers::delete_reaction,
         crate::api::handlers::start_reaction,
         crate::api::handlers::stop_reaction,
+        crate::api::handlers::restore_reaction,
+        crate::api::handlers::enable_reaction,
+        crate::api::handlers::disable_reaction,
+        crate::api::config_patch::patch_config,
     ),
     components(
         schemas(
             HealthResponse,
+            AboutResponse,
+            AboutFeatures,
+            AboutLimits,
+            StartupReport,
+            StartupFailure,
+            UsageResponse,
+            ComponentCounts,
             ComponentListItem,
+            StatusEvent,
             ApiResponseSchema,
             StatusResponse,
-            ErrorResponse,
-            ErrorDetail,
+            ErrorCode,
+            DispatchModeDto,
+            QueryLanguageDto,
+            ConfigPatch,
+            ConfigPatchResult,
+            ValidateJoinsRequest,
+            JoinValidationResult,
+            JoinIssue,
+            JoinIssueSeverity,
+            ResultsDiff,
             // Note: Config types from drasi_lib are not included
             // in the schema as they don't implement ToSchema trait
         )
@@ -69,6 +113,7 @@ use crate::config::{ReactionConfig, SourceConfig};
         (name = "Sources", description = "Data source management"),
         (name = "Queries", description = "Continuous query management"),
         (name = "Reactions", description = "Reaction management"),
+        (name = "Config", description = "Server configuration"),
     ),
     info(
         title = "Drasi Server API",
@@ -85,3 +130,78 @@ use crate::config::{ReactionConfig, SourceConfig};
     )
 )]
 pub struct ApiDoc;
+
+/// Deployment-specific OpenAPI metadata from the `openapi` setting, applied
+/// over the defaults above. Fields left unset keep the default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpenApiMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub contact_url: Option<String>,
+    /// URLs the API is reached at, with an optional description each
+    pub servers: Vec<(String, Option<String>)>,
+}
+
+impl OpenApiMetadata {
+    pub fn apply(&self, doc: &mut utoipa::openapi::OpenApi) {
+        if let Some(title) = &self.title {
+            doc.info.title = title.clone();
+        }
+        if let Some(description) = &self.description {
+            doc.info.description = Some(description.clone());
+        }
+        if self.contact_name.is_some() || self.contact_email.is_some() || self.contact_url.is_some()
+        {
+            doc.info.contact = Some(
+                utoipa::openapi::info::ContactBuilder::new()
+                    .name(self.contact_name.clone())
+                    .email(self.contact_email.clone())
+                    .url(self.contact_url.clone())
+                    .build(),
+            );
+        }
+        if !self.servers.is_empty() {
+            doc.servers = Some(
+                self.servers
+                    .iter()
+                    .map(|(url, description)| {
+                        utoipa::openapi::server::ServerBuilder::new()
+                            .url(url.clone())
+                            .description(description.clone())
+                            .build()
+                    })
+                    .collect(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_overrides_only_what_is_set() {
+        let mut doc = ApiDoc::openapi();
+        OpenApiMetadata {
+            title: Some("Orders Drasi".to_string()),
+            servers: vec![(
+                "https://drasi.internal.example.com".to_string(),
+                Some("Production".to_string()),
+            )],
+            ..Default::default()
+        }
+        .apply(&mut doc);
+
+        assert_eq!(doc.info.title, "Orders Drasi");
+        assert_eq!(
+            doc.info.description.as_deref(),
+            Some("Standalone Drasi server for data change processing")
+        );
+        let servers = doc.servers.unwrap_or_default();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].url, "https://drasi.internal.example.com");
+    }
+}
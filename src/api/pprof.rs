@@ -0,0 +1,83 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CPU profiling endpoint, built with the `pprof` feature (Unix only).
+//!
+//! `GET /debug/pprof/profile?seconds=30` samples the whole process for the
+//! given time and returns a protobuf profile that `go tool pprof` and other
+//! pprof viewers read. The endpoint is only served when `api_keys` or `jwt`
+//! is configured, and requests need the same credentials as changes to the
+//! server (see [`super::auth`]).
+
+use axum::extract::Query;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use pprof::protos::Message;
+use serde::Deserialize;
+use std::time::Duration;
+
+const DEFAULT_SECONDS: u64 = 30;
+const MAX_SECONDS: u64 = 300;
+/// Samples per second; slightly off 100 to avoid lockstep with timers
+const FREQUENCY: i32 = 99;
+
+#[derive(Debug, Deserialize)]
+pub struct ProfileParams {
+    /// Sampling duration in seconds (default 30, at most 300)
+    seconds: Option<u64>,
+}
+
+fn profile(duration: Duration) -> anyhow::Result<Vec<u8>> {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+    std::thread::sleep(duration);
+    let report = guard.report().build()?;
+    let mut body = Vec::new();
+    report.pprof()?.write_to_vec(&mut body)?;
+    Ok(body)
+}
+
+/// Record a CPU profile of the running server
+pub async fn cpu_profile(Query(params): Query<ProfileParams>) -> Response {
+    let seconds = params
+        .seconds
+        .unwrap_or(DEFAULT_SECONDS)
+        .clamp(1, MAX_SECONDS);
+    log::info!("Recording a {seconds}s CPU profile");
+    // The sampling thread sleeps for the whole duration
+    match tokio::task::spawn_blocking(move || profile(Duration::from_secs(seconds))).await {
+        Ok(Ok(body)) => (
+            [
+                (header::CONTENT_TYPE, "application/octet-stream"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"profile.pb\"",
+                ),
+            ],
+            body,
+        )
+            .into_response(),
+        Ok(Err(e)) => {
+            // Also the case while another profile is being recorded
+            log::error!("CPU profiling failed: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+        Err(e) => {
+            log::error!("CPU profiling task failed: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
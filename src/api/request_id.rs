@@ -0,0 +1,317 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `X-Request-Id` and W3C `traceparent` handling for the management API.
+//!
+//! Every request gets a request ID, taken from its `X-Request-Id` header or
+//! generated, and a trace context, continuing the trace of its `traceparent`
+//! header or starting a new one. Both are echoed in the response headers, the
+//! request ID is added to JSON error bodies, and log lines written while the
+//! request is handled carry it, so a client call can be matched with what the
+//! server did.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::io::Write;
+use std::time::Instant;
+
+use super::tls::ClientIdentity;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Longest client-supplied request ID that is accepted; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+/// Upper bound on error bodies buffered to add the request ID
+const MAX_ERROR_BODY_BYTES: usize = 1024 * 1024;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// ID of the API request being handled by the current task, if any.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Initialize logging from `RUST_LOG` like `env_logger::init`, adding the
+/// request ID to lines logged while an API request is handled.
+pub fn init_logger() {
+    env_logger::Builder::from_default_env()
+        .format(|buf, record| {
+            let level = buf.default_styled_level(record.level());
+            match current_request_id() {
+                Some(id) => writeln!(
+                    buf,
+                    "[{} {level} {} request_id={id}] {}",
+                    buf.timestamp(),
+                    record.target(),
+                    record.args()
+                ),
+                None => writeln!(
+                    buf,
+                    "[{} {level} {}] {}",
+                    buf.timestamp(),
+                    record.target(),
+                    record.args()
+                ),
+            }
+        })
+        .init();
+}
+
+/// W3C trace context of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TraceContext {
+    trace_id: String,
+    /// Span of this server's handling of the request
+    span_id: String,
+    flags: String,
+}
+
+impl TraceContext {
+    /// Continue the trace of a valid `traceparent` header, or start a new one.
+    fn continue_or_start(traceparent: Option<&str>) -> Self {
+        let span_id = random_hex(16);
+        if let Some((trace_id, flags)) = traceparent.and_then(parse_traceparent) {
+            return Self {
+                trace_id,
+                span_id,
+                flags,
+            };
+        }
+        Self {
+            trace_id: random_hex(32),
+            span_id,
+            flags: "01".to_string(),
+        }
+    }
+
+    fn header_value(&self) -> String {
+        format!("00-{}-{}-{}", self.trace_id, self.span_id, self.flags)
+    }
+}
+
+fn is_lower_hex(s: &str, len: usize) -> bool {
+    s.len() == len
+        && s.bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        && s.bytes().any(|b| b != b'0')
+}
+
+/// Trace ID and flags of a version 00 `traceparent` header.
+fn parse_traceparent(value: &str) -> Option<(String, String)> {
+    let mut parts = value.trim().split('-');
+    let (version, trace_id, parent_id, flags) =
+        (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    let valid = version == "00"
+        && parts.next().is_none()
+        && is_lower_hex(trace_id, 32)
+        && is_lower_hex(parent_id, 16)
+        && flags.len() == 2
+        && flags.bytes().all(|b| b.is_ascii_hexdigit());
+    valid.then(|| (trace_id.to_string(), flags.to_string()))
+}
+
+fn random_hex(len: usize) -> String {
+    let mut hex = uuid::Uuid::new_v4().simple().to_string();
+    hex.truncate(len);
+    hex
+}
+
+/// A client-supplied request ID, if it is safe to echo and log.
+fn accepted_request_id(value: Option<&HeaderValue>) -> Option<String> {
+    let id = value?.to_str().ok()?.trim();
+    let valid = !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|b| b.is_ascii_graphic());
+    valid.then(|| id.to_string())
+}
+
+/// Add `request_id` to a JSON error body; other bodies are returned unchanged.
+fn with_request_id(body: &[u8], request_id: &str) -> Option<Vec<u8>> {
+    let mut json: serde_json::Value = serde_json::from_slice(body).ok()?;
+    json.as_object_mut()?.insert(
+        "request_id".to_string(),
+        serde_json::Value::String(request_id.to_string()),
+    );
+    serde_json::to_vec(&json).ok()
+}
+
+/// Middleware that assigns request IDs and trace context.
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let request_id = accepted_request_id(request.headers().get(REQUEST_ID_HEADER))
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let trace = TraceContext::continue_or_start(
+        request
+            .headers()
+            .get(TRACEPARENT_HEADER)
+            .and_then(|v| v.to_str().ok()),
+    );
+    // Handlers and anything they call see the values the client will get back
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        request.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&trace.header_value()) {
+        request.headers_mut().insert(TRACEPARENT_HEADER, value);
+    }
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let client = request
+        .extensions()
+        .get::<ClientIdentity>()
+        .and_then(|identity| identity.subject.clone())
+        .map(|subject| format!(", client={subject}"))
+        .unwrap_or_default();
+    let started = Instant::now();
+    let response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .await;
+    log::debug!(
+        "{method} {path} -> {} in {}ms (request_id={request_id}, trace_id={}{client})",
+        response.status().as_u16(),
+        started.elapsed().as_millis(),
+        trace.trace_id
+    );
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    let mut response = if response.status().as_u16() >= 400 && is_json {
+        let (mut parts, body) = response.into_parts();
+        match to_bytes(body, MAX_ERROR_BODY_BYTES).await {
+            Ok(bytes) => {
+                let body = with_request_id(&bytes, &request_id).unwrap_or_else(|| bytes.to_vec());
+                parts.headers.remove(header::CONTENT_LENGTH);
+                Response::from_parts(parts, Body::from(body))
+            }
+            Err(e) => {
+                log::warn!("Failed to read error response body: {e}");
+                Response::from_parts(parts, Body::empty())
+            }
+        }
+    } else {
+        response
+    };
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&trace.header_value()) {
+        response.headers_mut().insert(TRACEPARENT_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, middleware, response::IntoResponse, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn router() -> Router {
+        Router::new()
+            .route("/ok", get(|| async { current_request_id().unwrap() }))
+            .route(
+                "/missing",
+                get(|| async {
+                    (
+                        StatusCode::NOT_FOUND,
+                        axum::Json(serde_json::json!({ "code": "NOT_FOUND" })),
+                    )
+                        .into_response()
+                }),
+            )
+            .layer(middleware::from_fn(request_id_middleware))
+    }
+
+    fn get_request(uri: &str, headers: &[(&str, &str)]) -> Request {
+        let mut builder = axum::http::Request::builder().uri(uri);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client_request_id_is_echoed_and_visible_to_handlers() {
+        let response = router()
+            .oneshot(get_request("/ok", &[(REQUEST_ID_HEADER, "client-42")]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "client-42");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"client-42");
+    }
+
+    #[tokio::test]
+    async fn test_request_id_is_generated_when_missing_or_unsafe() {
+        let response = router()
+            .oneshot(get_request("/ok", &[(REQUEST_ID_HEADER, "has space")]))
+            .await
+            .unwrap();
+
+        let id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_error_body_includes_request_id() {
+        let response = router()
+            .oneshot(get_request("/missing", &[(REQUEST_ID_HEADER, "abc")]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "NOT_FOUND");
+        assert_eq!(json["request_id"], "abc");
+    }
+
+    #[test]
+    fn test_traceparent_continues_incoming_trace() {
+        let incoming = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+        let trace = TraceContext::continue_or_start(Some(incoming));
+
+        assert_eq!(trace.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_ne!(trace.span_id, "00f067aa0ba902b7");
+        assert!(is_lower_hex(&trace.span_id, 16));
+        assert_eq!(trace.flags, "01");
+    }
+
+    #[test]
+    fn test_invalid_traceparent_starts_new_trace() {
+        for incoming in [
+            "garbage",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        ] {
+            let trace = TraceContext::continue_or_start(Some(incoming));
+            assert_ne!(trace.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+            assert!(is_lower_hex(&trace.trace_id, 32));
+        }
+    }
+}
@@ -0,0 +1,395 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Incremental query results for pull-based clients.
+//!
+//! The server keeps a log of each polled query's results: every row carries
+//! the sequence number at which it appeared, and removed rows are kept as
+//! tombstones. Each `GET /queries/{id}/results/diff` request compares the
+//! current results with the log, assigns a new sequence number if anything
+//! changed, and returns what changed after the client's `since` sequence.
+//!
+//! Sequence numbers start from the wall clock in milliseconds when a query is
+//! first polled, so a sequence from before a server restart is older than any
+//! the new server hands out and the client is told to resync.
+//!
+//! A query's log is dropped when the query is deleted or replaced through the
+//! API, so its copy of the results does not outlive the query.
+
+use axum::{
+    extract::{Extension, Path, Query},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use utoipa::{IntoParams, ToSchema};
+
+use super::error::{ApiError, ErrorCode};
+use super::handlers::{query_results_error, ApiResponse};
+
+/// Removed rows remembered per query; a client further behind must resync
+const MAX_TOMBSTONES: usize = 10_000;
+
+/// Identity of a result row: its JSON text and, for identical rows, which
+/// occurrence it is
+type RowKey = (String, usize);
+
+struct Tombstone {
+    removed_at: u64,
+    added_at: u64,
+    row: serde_json::Value,
+}
+
+struct ResultLog {
+    /// First sequence of this log; older sequences need a resync
+    base: u64,
+    sequence: u64,
+    /// Current rows and the sequence at which each appeared
+    rows: HashMap<RowKey, (serde_json::Value, u64)>,
+    tombstones: VecDeque<Tombstone>,
+    /// Newest sequence whose tombstones have been dropped
+    pruned_through: u64,
+}
+
+fn row_keys(results: Vec<serde_json::Value>) -> Vec<(RowKey, serde_json::Value)> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    results
+        .into_iter()
+        .map(|row| {
+            let text = row.to_string();
+            let occurrence = seen.entry(text.clone()).or_default();
+            let key = (text, *occurrence);
+            *occurrence += 1;
+            (key, row)
+        })
+        .collect()
+}
+
+impl ResultLog {
+    fn new(base: u64, results: Vec<serde_json::Value>) -> Self {
+        Self {
+            base,
+            sequence: base,
+            rows: row_keys(results)
+                .into_iter()
+                .map(|(key, row)| (key, (row, base)))
+                .collect(),
+            tombstones: VecDeque::new(),
+            pruned_through: base,
+        }
+    }
+
+    /// Record the current results, advancing the sequence if they changed.
+    fn update(&mut self, results: Vec<serde_json::Value>) {
+        let current: HashMap<RowKey, serde_json::Value> = row_keys(results).into_iter().collect();
+        let next = self.sequence + 1;
+        let mut changed = false;
+
+        let removed: Vec<RowKey> = self
+            .rows
+            .keys()
+            .filter(|key| !current.contains_key(*key))
+            .cloned()
+            .collect();
+        for key in removed {
+            if let Some((row, added_at)) = self.rows.remove(&key) {
+                self.tombstones.push_back(Tombstone {
+                    removed_at: next,
+                    added_at,
+                    row,
+                });
+                changed = true;
+            }
+        }
+        for (key, row) in current {
+            if let Entry::Vacant(entry) = self.rows.entry(key) {
+                entry.insert((row, next));
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.sequence = next;
+        }
+        while self.tombstones.len() > MAX_TOMBSTONES {
+            if let Some(tombstone) = self.tombstones.pop_front() {
+                self.pruned_through = tombstone.removed_at;
+            }
+        }
+    }
+
+    /// Changes after `since`, or every current row if the client must resync.
+    fn diff(&self, since: Option<u64>, key: Option<&str>) -> ResultsDiff {
+        let since = match since {
+            Some(since)
+                if since >= self.base && since <= self.sequence && since >= self.pruned_through =>
+            {
+                since
+            }
+            _ => {
+                return ResultsDiff {
+                    sequence: self.sequence,
+                    reset: true,
+                    added: self.rows.values().map(|(row, _)| row.clone()).collect(),
+                    updated: Vec::new(),
+                    removed: Vec::new(),
+                }
+            }
+        };
+
+        let mut added: Vec<serde_json::Value> = self
+            .rows
+            .values()
+            .filter(|(_, added_at)| *added_at > since)
+            .map(|(row, _)| row.clone())
+            .collect();
+        // Rows that came and went after `since` were never seen by the client
+        let mut removed: Vec<serde_json::Value> = self
+            .tombstones
+            .iter()
+            .filter(|t| t.removed_at > since && t.added_at <= since)
+            .map(|t| t.row.clone())
+            .collect();
+
+        let mut updated = Vec::new();
+        if let Some(key) = key {
+            added.retain(|row| {
+                let Some(value) = row.get(key) else {
+                    return true;
+                };
+                match removed.iter().position(|old| old.get(key) == Some(value)) {
+                    Some(index) => {
+                        removed.swap_remove(index);
+                        updated.push(row.clone());
+                        false
+                    }
+                    None => true,
+                }
+            });
+        }
+
+        ResultsDiff {
+            sequence: self.sequence,
+            reset: false,
+            added,
+            updated,
+            removed,
+        }
+    }
+}
+
+/// Result logs of the queries that have been polled for diffs, each behind
+/// its own lock so polls of different queries do not wait for each other.
+#[derive(Clone, Default)]
+pub struct ResultLogs {
+    logs: Arc<Mutex<HashMap<String, Arc<Mutex<Option<ResultLog>>>>>>,
+}
+
+impl ResultLogs {
+    /// The log of a query, empty until the query is first polled
+    async fn get(&self, id: &str) -> Arc<Mutex<Option<ResultLog>>> {
+        self.logs
+            .lock()
+            .await
+            .entry(id.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Drop a query's log, so the next poll starts over with a resync.
+    pub async fn evict(&self, id: &str) {
+        self.logs.lock().await.remove(id);
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ResultsDiffParams {
+    /// Sequence from the previous response; omit to get every current row
+    pub since: Option<u64>,
+    /// Result field identifying a row; a removed and an added row with the
+    /// same value are reported as one update
+    pub key: Option<String>,
+}
+
+/// Rows that changed since a client's last sync
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResultsDiff {
+    /// Pass as `since` on the next request
+    pub sequence: u64,
+    /// True if `added` holds the full result set and the client should drop
+    /// what it has
+    pub reset: bool,
+    #[schema(value_type = Vec<Object>)]
+    pub added: Vec<serde_json::Value>,
+    /// Only filled when `key` is given
+    #[schema(value_type = Vec<Object>)]
+    pub updated: Vec<serde_json::Value>,
+    #[schema(value_type = Vec<Object>)]
+    pub removed: Vec<serde_json::Value>,
+}
+
+/// Get the changes to a query's results since a sequence number
+///
+/// The first request, without `since`, returns every row with `reset: true`.
+/// Later requests pass the `sequence` of the previous response and get only
+/// the rows added or removed in between. With `key`, a row whose key field
+/// value was both removed and added is reported under `updated` instead.
+///
+/// Changes are detected by comparing snapshots when the endpoint is called,
+/// so a row that appears and disappears between two requests is not
+/// reported. The server keeps a copy of each polled query's results.
+#[utoipa::path(
+    get,
+    path = "/queries/{id}/results/diff",
+    params(
+        ("id" = String, Path, description = "Query ID"),
+        ResultsDiffParams
+    ),
+    responses(
+        (status = 200, description = "Changed rows", body = ApiResponse),
+        (status = 404, description = "Query not found"),
+        (status = 400, description = "Query is not running"),
+    ),
+    tag = "Queries"
+)]
+pub async fn get_query_results_diff(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(result_logs): Extension<ResultLogs>,
+    Path(id): Path<String>,
+    Query(params): Query<ResultsDiffParams>,
+) -> Result<Json<ApiResponse<ResultsDiff>>, ApiError> {
+    let entry = result_logs.get(&id).await;
+    // Results are read under the query's lock, so concurrent polls record
+    // them in order
+    let mut result_log = entry.lock().await;
+    let results = match core.get_query_results(&id).await {
+        Ok(results) => results,
+        Err(e) => {
+            let error = query_results_error(e);
+            if error.code == ErrorCode::NotFound {
+                drop(result_log);
+                result_logs.evict(&id).await;
+            }
+            return Err(error);
+        }
+    };
+
+    let result_log = match result_log.take() {
+        Some(mut log) => {
+            log.update(results);
+            result_log.insert(log)
+        }
+        None => result_log.insert(ResultLog::new(now_millis(), results)),
+    };
+    Ok(Json(ApiResponse::success(
+        result_log.diff(params.since, params.key.as_deref()),
+    )))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_reports_rows_changed_since_sequence() {
+        let mut log = ResultLog::new(100, vec![json!({"id": 1}), json!({"id": 2})]);
+        log.update(vec![json!({"id": 2}), json!({"id": 3})]);
+
+        let diff = log.diff(Some(100), None);
+
+        assert_eq!(diff.sequence, 101);
+        assert!(!diff.reset);
+        assert_eq!(diff.added, vec![json!({"id": 3})]);
+        assert_eq!(diff.removed, vec![json!({"id": 1})]);
+        assert!(log.diff(Some(101), None).added.is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_results_keep_sequence() {
+        let mut log = ResultLog::new(100, vec![json!({"id": 1})]);
+        log.update(vec![json!({"id": 1})]);
+
+        assert_eq!(log.sequence, 100);
+    }
+
+    #[test]
+    fn test_key_pairs_removed_and_added_rows_as_updates() {
+        let mut log = ResultLog::new(100, vec![json!({"id": 1, "name": "a"})]);
+        log.update(vec![json!({"id": 1, "name": "b"})]);
+
+        let diff = log.diff(Some(100), Some("id"));
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.updated, vec![json!({"id": 1, "name": "b"})]);
+    }
+
+    #[test]
+    fn test_unknown_sequence_resyncs() {
+        let mut log = ResultLog::new(100, vec![json!({"id": 1})]);
+        log.update(vec![json!({"id": 1}), json!({"id": 2})]);
+
+        for since in [None, Some(99), Some(500)] {
+            let diff = log.diff(since, None);
+            assert!(diff.reset);
+            assert_eq!(diff.added.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_row_added_and_removed_after_since_is_not_reported() {
+        let mut log = ResultLog::new(100, vec![]);
+        log.update(vec![json!({"id": 1})]);
+        log.update(vec![]);
+
+        let diff = log.diff(Some(100), None);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn test_identical_rows_are_tracked_separately() {
+        let mut log = ResultLog::new(100, vec![json!({"n": 1}), json!({"n": 1})]);
+        log.update(vec![json!({"n": 1})]);
+
+        let diff = log.diff(Some(100), None);
+
+        assert_eq!(diff.removed, vec![json!({"n": 1})]);
+        assert!(diff.added.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_evicted_log_starts_over() {
+        let logs = ResultLogs::default();
+        *logs.get("q").await.lock().await = Some(ResultLog::new(100, vec![json!({"id": 1})]));
+
+        logs.evict("q").await;
+
+        assert!(logs.get("q").await.lock().await.is_none());
+    }
+}
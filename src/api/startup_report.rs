@@ -0,0 +1,140 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Which components the server could not build at startup, for
+//! `GET /admin/startup-report` and the startup printout.
+//!
+//! Every source and reaction is built before any failure is reported, so one
+//! report lists all of them. Without `tolerate_component_errors` the report
+//! is the startup error; with it the server starts and keeps the report.
+
+use axum::extract::Extension;
+use axum::response::Json;
+use serde::Serialize;
+use std::fmt;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use super::handlers::ApiResponse;
+use crate::registry::ComponentKind;
+
+/// A component that could not be built, or was not started because of one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct StartupFailure {
+    /// `source`, `query` or `reaction`
+    pub kind: String,
+    pub id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct StartupReport {
+    /// Sources and reactions built successfully
+    pub built: usize,
+    /// Failed sources, then the queries held back, then failed reactions
+    pub failures: Vec<StartupFailure>,
+}
+
+impl StartupReport {
+    pub fn record_built(&mut self) {
+        self.built += 1;
+    }
+
+    pub fn record_failure(&mut self, kind: ComponentKind, id: &str, error: impl Into<String>) {
+        self.failures.push(StartupFailure {
+            kind: kind.label().to_string(),
+            id: id.to_string(),
+            error: error.into(),
+        });
+    }
+
+    /// Every component was built and nothing was held back
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Sources and reactions the server tried to build
+    fn attempted(&self) -> usize {
+        self.built
+            + self
+                .failures
+                .iter()
+                .filter(|failure| failure.kind != ComponentKind::Query.label())
+                .count()
+    }
+}
+
+impl fmt::Display for StartupReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Built {} of {} source(s) and reaction(s)",
+            self.built,
+            self.attempted()
+        )?;
+        for failure in &self.failures {
+            write!(
+                f,
+                "\n  {} '{}': {}",
+                failure.kind, failure.id, failure.error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Report which components failed at startup
+///
+/// Lists the sources and reactions that could not be built when the server
+/// started, and the queries that were not started because a source they read
+/// from failed. Empty unless `tolerate_component_errors` is set, since the
+/// server does not start otherwise.
+#[utoipa::path(
+    get,
+    path = "/admin/startup-report",
+    responses(
+        (status = 200, description = "Components that failed at startup", body = ApiResponse),
+    ),
+    tag = "Health"
+)]
+pub async fn startup_report(
+    Extension(report): Extension<Arc<StartupReport>>,
+) -> Json<ApiResponse<StartupReport>> {
+    Json(ApiResponse::success(report.as_ref().clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_every_failure() {
+        let mut report = StartupReport::default();
+        report.record_built();
+        report.record_failure(ComponentKind::Source, "orders", "connection refused");
+        report.record_failure(
+            ComponentKind::Query,
+            "open-orders",
+            "not started: source 'orders' failed to build",
+        );
+
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.to_string(),
+            "Built 1 of 2 source(s) and reaction(s)\n  \
+             source 'orders': connection refused\n  \
+             query 'open-orders': not started: source 'orders' failed to build"
+        );
+    }
+}
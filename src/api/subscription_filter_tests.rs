@@ -0,0 +1,118 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod api_subscription_filter_tests {
+    use crate::api::error::ErrorCode;
+    use crate::api::handlers::*;
+    use crate::api::joins::StrictJoins;
+    use crate::persistence::ConfigPersistence;
+    use axum::{Extension, Json};
+    use drasi_lib::{DrasiLib, Query, QueryConfig};
+    use std::sync::Arc;
+
+    async fn create_test_environment() -> (Arc<DrasiLib>, Arc<bool>, Option<Arc<ConfigPersistence>>)
+    {
+        let core = DrasiLib::builder()
+            .with_id("test-server")
+            .build()
+            .await
+            .expect("Failed to build test core");
+
+        let core = Arc::new(core);
+        core.start().await.expect("Failed to start core");
+
+        (core, Arc::new(false), None)
+    }
+
+    fn filtered_query(id: &str, nodes: &[&str], relations: &[&str]) -> QueryConfig {
+        let mut config = Query::cypher(id)
+            .query("MATCH (o:Order)-[:PLACED_BY]->(c:Customer) RETURN o.id, c.name")
+            .from_source("orders")
+            .auto_start(false)
+            .build();
+        config.sources[0].nodes = nodes.iter().map(|l| l.to_string()).collect();
+        config.sources[0].relations = relations.iter().map(|l| l.to_string()).collect();
+        config
+    }
+
+    #[tokio::test]
+    async fn test_create_query_with_valid_label_filters() {
+        let (core, read_only, config_persistence) = create_test_environment().await;
+
+        let config = filtered_query("filtered-query", &["Order", "Customer"], &["PLACED_BY"]);
+
+        let response = create_query(
+            Extension(core.clone()),
+            Extension(read_only),
+            Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
+            Json(config),
+        )
+        .await
+        .unwrap();
+
+        let json_response = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(json_response["success"], true);
+
+        let stored = core.get_query_config("filtered-query").await.unwrap();
+        assert_eq!(stored.sources[0].nodes, vec!["Order", "Customer"]);
+        assert_eq!(stored.sources[0].relations, vec!["PLACED_BY"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_query_rejects_unknown_node_label_filter() {
+        let (core, read_only, config_persistence) = create_test_environment().await;
+
+        let config = filtered_query("bad-node-filter", &["Order", "Product"], &[]);
+
+        let error = create_query(
+            Extension(core.clone()),
+            Extension(read_only),
+            Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
+            Json(config),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::Validation);
+        assert!(error.message.contains("node label 'Product'"));
+        assert!(core.get_query_config("bad-node-filter").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_query_rejects_unknown_relation_label_filter() {
+        let (core, read_only, config_persistence) = create_test_environment().await;
+
+        let config = filtered_query("bad-rel-filter", &[], &["SHIPPED_TO"]);
+
+        let error = create_query(
+            Extension(core),
+            Extension(read_only),
+            Extension(config_persistence),
+            Extension(StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
+            Json(config),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::Validation);
+        assert!(error.message.contains("relation label 'SHIPPED_TO'"));
+    }
+}
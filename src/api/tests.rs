@@ -34,6 +34,12 @@ mod handler_tests {
         assert_eq!(json["success"], false);
         assert!(json["data"].is_null());
         assert_eq!(json["error"], "Something went wrong");
+        assert!(json.get("code").is_none());
+
+        let error_response: ApiResponse<String> =
+            ApiResponse::error("not found".to_string()).with_code(ErrorCode::NotFound);
+        let json = serde_json::to_value(&error_response).unwrap();
+        assert_eq!(json["code"], "NOT_FOUND");
     }
 
     #[tokio::test]
@@ -47,6 +53,473 @@ mod handler_tests {
         let json = serde_json::to_value(&stopped).unwrap();
         assert_eq!(json, "Stopped");
     }
+
+    async fn create_core_with_defaults() -> std::sync::Arc<drasi_lib::DrasiLib> {
+        let core = drasi_lib::DrasiLib::builder()
+            .with_id("test-server")
+            .with_priority_queue_capacity(20000)
+            .with_dispatch_buffer_capacity(500)
+            .build()
+            .await
+            .unwrap();
+        let core = std::sync::Arc::new(core);
+        core.start().await.unwrap();
+        core
+    }
+
+    #[tokio::test]
+    async fn test_create_query_rejects_zero_capacity() {
+        let core = create_core_with_defaults().await;
+        let mut config = drasi_lib::Query::cypher("zero-capacity")
+            .query("MATCH (n) RETURN n")
+            .from_source("source1")
+            .auto_start(false)
+            .build();
+        config.priority_queue_capacity = Some(0);
+
+        let error = create_query(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(crate::api::joins::StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
+            axum::Json(config),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::Validation);
+        assert!(error.message.contains("priority_queue_capacity"));
+        assert!(core.get_query_config("zero-capacity").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_query_reports_effective_capacities() {
+        let core = create_core_with_defaults().await;
+        let mut config = drasi_lib::Query::cypher("heavy-query")
+            .query("MATCH (n) RETURN n")
+            .from_source("source1")
+            .auto_start(false)
+            .build();
+        config.priority_queue_capacity = Some(50000);
+
+        create_query(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(crate::api::joins::StrictJoins::default()),
+            axum::extract::Query(CreateQueryParams::default()),
+            axum::Json(config),
+        )
+        .await
+        .unwrap();
+
+        let (_, response) = get_query(
+            axum::Extension(core),
+            axum::extract::Path("heavy-query".to_string()),
+        )
+        .await
+        .unwrap();
+
+        let json = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(json["data"]["id"], "heavy-query");
+        // Query override wins, missing override falls back to the server default
+        assert_eq!(json["data"]["effective_priority_queue_capacity"], 50000);
+        assert_eq!(json["data"]["effective_dispatch_buffer_capacity"], 500);
+    }
+
+    #[tokio::test]
+    async fn test_replace_query_swaps_definition() {
+        let core = create_core_with_defaults().await;
+        let config = drasi_lib::Query::cypher("swap-query")
+            .query("MATCH (n) RETURN n")
+            .from_source("source1")
+            .auto_start(false)
+            .build();
+        core.add_query(config).await.unwrap();
+        let registry = std::sync::Arc::new(crate::registry::ComponentRegistry::new());
+
+        let replace = |id: &str, query: &str| {
+            let config = drasi_lib::Query::cypher("swap-query")
+                .query(query)
+                .from_source("source1")
+                .auto_start(false)
+                .build();
+            replace_query(
+                axum::Extension(core.clone()),
+                axum::Extension(std::sync::Arc::new(false)),
+                axum::Extension(None),
+                axum::Extension(registry.clone()),
+                axum::Extension(crate::api::joins::StrictJoins::default()),
+                axum::Extension(crate::api::results_diff::ResultLogs::default()),
+                axum::extract::Path(id.to_string()),
+                axum::extract::Query(CreateQueryParams::default()),
+                axum::http::HeaderMap::new(),
+                axum::Json(config),
+            )
+        };
+
+        replace("swap-query", "MATCH (n:Order) RETURN n.id")
+            .await
+            .unwrap();
+        let stored = core.get_query_config("swap-query").await.unwrap();
+        assert_eq!(stored.query, "MATCH (n:Order) RETURN n.id");
+
+        // An invalid query is rejected before the old one is touched
+        let error = replace("swap-query", "MATCH (n RETURN").await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::Validation);
+        let stored = core.get_query_config("swap-query").await.unwrap();
+        assert_eq!(stored.query, "MATCH (n:Order) RETURN n.id");
+
+        let error = replace("other-query", "MATCH (n) RETURN n")
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::Validation);
+    }
+
+    #[tokio::test]
+    async fn test_delete_query_honors_if_match() {
+        let core = create_core_with_defaults().await;
+        let config = drasi_lib::Query::cypher("etag-query")
+            .query("MATCH (n) RETURN n")
+            .from_source("source1")
+            .auto_start(false)
+            .build();
+        core.add_query(config).await.unwrap();
+        let registry = std::sync::Arc::new(crate::registry::ComponentRegistry::new());
+
+        let (headers, _) = get_query(
+            axum::Extension(core.clone()),
+            axum::extract::Path("etag-query".to_string()),
+        )
+        .await
+        .unwrap();
+        let etag = headers[0].1.clone();
+
+        // A stale tag is rejected and the query is kept
+        let mut stale = axum::http::HeaderMap::new();
+        stale.insert(axum::http::header::IF_MATCH, "\"stale\"".parse().unwrap());
+        let result = delete_query(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry.clone()),
+            axum::Extension(crate::api::results_diff::ResultLogs::default()),
+            axum::extract::Path("etag-query".to_string()),
+            axum::extract::Query(DeleteParams::default()),
+            stale,
+        )
+        .await;
+        assert_eq!(
+            result.err().map(|e| e.status()),
+            Some(axum::http::StatusCode::PRECONDITION_FAILED)
+        );
+        assert!(core.get_query_config("etag-query").await.is_ok());
+
+        // The current tag allows the delete
+        let mut current = axum::http::HeaderMap::new();
+        current.insert(axum::http::header::IF_MATCH, etag.parse().unwrap());
+        let response = delete_query(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry),
+            axum::Extension(crate::api::results_diff::ResultLogs::default()),
+            axum::extract::Path("etag-query".to_string()),
+            axum::extract::Query(DeleteParams::default()),
+            current,
+        )
+        .await
+        .unwrap();
+        let json = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(json["success"], true);
+    }
+
+    #[tokio::test]
+    async fn test_replace_query_joins_honors_if_match() {
+        let core = create_core_with_defaults().await;
+        let config = drasi_lib::Query::cypher("joins-query")
+            .query("MATCH (n) RETURN n")
+            .from_source("source1")
+            .auto_start(false)
+            .build();
+        core.add_query(config).await.unwrap();
+
+        let mut stale = axum::http::HeaderMap::new();
+        stale.insert(axum::http::header::IF_MATCH, "\"stale\"".parse().unwrap());
+        let result = crate::api::joins::replace_query_joins(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(std::sync::Arc::new(
+                crate::registry::ComponentRegistry::new(),
+            )),
+            axum::Extension(crate::api::joins::StrictJoins::default()),
+            axum::extract::Path("joins-query".to_string()),
+            stale,
+            axum::Json(Vec::new()),
+        )
+        .await;
+
+        assert_eq!(
+            result.err().map(|e| e.status()),
+            Some(axum::http::StatusCode::PRECONDITION_FAILED)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete_and_restore_query() {
+        let core = create_core_with_defaults().await;
+        let config = drasi_lib::Query::cypher("soft-query")
+            .query("MATCH (n) RETURN n")
+            .from_source("source1")
+            .auto_start(false)
+            .build();
+        core.add_query(config).await.unwrap();
+        let registry = std::sync::Arc::new(crate::registry::ComponentRegistry::new());
+
+        let response = delete_query(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry.clone()),
+            axum::Extension(crate::api::results_diff::ResultLogs::default()),
+            axum::extract::Path("soft-query".to_string()),
+            axum::extract::Query(DeleteParams { soft: true }),
+            axum::http::HeaderMap::new(),
+        )
+        .await
+        .unwrap();
+        let json = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(json["success"], true);
+
+        // The query is kept but disabled
+        assert!(core.get_query_config("soft-query").await.is_ok());
+        assert!(
+            registry
+                .is_disabled(crate::registry::ComponentKind::Query, "soft-query")
+                .await
+        );
+
+        let response = restore_query(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry.clone()),
+            axum::extract::Path("soft-query".to_string()),
+        )
+        .await
+        .unwrap();
+        let json = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(json["success"], true);
+        assert!(
+            !registry
+                .is_disabled(crate::registry::ComponentKind::Query, "soft-query")
+                .await
+        );
+
+        // Restoring something that does not exist is a 404
+        let result = restore_query(
+            axum::Extension(core),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry),
+            axum::extract::Path("missing".to_string()),
+        )
+        .await;
+        assert_eq!(result.err().map(|e| e.code), Some(ErrorCode::NotFound));
+    }
+
+    #[tokio::test]
+    async fn test_create_existing_source_conflicts() {
+        let core = create_core_with_defaults().await;
+        let registry = std::sync::Arc::new(crate::registry::ComponentRegistry::new());
+        let create = || {
+            create_source_handler(
+                axum::Extension(core.clone()),
+                axum::Extension(std::sync::Arc::new(false)),
+                axum::Extension(None),
+                axum::Extension(registry.clone()),
+                axum::Json(serde_json::json!({"kind": "mock", "id": "s1", "auto_start": false})),
+            )
+        };
+
+        create().await.unwrap();
+        let result = create().await;
+
+        assert_eq!(result.err().map(|e| e.code), Some(ErrorCode::Conflict));
+    }
+
+    #[tokio::test]
+    async fn test_replace_source_swaps_definition() {
+        let core = create_core_with_defaults().await;
+        let registry = std::sync::Arc::new(crate::registry::ComponentRegistry::new());
+        create_source_handler(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry.clone()),
+            axum::Json(serde_json::json!({"kind": "mock", "id": "s1", "auto_start": false})),
+        )
+        .await
+        .unwrap();
+
+        let replace = |id: &str, body: serde_json::Value| {
+            replace_source(
+                axum::Extension(core.clone()),
+                axum::Extension(std::sync::Arc::new(false)),
+                axum::Extension(None),
+                axum::Extension(registry.clone()),
+                axum::extract::Path(id.to_string()),
+                axum::http::HeaderMap::new(),
+                axum::Json(body),
+            )
+        };
+
+        let response = replace(
+            "s1",
+            serde_json::json!({"kind": "mock", "id": "s1", "auto_start": false, "interval_ms": 250}),
+        )
+        .await
+        .unwrap();
+        let json = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(json["success"], true);
+        let stored = serde_json::to_value(registry.get_source("s1").await.unwrap()).unwrap();
+        assert_eq!(stored["interval_ms"], 250);
+        assert!(core.get_source_status("s1").await.is_ok());
+
+        // The body must describe the source named in the path
+        let error = replace("s1", serde_json::json!({"kind": "mock", "id": "s2"}))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::Validation);
+
+        let error = replace("s2", serde_json::json!({"kind": "mock", "id": "s2"}))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_failed_source_is_listed_until_recreated() {
+        let core = create_core_with_defaults().await;
+        let registry = std::sync::Arc::new(crate::registry::ComponentRegistry::new());
+        let config = serde_json::json!({"kind": "mock", "id": "broken", "auto_start": false});
+        registry
+            .insert_failed_source(
+                serde_json::from_value(config.clone()).unwrap(),
+                "connection refused".to_string(),
+            )
+            .await;
+
+        let list = list_sources(
+            axum::Extension(core.clone()),
+            axum::Extension(registry.clone()),
+        )
+        .await;
+        let json = serde_json::to_value(&list.0).unwrap();
+        assert_eq!(json["data"][0]["id"], "broken");
+        assert_eq!(json["data"][0]["status"], "Error");
+        assert_eq!(json["data"][0]["error"], "connection refused");
+
+        // Creating it again with a working definition replaces the failed one
+        create_source_handler(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry.clone()),
+            axum::Json(config),
+        )
+        .await
+        .unwrap();
+        let list = list_sources(axum::Extension(core), axum::Extension(registry)).await;
+        let json = serde_json::to_value(&list.0).unwrap();
+        assert_eq!(json["data"].as_array().unwrap().len(), 1);
+        assert_eq!(json["data"][0]["status"], "Stopped");
+        assert!(json["data"][0].get("error").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_source_can_be_replaced() {
+        let core = create_core_with_defaults().await;
+        let registry = std::sync::Arc::new(crate::registry::ComponentRegistry::new());
+        let config = serde_json::json!({"kind": "mock", "id": "broken", "auto_start": false});
+        registry
+            .insert_failed_source(
+                serde_json::from_value(config.clone()).unwrap(),
+                "connection refused".to_string(),
+            )
+            .await;
+
+        replace_source(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry.clone()),
+            axum::extract::Path("broken".to_string()),
+            axum::http::HeaderMap::new(),
+            axum::Json(config),
+        )
+        .await
+        .unwrap();
+
+        assert!(core.get_source_status("broken").await.is_ok());
+        assert!(registry
+            .build_error(crate::registry::ComponentKind::Source, "broken")
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_start_refuses_disabled_query() {
+        let core = create_core_with_defaults().await;
+        let config = drasi_lib::Query::cypher("disabled-query")
+            .query("MATCH (n) RETURN n")
+            .from_source("source1")
+            .auto_start(false)
+            .build();
+        core.add_query(config).await.unwrap();
+        let registry = std::sync::Arc::new(crate::registry::ComponentRegistry::new());
+
+        let response = disable_query(
+            axum::Extension(core.clone()),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry.clone()),
+            axum::extract::Path("disabled-query".to_string()),
+        )
+        .await
+        .unwrap();
+        let json = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(json["success"], true);
+
+        let error = start_query(
+            axum::Extension(core.clone()),
+            axum::Extension(registry.clone()),
+            axum::extract::Path("disabled-query".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.code, ErrorCode::Conflict);
+        assert!(error.message.contains("disabled"));
+
+        let response = enable_query(
+            axum::Extension(core),
+            axum::Extension(std::sync::Arc::new(false)),
+            axum::Extension(None),
+            axum::Extension(registry.clone()),
+            axum::extract::Path("disabled-query".to_string()),
+        )
+        .await
+        .unwrap();
+        let json = serde_json::to_value(&response.0).unwrap();
+        assert_eq!(json["success"], true);
+        assert!(
+            !registry
+                .is_disabled(crate::registry::ComponentKind::Query, "disabled-query")
+                .await
+        );
+    }
 }
 
 #[cfg(test)]
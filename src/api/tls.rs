@@ -0,0 +1,258 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! HTTPS and mutual TLS for the management API.
+//!
+//! With `tls` set in the config file the API is served over HTTPS using the
+//! PEM certificate chain and private key at `cert_path` and `key_path`. With
+//! `client_ca_path` set as well, clients are asked for a certificate, which
+//! must chain to that CA bundle; `require_client_cert` rejects connections
+//! without one. The subject of a verified client certificate is added to
+//! each request as a [`ClientIdentity`] extension, for audit logging.
+//!
+//! On Unix, `SIGHUP` reloads all files, so a renewed certificate is picked
+//! up without dropping connections or restarting the server.
+
+use anyhow::{anyhow, Context, Result};
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use futures::future::BoxFuture;
+use rustls::crypto::ring::default_provider;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tokio_util::sync::CancellationToken;
+use tower_http::add_extension::AddExtension;
+
+/// Resolved `tls` settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    /// CA bundle client certificates are verified against; without it
+    /// clients are not asked for one
+    pub client_ca_path: Option<PathBuf>,
+    /// Reject connections that present no valid client certificate
+    pub require_client_cert: bool,
+}
+
+/// Client certificate of a TLS connection, added to the extensions of every
+/// request received over it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientIdentity {
+    /// Subject of the verified client certificate, e.g. `CN=ci-pipeline`;
+    /// `None` when the client presented none
+    pub subject: Option<String>,
+}
+
+impl TlsConfig {
+    /// Load the certificates and key.
+    pub fn load(&self) -> Result<RustlsConfig> {
+        Ok(RustlsConfig::from_config(Arc::new(self.server_config()?)))
+    }
+
+    fn server_config(&self) -> Result<ServerConfig> {
+        let provider = Arc::new(default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()?;
+        let builder = match &self.client_ca_path {
+            Some(path) => {
+                let mut roots = RootCertStore::empty();
+                for cert in read_certs(path)? {
+                    roots.add(cert).with_context(|| {
+                        format!("Invalid CA certificate in '{}'", path.display())
+                    })?;
+                }
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+                let verifier = if self.require_client_cert {
+                    verifier.build()?
+                } else {
+                    verifier.allow_unauthenticated().build()?
+                };
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder
+            .with_single_cert(read_certs(&self.cert_path)?, read_key(&self.key_path)?)
+            .with_context(|| {
+                format!(
+                    "Failed to load TLS certificate '{}' and key '{}'",
+                    self.cert_path.display(),
+                    self.key_path.display()
+                )
+            })?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+
+    /// Reload the certificates and key into `rustls` whenever the process
+    /// receives `SIGHUP`, until `shutdown` is cancelled. A failed reload is
+    /// logged and the previous certificates stay in use.
+    #[cfg(unix)]
+    pub fn reload_on_sighup(
+        &self,
+        rustls: RustlsConfig,
+        shutdown: CancellationToken,
+    ) -> Result<Option<tokio::task::JoinHandle<()>>> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangups = signal(SignalKind::hangup()).context("Failed to listen for SIGHUP")?;
+        let tls = self.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let hangup = tokio::select! {
+                    _ = shutdown.cancelled() => None,
+                    hangup = hangups.recv() => hangup,
+                };
+                if hangup.is_none() {
+                    break;
+                }
+                match tls.server_config() {
+                    Ok(config) => {
+                        rustls.reload_from_config(Arc::new(config));
+                        log::info!("Reloaded TLS certificate from {}", tls.cert_path.display());
+                    }
+                    Err(e) => log::error!(
+                        "Failed to reload TLS certificate, keeping the current one: {e:#}"
+                    ),
+                }
+            }
+        });
+        Ok(Some(task))
+    }
+
+    #[cfg(not(unix))]
+    pub fn reload_on_sighup(
+        &self,
+        _rustls: RustlsConfig,
+        _shutdown: CancellationToken,
+    ) -> Result<Option<tokio::task::JoinHandle<()>>> {
+        Ok(None)
+    }
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).with_context(|| format!("Cannot read '{}'", path.display()))
+}
+
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let pem = read_pem(path)?;
+    let certs = rustls_pemfile::certs(&mut pem.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid PEM in '{}'", path.display()))?;
+    if certs.is_empty() {
+        return Err(anyhow!("No certificates in '{}'", path.display()));
+    }
+    Ok(certs)
+}
+
+fn read_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let pem = read_pem(path)?;
+    rustls_pemfile::private_key(&mut pem.as_slice())
+        .with_context(|| format!("Invalid PEM in '{}'", path.display()))?
+        .ok_or_else(|| anyhow!("No private key in '{}'", path.display()))
+}
+
+/// Subject of a DER certificate, if it parses.
+fn certificate_subject(cert: &CertificateDer<'_>) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(cert.as_ref()).ok()?;
+    Some(cert.subject().to_string())
+}
+
+/// Accepts TLS connections and adds the [`ClientIdentity`] of each to the
+/// requests received over it.
+#[derive(Clone)]
+pub struct ClientCertAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl ClientCertAcceptor {
+    pub fn new(config: RustlsConfig) -> Self {
+        Self {
+            inner: RustlsAcceptor::new(config),
+        }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientCertAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+    RustlsAcceptor: Accept<I, S, Stream = TlsStream<I>, Service = S>,
+    <RustlsAcceptor as Accept<I, S>>::Future: Send,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, ClientIdentity>;
+    type Future = BoxFuture<'static, std::io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let accept = self.inner.accept(stream, service);
+        Box::pin(async move {
+            let (stream, service) = accept.await?;
+            let identity = ClientIdentity {
+                subject: stream
+                    .get_ref()
+                    .1
+                    .peer_certificates()
+                    .and_then(|certs| certs.first())
+                    .and_then(certificate_subject),
+            };
+            Ok((stream, AddExtension::new(service, identity)))
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_certificate_names_the_file() {
+        let tls = TlsConfig {
+            cert_path: PathBuf::from("/nonexistent/server.crt"),
+            key_path: PathBuf::from("/nonexistent/server.key"),
+            client_ca_path: None,
+            require_client_cert: false,
+        };
+
+        let err = tls.load().err().unwrap();
+
+        assert!(
+            format!("{err:#}").contains("/nonexistent/server.crt"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn test_missing_client_ca_names_the_file() {
+        let tls = TlsConfig {
+            cert_path: PathBuf::from("/nonexistent/server.crt"),
+            key_path: PathBuf::from("/nonexistent/server.key"),
+            client_ca_path: Some(PathBuf::from("/nonexistent/clients-ca.pem")),
+            require_client_cert: true,
+        };
+
+        let err = tls.load().err().unwrap();
+
+        assert!(format!("{err:#}").contains("clients-ca.pem"), "{err:#}");
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! How much a running server holds, for `GET /admin/usage`.
+//!
+//! Counts and sizes are measured on every request, so the response is a
+//! current snapshot. The server has no per-object quotas; the limits reported
+//! are the configured capacities also shown by `GET /about`.
+
+use axum::extract::Extension;
+use axum::response::Json;
+use drasi_lib::channels::ComponentStatus;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use super::about::{AboutLimits, AboutResponse};
+use super::handlers::ApiResponse;
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+/// Where query indexes are stored on disk; `None` when they are in memory
+#[derive(Debug, Clone, Default)]
+pub struct IndexDir(pub Option<PathBuf>);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ComponentCounts {
+    pub total: usize,
+    /// Running or starting
+    pub running: usize,
+    /// Registered but could not be built (`tolerate_component_errors`)
+    pub failed: usize,
+}
+
+impl ComponentCounts {
+    fn new(statuses: &[(String, ComponentStatus)], failed: usize) -> Self {
+        Self {
+            total: statuses.len() + failed,
+            running: statuses
+                .iter()
+                .filter(|(_, status)| {
+                    matches!(status, ComponentStatus::Running | ComponentStatus::Starting)
+                })
+                .count(),
+            failed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UsageResponse {
+    pub sources: ComponentCounts,
+    pub queries: ComponentCounts,
+    pub reactions: ComponentCounts,
+    /// Result rows currently held by running queries
+    pub result_rows: usize,
+    /// Size of the RocksDB index directory; `null` when indexes are in memory
+    pub index_bytes: Option<u64>,
+    pub limits: AboutLimits,
+}
+
+/// Total size of the files under `path`
+fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(size)
+}
+
+/// Report how much the server holds
+///
+/// Counts sources, queries and reactions, the result rows held by running
+/// queries and the disk space used by persistent indexes, next to the
+/// configured capacities, for a capacity overview in one request.
+#[utoipa::path(
+    get,
+    path = "/admin/usage",
+    responses(
+        (status = 200, description = "Component counts, held results and index size", body = ApiResponse),
+    ),
+    tag = "Health"
+)]
+pub async fn usage(
+    Extension(core): Extension<Arc<drasi_lib::DrasiLib>>,
+    Extension(registry): Extension<Arc<ComponentRegistry>>,
+    Extension(about): Extension<Arc<AboutResponse>>,
+    Extension(index_dir): Extension<Arc<IndexDir>>,
+) -> Json<ApiResponse<UsageResponse>> {
+    let sources = core.list_sources().await.unwrap_or_default();
+    let queries = core.list_queries().await.unwrap_or_default();
+    let reactions = core.list_reactions().await.unwrap_or_default();
+
+    let mut result_rows = 0;
+    for (id, status) in &queries {
+        if matches!(status, ComponentStatus::Running) {
+            // A query that stops in the meantime no longer holds any rows
+            if let Ok(results) = core.get_query_results(id).await {
+                result_rows += results.len();
+            }
+        }
+    }
+
+    let index_bytes = match index_dir.0.clone() {
+        Some(path) => tokio::task::spawn_blocking(move || match dir_size(&path) {
+            Ok(size) => Some(size),
+            Err(e) => {
+                log::warn!("Failed to measure index directory {}: {e}", path.display());
+                None
+            }
+        })
+        .await
+        .unwrap_or_default(),
+        None => None,
+    };
+
+    Json(ApiResponse::success(UsageResponse {
+        sources: ComponentCounts::new(&sources, registry.failed(ComponentKind::Source).await.len()),
+        queries: ComponentCounts::new(&queries, 0),
+        reactions: ComponentCounts::new(
+            &reactions,
+            registry.failed(ComponentKind::Reaction).await.len(),
+        ),
+        result_rows,
+        index_bytes,
+        limits: about.limits.clone(),
+    }))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_include_failed_components() {
+        let statuses = vec![
+            ("s1".to_string(), ComponentStatus::Running),
+            ("s2".to_string(), ComponentStatus::Stopped),
+        ];
+
+        assert_eq!(
+            ComponentCounts::new(&statuses, 1),
+            ComponentCounts {
+                total: 3,
+                running: 1,
+                failed: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_dir_size_includes_subdirectories() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a"), [0u8; 10]).unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub").join("b"), [0u8; 5]).unwrap();
+
+        assert_eq!(dir_size(dir.path()).unwrap(), 15);
+    }
+}
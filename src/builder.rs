@@ -45,6 +45,21 @@ impl DrasiServerBuilder {
         Self::default()
     }
 
+    /// Create a builder for a throwaway server, for tests and demos
+    ///
+    /// The API is served on a free port on 127.0.0.1, indexes are kept in
+    /// memory and nothing is written to disk. Use [`Self::start`] to learn the
+    /// port that was picked.
+    pub fn ephemeral() -> Self {
+        Self {
+            enable_api: true,
+            port: Some(0),
+            host: Some("127.0.0.1".to_string()),
+            config_file_path: None,
+            ..Self::default()
+        }
+    }
+
     /// Set the server ID
     pub fn with_id(mut self, id: impl Into<String>) -> Self {
         self.core_builder = self.core_builder.with_id(id);
@@ -149,6 +164,12 @@ impl DrasiServerBuilder {
         Ok(server)
     }
 
+    /// Build the server and start it, returning a handle with the bound API
+    /// address
+    pub async fn start(self) -> anyhow::Result<crate::server::RunningServer> {
+        self.build().await?.start().await
+    }
+
     /// Build a DrasiLib instance, start it, and return a handle
     ///
     /// Note: Application source/reaction handles were removed during the plugin architecture refactor.
@@ -180,6 +201,14 @@ mod tests {
         assert!(!builder.enable_api);
     }
 
+    #[test]
+    fn test_ephemeral_builder_picks_a_free_port() {
+        let builder = DrasiServerBuilder::ephemeral();
+        assert!(builder.enable_api);
+        assert_eq!(builder.port, Some(0));
+        assert_eq!(builder.config_file_path, None);
+    }
+
     #[test]
     fn test_builder_fluent_api() {
         let builder = DrasiServerBuilder::new()
@@ -0,0 +1,187 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Comparison of query results between two running servers.
+//!
+//! Used by `drasi-server compare` to check that two deployments, e.g. the old
+//! and new version during a blue/green upgrade, agree on a query's results.
+//! Results are compared as unordered collections of rows.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Differences between the results of one query on two servers.
+#[derive(Debug, Default, PartialEq)]
+pub struct ResultDiff {
+    /// Rows present on both servers
+    pub matching: usize,
+    /// Rows only the left server returned
+    pub only_left: Vec<Value>,
+    /// Rows only the right server returned
+    pub only_right: Vec<Value>,
+    /// Rows with the same key but different values, as (left, right)
+    pub changed: Vec<(Value, Value)>,
+}
+
+impl ResultDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_left.is_empty() && self.only_right.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Text of a row with object fields sorted, so field order does not matter.
+fn canonical(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut fields: Vec<(&String, &Value)> = map.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(name, value)| {
+                    format!("{}:{}", Value::from(name.as_str()), canonical(value))
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Compare two result sets, ignoring row order.
+///
+/// With a `key` field, rows that are not identical but share the key value
+/// are reported as changed instead of as one row missing on each side.
+pub fn diff_results(left: &[Value], right: &[Value], key: Option<&str>) -> ResultDiff {
+    let mut unmatched: HashMap<String, usize> = HashMap::new();
+    for row in right {
+        *unmatched.entry(canonical(row)).or_default() += 1;
+    }
+    let mut take = |row: &Value| match unmatched.get_mut(&canonical(row)) {
+        Some(count) if *count > 0 => {
+            *count -= 1;
+            true
+        }
+        _ => false,
+    };
+
+    let mut diff = ResultDiff::default();
+    let mut only_left = Vec::new();
+    for row in left {
+        if take(row) {
+            diff.matching += 1;
+        } else {
+            only_left.push(row);
+        }
+    }
+    // What is left in `unmatched` are the right rows no left row matched
+    let mut only_right: Vec<&Value> = right.iter().filter(|&row| take(row)).collect();
+
+    for row in only_left {
+        let key_value = key.and_then(|k| row.get(k));
+        let paired = key_value.and_then(|value| {
+            only_right
+                .iter()
+                .position(|other| key.and_then(|k| other.get(k)) == Some(value))
+        });
+        match paired {
+            Some(index) => {
+                let other = only_right.remove(index);
+                diff.changed.push((row.clone(), other.clone()));
+            }
+            None => diff.only_left.push(row.clone()),
+        }
+    }
+    diff.only_right = only_right.into_iter().cloned().collect();
+    diff
+}
+
+/// Fetch the current results of `query_id` from the server at `base_url`.
+pub async fn fetch_results(
+    client: &reqwest::Client,
+    base_url: &str,
+    query_id: &str,
+) -> Result<Vec<Value>> {
+    let url = format!("{}/queries/{query_id}/results", base_url.trim_end_matches('/'));
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("{url} returned {status}"));
+    }
+    let body: Value = response
+        .json()
+        .await
+        .with_context(|| format!("Invalid response from {url}"))?;
+    if body["success"] != Value::Bool(true) {
+        let error = body["error"].as_str().unwrap_or("unknown error");
+        return Err(anyhow!("{url}: {error}"));
+    }
+    serde_json::from_value(body["data"].clone())
+        .with_context(|| format!("Unexpected results format from {url}"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_identical_results_in_any_order_match() {
+        let left = vec![json!({"id": 1}), json!({"id": 2}), json!({"id": 2})];
+        let right = vec![json!({"id": 2}), json!({"id": 1}), json!({"id": 2})];
+
+        let diff = diff_results(&left, &right, None);
+
+        assert!(diff.is_empty());
+        assert_eq!(diff.matching, 3);
+    }
+
+    #[test]
+    fn test_missing_and_changed_rows() {
+        let left = vec![
+            json!({"id": 1, "stock": 5}),
+            json!({"id": 2, "stock": 7}),
+            json!({"id": 3, "stock": 1}),
+        ];
+        let right = vec![
+            json!({"id": 1, "stock": 5}),
+            json!({"id": 2, "stock": 8}),
+            json!({"id": 4, "stock": 0}),
+        ];
+
+        let diff = diff_results(&left, &right, Some("id"));
+
+        assert_eq!(diff.matching, 1);
+        assert_eq!(
+            diff.changed,
+            vec![(json!({"id": 2, "stock": 7}), json!({"id": 2, "stock": 8}))]
+        );
+        assert_eq!(diff.only_left, vec![json!({"id": 3, "stock": 1})]);
+        assert_eq!(diff.only_right, vec![json!({"id": 4, "stock": 0})]);
+
+        let unkeyed = diff_results(&left, &right, None);
+        assert!(unkeyed.changed.is_empty());
+        assert_eq!(unkeyed.only_left.len(), 2);
+    }
+}
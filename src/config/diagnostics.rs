@@ -0,0 +1,335 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Readable descriptions of config parse errors.
+//!
+//! Adds the offending line of the file and, for unknown fields and `kind`
+//! values, the closest valid name:
+//!
+//! ```text
+//! line 4, column 5: sources[0]: unknown variant `htpp`, expected one of `mock`, `http`, ... - did you mean `http`?
+//!     4 |   - kind: htpp
+//! ```
+
+/// Optimal string alignment distance: edits, with adjacent transpositions
+/// counted as one edit so `htpp` is one step from `http`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// The candidate closest to `name`, if it is close enough to be a typo.
+pub(crate) fn suggest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), *candidate))
+        .filter(|(distance, _)| *distance > 0 && *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Backtick-quoted words in `text`.
+fn quoted(text: &str) -> Vec<&str> {
+    text.split('`').skip(1).step_by(2).collect()
+}
+
+/// For serde's "unknown field/variant `x`, expected ..." messages, a hint
+/// naming the closest expected value.
+fn hint(message: &str) -> Option<String> {
+    let rest = ["unknown variant `", "unknown field `"]
+        .iter()
+        .find_map(|marker| message.find(marker).map(|i| &message[i + marker.len()..]))?;
+    let (name, expected) = rest.split_once('`')?;
+    let candidates = quoted(expected.split_once("expected")?.1);
+    suggest(name, &candidates).map(|best| format!(" - did you mean `{best}`?"))
+}
+
+/// Whether a value carries no settings, so a key holding it is harmless even
+/// if the parsed config does not write it back.
+fn is_empty_value(value: &serde_yaml::Value) -> bool {
+    use serde_yaml::Value;
+    match value {
+        Value::Null => true,
+        Value::Sequence(items) => items.is_empty(),
+        Value::Mapping(mapping) => mapping.values().all(is_empty_value),
+        _ => false,
+    }
+}
+
+/// A copy of `value` with every scalar in it changed.
+fn changed(value: &serde_yaml::Value) -> serde_yaml::Value {
+    use serde_yaml::Value;
+    match value {
+        Value::Bool(b) => Value::Bool(!b),
+        Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(n), _) => Value::from(n.wrapping_add(1)),
+            (None, Some(n)) => Value::from(n.wrapping_add(1)),
+            _ => Value::from(n.as_f64().unwrap_or_default() + 1.0),
+        },
+        Value::String(s) => Value::String(format!("{s}~")),
+        Value::Sequence(items) => Value::Sequence(items.iter().map(changed).collect()),
+        Value::Mapping(mapping) => Value::Mapping(
+            mapping
+                .iter()
+                .map(|(key, value)| (key.clone(), changed(value)))
+                .collect(),
+        ),
+        Value::Tagged(tagged) => Value::Tagged(Box::new(serde_yaml::value::TaggedValue {
+            tag: tagged.tag.clone(),
+            value: changed(&tagged.value),
+        })),
+        Value::Null => Value::Null,
+    }
+}
+
+/// A step from a document to one of its values
+#[derive(Clone)]
+enum Segment {
+    Key(serde_yaml::Value),
+    Index(usize),
+}
+
+fn value_at<'a>(
+    document: &'a mut serde_yaml::Value,
+    path: &[Segment],
+) -> Option<&'a mut serde_yaml::Value> {
+    path.iter()
+        .try_fold(document, |value, segment| match segment {
+            Segment::Key(key) => value.as_mapping_mut()?.get_mut(key),
+            Segment::Index(i) => value.as_sequence_mut()?.get_mut(*i),
+        })
+}
+
+/// Walks a document next to its parsed form to find the keys the config
+/// does not read.
+struct UnknownFields<'a, F> {
+    raw: &'a serde_yaml::Value,
+    parsed: &'a serde_yaml::Value,
+    parse: F,
+    unknown: Vec<String>,
+}
+
+impl<F> UnknownFields<'_, F>
+where
+    F: Fn(&serde_yaml::Value) -> Option<serde_yaml::Value>,
+{
+    /// Whether the config reads the value at `path`: changing it either makes
+    /// the document fail to parse or changes what it parses to. A field set
+    /// to the default that serialization skips is read; an unknown field has
+    /// no effect.
+    fn is_read(&self, path: &[Segment]) -> bool {
+        let mut document = self.raw.clone();
+        let Some(value) = value_at(&mut document, path) else {
+            return false;
+        };
+        *value = changed(value);
+        (self.parse)(&document).as_ref() != Some(self.parsed)
+    }
+
+    fn collect(
+        &mut self,
+        raw: &serde_yaml::Value,
+        parsed: &serde_yaml::Value,
+        path: &mut Vec<Segment>,
+        name: &str,
+    ) {
+        use serde_yaml::Value;
+        match (raw, parsed) {
+            (Value::Mapping(raw), Value::Mapping(parsed)) => {
+                let known: Vec<&str> = parsed.keys().filter_map(Value::as_str).collect();
+                for (key, value) in raw {
+                    let Some(field) = key.as_str() else {
+                        continue;
+                    };
+                    let child = if name.is_empty() {
+                        field.to_string()
+                    } else {
+                        format!("{name}.{field}")
+                    };
+                    path.push(Segment::Key(key.clone()));
+                    match parsed.get(key) {
+                        Some(parsed_value) => self.collect(value, parsed_value, path, &child),
+                        None if is_empty_value(value) || self.is_read(path) => {}
+                        None => {
+                            let hint = suggest(field, &known)
+                                .map(|best| format!(" (did you mean `{best}`?)"))
+                                .unwrap_or_default();
+                            self.unknown.push(format!("`{child}`{hint}"));
+                        }
+                    }
+                    path.pop();
+                }
+            }
+            (Value::Sequence(raw), Value::Sequence(parsed)) => {
+                for (i, (raw, parsed)) in raw.iter().zip(parsed).enumerate() {
+                    path.push(Segment::Index(i));
+                    self.collect(raw, parsed, path, &format!("{name}[{i}]"));
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Fields present in the `raw` document that the config does not read.
+///
+/// `parsed` is the parsed config serialized back, and `parse` parses a
+/// document the same way, returning it serialized or `None` if it fails.
+/// Keys missing from `parsed` are either unknown or set to a default that
+/// serialization skips, so each of those is checked by changing its value and
+/// parsing again.
+///
+/// Serde ignores unknown fields by default, and neither `deny_unknown_fields`
+/// nor `serde_ignored` sees into the internally tagged, flattened component
+/// configs, so the check is done on the documents instead.
+pub(crate) fn unknown_fields<F>(
+    raw: &serde_yaml::Value,
+    parsed: &serde_yaml::Value,
+    parse: F,
+) -> Vec<String>
+where
+    F: Fn(&serde_yaml::Value) -> Option<serde_yaml::Value>,
+{
+    let mut walk = UnknownFields {
+        raw,
+        parsed,
+        parse,
+        unknown: Vec::new(),
+    };
+    walk.collect(raw, parsed, &mut Vec::new(), "");
+    walk.unknown
+}
+
+/// Describe a parse error at an optional 1-based `line`/`column` of `content`.
+fn describe(content: &str, message: &str, location: Option<(usize, usize)>) -> String {
+    let hint = hint(message).unwrap_or_default();
+    match location {
+        Some((line, column)) => {
+            let mut description = format!("line {line}, column {column}: {message}{hint}");
+            if let Some(text) = content.lines().nth(line.saturating_sub(1)) {
+                description.push_str(&format!("\n    {line} | {text}"));
+            }
+            description
+        }
+        None => format!("{message}{hint}"),
+    }
+}
+
+pub(crate) fn describe_yaml_error(content: &str, err: &serde_yaml::Error) -> String {
+    let message = err.to_string();
+    let location = err.location().map(|l| (l.line(), l.column()));
+    // The location is shown separately, with the offending line
+    let message = match message.rfind(" at line ") {
+        Some(i) if location.is_some() => message[..i].to_string(),
+        _ => message,
+    };
+    describe(content, &message, location)
+}
+
+pub(crate) fn describe_json_error(content: &str, err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    let message = match message.rfind(" at line ") {
+        Some(i) => message[..i].to_string(),
+        None => message,
+    };
+    describe(content, &message, Some((err.line(), err.column())))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::config::DrasiServerConfig;
+
+    #[test]
+    fn test_suggest_closest_candidate() {
+        assert_eq!(suggest("htpp", &["mock", "http", "grpc"]), Some("http"));
+        assert_eq!(suggest("auto_strat", &["id", "auto_start"]), Some("auto_start"));
+        assert_eq!(suggest("zzzzzz", &["mock", "http"]), None);
+    }
+
+    fn unknown_in(yaml: &str) -> Vec<String> {
+        let parse = |document: &serde_yaml::Value| {
+            let config: DrasiServerConfig = serde_yaml::from_value(document.clone()).ok()?;
+            serde_yaml::to_value(&config).ok()
+        };
+        let raw: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+        let parsed = parse(&raw).unwrap();
+        unknown_fields(&raw, &parsed, parse)
+    }
+
+    #[test]
+    fn test_unknown_fields_are_reported_with_path() {
+        assert_eq!(
+            unknown_in(
+                "sources:\n  - kind: mock\n    id: s1\n    auto_strat: false\ndisabled: {sources: []}\n"
+            ),
+            vec!["`sources[0].auto_strat` (did you mean `auto_start`?)"]
+        );
+    }
+
+    #[test]
+    fn test_fields_set_to_their_default_are_known() {
+        let yaml = r#"
+tolerate_component_errors: false
+split_files: false
+docs:
+  enabled: true
+  require_auth: false
+tls:
+  cert_path: server.crt
+  key_path: server.key
+  require_client_cert: false
+sources:
+  - kind: mock
+    id: s1
+    lazy: false
+"#;
+
+        assert_eq!(unknown_in(yaml), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_unknown_kind_shows_line_and_suggestion() {
+        let content = "port: 8080\nsources:\n  - kind: htpp\n    id: s1\n";
+        let err = serde_yaml::from_str::<DrasiServerConfig>(content).unwrap_err();
+
+        let description = describe_yaml_error(content, &err);
+
+        assert!(description.starts_with("line "), "{description}");
+        assert!(description.contains(" | "), "{description}");
+        assert!(description.contains("did you mean `http`?"), "{description}");
+    }
+}
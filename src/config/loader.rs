@@ -16,6 +16,8 @@
 //!
 //! This module provides the primary interface for loading Drasi Server configuration files.
 
+use super::diagnostics::{describe_json_error, describe_yaml_error, unknown_fields};
+use super::split_files::{include_section_files, section_files};
 use super::types::DrasiServerConfig;
 use serde::de::DeserializeOwned;
 use std::fs;
@@ -33,19 +35,242 @@ pub enum ConfigError {
     #[error("Failed to parse JSON: {0}")]
     JsonError(#[from] serde_json::Error),
 
-    #[error(
-        "Failed to parse config file '{path}': YAML error: {yaml_err}, JSON error: {json_err}"
-    )]
-    ParseError {
-        path: String,
-        yaml_err: String,
-        json_err: String,
-    },
+    #[error("Failed to parse config file '{path}': {message}")]
+    ParseError { path: String, message: String },
+
+    #[error("Invalid environment override {var}: {reason}")]
+    EnvOverrideError { var: String, reason: String },
+
+    #[error("Unknown fields in config file (strict mode): {0}")]
+    UnknownFields(String),
 
     #[error("Validation error: {0}")]
     ValidationError(#[from] anyhow::Error),
 }
 
+/// Prefix of environment variables that override config file values.
+///
+/// The rest of the name is the config path with `__` between segments and
+/// list indexes as numbers, e.g. `DRASI__PORT` or `DRASI__SOURCES__0__PORT`.
+pub const ENV_OVERRIDE_PREFIX: &str = "DRASI__";
+
+/// Collect the override variables from `vars`, sorted by name.
+pub fn env_overrides<I>(vars: I) -> Vec<(String, String)>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let mut overrides: Vec<_> = vars
+        .into_iter()
+        .filter(|(name, _)| name.starts_with(ENV_OVERRIDE_PREFIX))
+        .collect();
+    overrides.sort();
+    overrides
+}
+
+/// Overrides given on the command line with `--set` and `--strict`.
+///
+/// They are passed to [`load_config_file_with`] rather than put into the
+/// environment, so they do not leak to processes the server runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliOverrides {
+    /// `--set` arguments, as translated by [`parse_set_override`]
+    pub set: Vec<(String, String)>,
+    /// Reject unknown fields, as with `strict: true` in the file
+    pub strict: bool,
+}
+
+/// Translate a `--set path=value` argument into the equivalent `DRASI__`
+/// override, e.g. `sources[0].port=9001` becomes `DRASI__sources__0__port`.
+///
+/// Segments are separated by `.`; list indexes may be written as `[0]` or as a
+/// plain `.0` segment. Their case is kept, so map keys such as query ids can
+/// be addressed exactly.
+///
+/// # Errors
+///
+/// Returns an error if the argument has no `=` or the path has an empty segment.
+pub fn parse_set_override(arg: &str) -> Result<(String, String), ConfigError> {
+    let fail = |reason: &str| ConfigError::EnvOverrideError {
+        var: format!("--set {arg}"),
+        reason: reason.to_string(),
+    };
+    let (path, value) = arg
+        .split_once('=')
+        .ok_or_else(|| fail("expected PATH=VALUE"))?;
+    let segments: Vec<String> = path
+        .replace('[', ".")
+        .replace(']', "")
+        .split('.')
+        .map(|segment| segment.trim().to_string())
+        .collect();
+    if segments.iter().any(String::is_empty) {
+        return Err(fail("path has an empty segment"));
+    }
+    Ok((
+        format!("{ENV_OVERRIDE_PREFIX}{}", segments.join("__")),
+        value.to_string(),
+    ))
+}
+
+/// An override's value read as a YAML scalar, so `9090` becomes a number and
+/// `true` a boolean. Anything else, including an empty value, stays a string.
+fn scalar(raw: &str) -> serde_yaml::Value {
+    match serde_yaml::from_str::<serde_yaml::Value>(raw) {
+        Ok(v) if !raw.is_empty() && !v.is_mapping() && !v.is_sequence() => v,
+        _ => serde_yaml::Value::String(raw.to_string()),
+    }
+}
+
+/// The key in `map` a path segment refers to.
+///
+/// Environment variable names are upper case, so an existing key matches
+/// regardless of case, preferring an exact match. A new key is lower case
+/// when the segment is all upper case, and written as given otherwise.
+fn mapping_key(map: &serde_yaml::Mapping, segment: &str) -> serde_yaml::Value {
+    let exact = serde_yaml::Value::String(segment.to_string());
+    if map.contains_key(&exact) {
+        return exact;
+    }
+    if let Some(key) = map.keys().find(|key| {
+        key.as_str()
+            .is_some_and(|key| key.eq_ignore_ascii_case(segment))
+    }) {
+        return key.clone();
+    }
+    if segment.chars().any(char::is_lowercase) {
+        exact
+    } else {
+        serde_yaml::Value::String(segment.to_lowercase())
+    }
+}
+
+/// Set the value at an override variable's path.
+fn set_override(
+    document: &mut serde_yaml::Value,
+    var: &str,
+    value: serde_yaml::Value,
+) -> Result<(), ConfigError> {
+    let fail = |reason: String| ConfigError::EnvOverrideError {
+        var: var.to_string(),
+        reason,
+    };
+    let path: Vec<&str> = var[ENV_OVERRIDE_PREFIX.len()..].split("__").collect();
+    if path.iter().any(|segment| segment.is_empty()) {
+        return Err(fail("path has an empty segment".to_string()));
+    }
+
+    let mut current = document;
+    for segment in path {
+        if current.is_null() {
+            *current = serde_yaml::Value::Mapping(Default::default());
+        }
+        current = match current {
+            serde_yaml::Value::Mapping(map) => {
+                let key = mapping_key(map, segment);
+                map.entry(key).or_insert(serde_yaml::Value::Null)
+            }
+            serde_yaml::Value::Sequence(seq) => {
+                let len = seq.len();
+                let index: usize = segment
+                    .parse()
+                    .map_err(|_| fail(format!("'{segment}' is not a list index")))?;
+                seq.get_mut(index).ok_or_else(|| {
+                    fail(format!(
+                        "index {index} is out of range (list has {len} items)"
+                    ))
+                })?
+            }
+            _ => return Err(fail(format!("'{segment}' is inside a scalar value"))),
+        };
+    }
+    *current = value;
+    Ok(())
+}
+
+/// Apply `DRASI__` overrides to a parsed config document.
+///
+/// A value that reads as a number or boolean is set as one where the config
+/// expects it, and as a string where the config expects a string, so
+/// `DRASI__SOURCES__0__PASSWORD=123456` stays a password. Missing mapping keys
+/// are created; list indexes must already exist.
+///
+/// # Errors
+///
+/// Returns an error if a path is empty, indexes past the end of a list, or
+/// descends into a scalar value.
+pub fn apply_env_overrides(
+    document: &mut serde_yaml::Value,
+    overrides: &[(String, String)],
+) -> Result<(), ConfigError> {
+    let parses = |document: &serde_yaml::Value| {
+        serde_yaml::from_value::<DrasiServerConfig>(document.clone()).is_ok()
+    };
+    let with = |base: &serde_yaml::Value, var: &str, value: serde_yaml::Value| {
+        let mut document = base.clone();
+        set_override(&mut document, var, value).map(|_| document)
+    };
+
+    // Decide each ambiguous value on the file as it is, then on the file with
+    // the other overrides for those that only fit together with them
+    let base = document.clone();
+    let mut values: Vec<serde_yaml::Value> = overrides.iter().map(|(_, raw)| scalar(raw)).collect();
+    let mut undecided = Vec::new();
+    for (i, (var, raw)) in overrides.iter().enumerate() {
+        if values[i].is_string() || parses(&with(&base, var, values[i].clone())?) {
+            continue;
+        }
+        let as_string = serde_yaml::Value::String(raw.clone());
+        if parses(&with(&base, var, as_string.clone())?) {
+            values[i] = as_string;
+        } else {
+            undecided.push(i);
+        }
+    }
+    let mut all = base;
+    for ((var, _), value) in overrides.iter().zip(&values) {
+        set_override(&mut all, var, value.clone())?;
+    }
+    for i in undecided {
+        let (var, raw) = &overrides[i];
+        let as_string = serde_yaml::Value::String(raw.clone());
+        if !parses(&all) && parses(&with(&all, var, as_string.clone())?) {
+            set_override(&mut all, var, as_string)?;
+        }
+    }
+
+    *document = all;
+    Ok(())
+}
+
+/// Report whichever of the two parse errors matches what the file looks like.
+fn parse_error(
+    path: &Path,
+    content: &str,
+    yaml_err: &serde_yaml::Error,
+    json_err: &serde_json::Error,
+) -> ConfigError {
+    let message = if content.trim_start().starts_with('{') {
+        describe_json_error(content, json_err)
+    } else {
+        describe_yaml_error(content, yaml_err)
+    };
+    ConfigError::ParseError {
+        path: path.display().to_string(),
+        message,
+    }
+}
+
+/// Parse a YAML or JSON config document without deserializing it.
+pub(crate) fn parse_document(content: &str, path: &Path) -> Result<serde_yaml::Value, ConfigError> {
+    match serde_yaml::from_str::<serde_yaml::Value>(content) {
+        Ok(value) => Ok(value),
+        Err(yaml_err) => match serde_json::from_str::<serde_yaml::Value>(content) {
+            Ok(value) => Ok(value),
+            Err(json_err) => Err(parse_error(path, content, &yaml_err, &json_err)),
+        },
+    }
+}
+
 /// Deserialize YAML.
 ///
 /// # Arguments
@@ -85,7 +310,12 @@ pub fn from_json_str<T: DeserializeOwned>(s: &str) -> Result<T, ConfigError> {
 /// This is the primary function for loading Drasi Server configuration. It:
 /// 1. Reads the file
 /// 2. Tries to parse as YAML, falls back to JSON if that fails
-/// 3. Validates the configuration
+/// 3. Reads `sources`, `queries` and `reactions` that name a file from that
+///    file (see [`super::split_files`])
+/// 4. Applies `DRASI__` environment variable overrides (see [`ENV_OVERRIDE_PREFIX`])
+///    and then `--set` overrides, so a `--set` flag wins for the same path
+/// 5. In strict mode, rejects fields the server does not recognize
+/// 6. Validates the configuration
 ///
 /// # Arguments
 ///
@@ -100,6 +330,9 @@ pub fn from_json_str<T: DeserializeOwned>(s: &str) -> Result<T, ConfigError> {
 /// Returns an error if:
 /// - File cannot be read
 /// - File is neither valid YAML nor JSON
+/// - A file named by `sources`, `queries` or `reactions` cannot be read or is not a list
+/// - An environment override does not fit the document
+/// - Strict mode is on and the file contains unknown fields
 /// - Configuration validation fails
 ///
 /// # Examples
@@ -111,27 +344,73 @@ pub fn from_json_str<T: DeserializeOwned>(s: &str) -> Result<T, ConfigError> {
 /// println!("Server configuration loaded successfully");
 /// ```
 pub fn load_config_file<P: AsRef<Path>>(path: P) -> Result<DrasiServerConfig, ConfigError> {
+    load_config_file_with(path, &CliOverrides::default())
+}
+
+/// Load DrasiServerConfig from a file, as [`load_config_file`] does, with
+/// command line overrides on top.
+///
+/// # Errors
+///
+/// As [`load_config_file`], and if a `--set` override does not fit the
+/// document.
+pub fn load_config_file_with<P: AsRef<Path>>(
+    path: P,
+    cli: &CliOverrides,
+) -> Result<DrasiServerConfig, ConfigError> {
     let path_ref = path.as_ref();
     let content = fs::read_to_string(path_ref)?;
 
-    // Try YAML first, then JSON
-    let config = match serde_yaml::from_str::<DrasiServerConfig>(&content) {
-        Ok(config) => config,
-        Err(yaml_err) => {
-            // If YAML fails, try JSON
-            match serde_json::from_str::<DrasiServerConfig>(&content) {
-                Ok(config) => config,
-                Err(json_err) => {
-                    return Err(ConfigError::ParseError {
-                        path: path_ref.display().to_string(),
-                        yaml_err: yaml_err.to_string(),
-                        json_err: json_err.to_string(),
-                    });
+    let mut overrides = env_overrides(std::env::vars());
+    overrides.extend(cli.set.iter().cloned());
+    let names_files = serde_yaml::from_str::<serde_yaml::Value>(&content)
+        .is_ok_and(|document| !section_files(&document).is_empty());
+    let (config, document) = if overrides.is_empty() && !names_files {
+        // Try YAML first, then JSON
+        let config = match serde_yaml::from_str::<DrasiServerConfig>(&content) {
+            Ok(config) => config,
+            Err(yaml_err) => {
+                // If YAML fails, try JSON
+                match serde_json::from_str::<DrasiServerConfig>(&content) {
+                    Ok(config) => config,
+                    Err(json_err) => {
+                        return Err(parse_error(path_ref, &content, &yaml_err, &json_err));
+                    }
                 }
             }
+        };
+        (config, None)
+    } else {
+        let mut document = parse_document(&content, path_ref)?;
+        include_section_files(&mut document, path_ref)?;
+        apply_env_overrides(&mut document, &overrides)?;
+        for (var, _) in &overrides {
+            log::info!("Applied config override from {var}");
         }
+        let config = serde_yaml::from_value(document.clone()).map_err(|e| {
+            ConfigError::ParseError {
+                path: path_ref.display().to_string(),
+                message: describe_yaml_error(&content, &e),
+            }
+        })?;
+        (config, Some(document))
     };
 
+    if config.strict || cli.strict {
+        let document = match document {
+            Some(document) => document,
+            None => parse_document(&content, path_ref)?,
+        };
+        let parse = |document: &serde_yaml::Value| {
+            let config: DrasiServerConfig = serde_yaml::from_value(document.clone()).ok()?;
+            serde_yaml::to_value(&config).ok()
+        };
+        let unknown = unknown_fields(&document, &serde_yaml::to_value(&config)?, parse);
+        if !unknown.is_empty() {
+            return Err(ConfigError::UnknownFields(unknown.join(", ")));
+        }
+    }
+
     // Validate the configuration
     config.validate()?;
 
@@ -178,7 +457,7 @@ mod tests {
 
         // Create a config
         let config = DrasiServerConfig {
-            host: crate::api::models::ConfigValue::Static("localhost".to_string()),
+            host: crate::api::models::ConfigValue::Static("localhost".into()),
             port: crate::api::models::ConfigValue::Static(9090),
             ..DrasiServerConfig::default()
         };
@@ -199,6 +478,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_load_reports_location_and_suggestion() {
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(temp_file.path(), "sources:\n  - kind: htpp\n    id: s1\n").unwrap();
+
+        let err = load_config_file(temp_file.path()).unwrap_err().to_string();
+
+        assert!(err.contains("line "), "{err}");
+        assert!(err.contains("did you mean `http`?"), "{err}");
+        assert!(!err.contains("JSON"), "{err}");
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_unknown_fields() {
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(temp_file.path(), "strict: true\nport: 8080\nlog_levle: debug\n").unwrap();
+
+        let err = load_config_file(temp_file.path()).unwrap_err().to_string();
+
+        assert!(err.contains("`log_levle` (did you mean `log_level`?)"), "{err}");
+    }
+
+    #[test]
+    fn test_cli_overrides_apply_without_the_environment() {
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(temp_file.path(), "port: 8080\nlog_levle: debug\n").unwrap();
+        let mut cli = CliOverrides {
+            set: vec![parse_set_override("port=9090").unwrap()],
+            strict: false,
+        };
+
+        let config = load_config_file_with(temp_file.path(), &cli).unwrap();
+        assert_eq!(config.port, crate::api::models::ConfigValue::Static(9090));
+
+        cli.strict = true;
+        assert!(load_config_file_with(temp_file.path(), &cli).is_err());
+        assert!(load_config_file(temp_file.path()).is_ok());
+    }
+
     #[test]
     fn test_load_basic_config() {
         let config_content = r#"
@@ -222,4 +540,190 @@ reactions: []
         );
         assert_eq!(config.port, crate::api::models::ConfigValue::Static(8080));
     }
+
+    fn overrides(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        env_overrides(
+            vars.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .chain([("PATH".to_string(), "/usr/bin".to_string())]),
+        )
+    }
+
+    #[test]
+    fn test_env_overrides_set_nested_values() {
+        let mut document: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+port: 8080
+sources:
+  - kind: mock
+    id: s1
+"#,
+        )
+        .unwrap();
+
+        apply_env_overrides(
+            &mut document,
+            &overrides(&[
+                ("DRASI__PORT", "9090"),
+                ("DRASI__LOG_LEVEL", "debug"),
+                ("DRASI__SOURCES__0__AUTO_START", "false"),
+            ]),
+        )
+        .unwrap();
+
+        let config: DrasiServerConfig = serde_yaml::from_value(document).unwrap();
+        assert_eq!(config.port, crate::api::models::ConfigValue::Static(9090));
+        assert_eq!(
+            config.log_level,
+            crate::api::models::ConfigValue::Static("debug".to_string())
+        );
+        assert!(!config.sources[0].auto_start());
+    }
+
+    #[test]
+    fn test_env_overrides_keep_strings_that_look_like_numbers() {
+        let mut document: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+id: server
+sources:
+  - kind: postgres
+    id: pg
+    host: localhost
+    database: app
+    user: app
+    password: secret
+    tables: [orders]
+"#,
+        )
+        .unwrap();
+
+        apply_env_overrides(
+            &mut document,
+            &overrides(&[
+                ("DRASI__ID", "1"),
+                ("DRASI__PORT", "9090"),
+                ("DRASI__SOURCES__0__PASSWORD", "123456"),
+                ("DRASI__SOURCES__0__AUTO_START", "false"),
+            ]),
+        )
+        .unwrap();
+
+        let config: DrasiServerConfig = serde_yaml::from_value(document.clone()).unwrap();
+        assert_eq!(
+            config.id,
+            crate::api::models::ConfigValue::Static("1".to_string())
+        );
+        assert_eq!(config.port, crate::api::models::ConfigValue::Static(9090));
+        assert!(!config.sources[0].auto_start());
+        assert_eq!(
+            document["sources"][0]["password"],
+            serde_yaml::Value::from("123456")
+        );
+    }
+
+    #[test]
+    fn test_env_overrides_match_map_keys_regardless_of_case() {
+        let mut document: serde_yaml::Value =
+            serde_yaml::from_str("routes:\n  OrderTotals:\n    topic: a\n").unwrap();
+
+        apply_env_overrides(
+            &mut document,
+            &overrides(&[
+                ("DRASI__ROUTES__ORDERTOTALS__TOPIC", "b"),
+                ("DRASI__routes__NewQuery__topic", "c"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            document["routes"]["OrderTotals"]["topic"],
+            serde_yaml::Value::from("b")
+        );
+        assert_eq!(
+            document["routes"]["NewQuery"]["topic"],
+            serde_yaml::Value::from("c")
+        );
+    }
+
+    #[test]
+    fn test_env_override_rejects_missing_list_item() {
+        let mut document: serde_yaml::Value = serde_yaml::from_str("sources: []").unwrap();
+
+        let err = apply_env_overrides(
+            &mut document,
+            &overrides(&[("DRASI__SOURCES__1__PORT", "1")]),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("DRASI__SOURCES__1__PORT"));
+        assert!(err.to_string().contains("out of range"));
+    }
+
+    #[test]
+    fn test_parse_set_override() {
+        assert_eq!(
+            parse_set_override("sources[0].port=9001").unwrap(),
+            ("DRASI__sources__0__port".to_string(), "9001".to_string())
+        );
+        assert_eq!(
+            parse_set_override("log_level=debug").unwrap(),
+            ("DRASI__log_level".to_string(), "debug".to_string())
+        );
+        assert!(parse_set_override("port").is_err());
+        assert!(parse_set_override("sources..port=1").is_err());
+    }
+
+    #[test]
+    fn test_load_source_dispatch_settings() {
+        let config_content = r#"
+sources:
+  - kind: mock
+    id: fast-source
+    dispatch_mode: broadcast
+    dispatch_buffer_capacity: 2000
+  - kind: mock
+    id: default-source
+"#;
+
+        let config: DrasiServerConfig = from_yaml_str(config_content).unwrap();
+
+        assert_eq!(
+            config.sources[0].dispatch_mode(),
+            Some(crate::api::models::DispatchModeDto::Broadcast)
+        );
+        assert_eq!(config.sources[0].dispatch_buffer_capacity(), Some(2000));
+        assert_eq!(config.sources[1].dispatch_mode(), None);
+        assert_eq!(config.sources[1].dispatch_buffer_capacity(), None);
+    }
+
+    #[test]
+    fn test_load_source_rejects_unknown_dispatch_mode() {
+        let config_content = r#"
+sources:
+  - kind: mock
+    id: bad-source
+    dispatch_mode: partitioned
+"#;
+
+        assert!(from_yaml_str::<DrasiServerConfig>(config_content).is_err());
+    }
+
+    #[test]
+    fn test_load_reaction_wait_for_queries() {
+        let config_content = r#"
+reactions:
+  - kind: log
+    id: gated-reaction
+    queries: [my-query]
+    wait_for_queries_ms: 30000
+  - kind: log
+    id: plain-reaction
+    queries: [my-query]
+"#;
+
+        let config: DrasiServerConfig = from_yaml_str(config_content).unwrap();
+
+        assert_eq!(config.reactions[0].wait_for_queries_ms(), Some(30000));
+        assert_eq!(config.reactions[1].wait_for_queries_ms(), None);
+    }
 }
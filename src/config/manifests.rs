@@ -0,0 +1,561 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Import of Drasi platform resource manifests.
+//!
+//! The Drasi platform describes components as `Source`, `ContinuousQuery` and
+//! `Reaction` resources:
+//!
+//! ```yaml
+//! apiVersion: v1
+//! kind: Source
+//! name: orders-db
+//! spec:
+//!   kind: PostgreSQL
+//!   properties:
+//!     host: postgres
+//!     database: orders
+//! ```
+//!
+//! This module translates those documents into the server's own config types
+//! so a platform deployment can be moved to a standalone server. Only kinds
+//! that have a standalone equivalent are supported; anything else is an error,
+//! and settings that cannot be carried over are reported as warnings.
+
+use anyhow::{anyhow, Context, Result};
+use drasi_lib::config::{
+    QueryConfig, QueryJoinConfig, QueryJoinKeyConfig, QueryLanguage, SourceSubscriptionConfig,
+};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::Path;
+
+use super::{DrasiServerConfig, ReactionConfig, SourceConfig};
+
+/// Components translated from a set of manifests.
+#[derive(Debug, Default)]
+pub struct ManifestImport {
+    pub sources: Vec<SourceConfig>,
+    pub queries: Vec<QueryConfig>,
+    pub reactions: Vec<ReactionConfig>,
+    /// Settings that were dropped during translation
+    pub warnings: Vec<String>,
+}
+
+impl ManifestImport {
+    /// Translate one resource, given the way the platform API reports it:
+    /// its kind (`Source`, `ContinuousQuery` or `Reaction`), name and spec.
+    pub fn add_resource(&mut self, kind: &str, name: &str, spec: Value) -> Result<()> {
+        let manifest = Manifest {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            spec,
+        };
+        translate_into(&manifest, self)
+    }
+
+    /// Merge the imported components into `config`, replacing any component
+    /// with the same ID. Returns one line per change.
+    pub fn merge_into(self, config: &mut DrasiServerConfig) -> Vec<String> {
+        let mut changes = Vec::new();
+        for source in self.sources {
+            let id = source.id().to_string();
+            match config.sources.iter_mut().find(|s| s.id() == id) {
+                Some(existing) => {
+                    *existing = source;
+                    changes.push(format!("replaced source '{id}'"));
+                }
+                None => {
+                    config.sources.push(source);
+                    changes.push(format!("created source '{id}'"));
+                }
+            }
+        }
+        for query in self.queries {
+            let id = query.id.clone();
+            match config.queries.iter_mut().find(|q| q.id == id) {
+                Some(existing) => {
+                    *existing = query;
+                    changes.push(format!("replaced query '{id}'"));
+                }
+                None => {
+                    config.queries.push(query);
+                    changes.push(format!("created query '{id}'"));
+                }
+            }
+        }
+        for reaction in self.reactions {
+            let id = reaction.id().to_string();
+            match config.reactions.iter_mut().find(|r| r.id() == id) {
+                Some(existing) => {
+                    *existing = reaction;
+                    changes.push(format!("replaced reaction '{id}'"));
+                }
+                None => {
+                    config.reactions.push(reaction);
+                    changes.push(format!("created reaction '{id}'"));
+                }
+            }
+        }
+        changes
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    kind: String,
+    name: String,
+    #[serde(default)]
+    spec: Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuerySpec {
+    query: String,
+    #[serde(default)]
+    query_language: Option<String>,
+    #[serde(default)]
+    sources: QuerySources,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct QuerySources {
+    #[serde(default)]
+    subscriptions: Vec<Subscription>,
+    #[serde(default)]
+    joins: Vec<Join>,
+    #[serde(default)]
+    middleware: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Subscription {
+    id: String,
+    #[serde(default)]
+    nodes: Vec<LabelRef>,
+    #[serde(default)]
+    relations: Vec<LabelRef>,
+    #[serde(default)]
+    pipeline: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LabelRef {
+    source_label: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Join {
+    id: String,
+    keys: Vec<JoinKey>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JoinKey {
+    label: String,
+    property: String,
+}
+
+/// Read every `.yaml`/`.yml` file under `path` (or `path` itself if it is a
+/// file) and translate the manifests in them.
+pub fn load_manifests<P: AsRef<Path>>(path: P) -> Result<ManifestImport> {
+    let path = path.as_ref();
+    let mut files = Vec::new();
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let file = entry?.path();
+            let is_yaml = file
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e == "yaml" || e == "yml");
+            if is_yaml {
+                files.push(file);
+            }
+        }
+        files.sort();
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    let mut import = ManifestImport::default();
+    for file in files {
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        parse_manifests_into(&content, &mut import)
+            .with_context(|| format!("Invalid manifest in {}", file.display()))?;
+    }
+    Ok(import)
+}
+
+/// Translate the manifests in a (possibly multi-document) YAML string.
+pub fn parse_manifests(content: &str) -> Result<ManifestImport> {
+    let mut import = ManifestImport::default();
+    parse_manifests_into(content, &mut import)?;
+    Ok(import)
+}
+
+fn parse_manifests_into(content: &str, import: &mut ManifestImport) -> Result<()> {
+    for document in serde_yaml::Deserializer::from_str(content) {
+        let value = serde_yaml::Value::deserialize(document)?;
+        if value.is_null() {
+            continue;
+        }
+        let manifest: Manifest = serde_yaml::from_value(value)?;
+        translate_into(&manifest, import)?;
+    }
+    Ok(())
+}
+
+fn translate_into(manifest: &Manifest, import: &mut ManifestImport) -> Result<()> {
+    match manifest.kind.as_str() {
+        "Source" => {
+            let source = translate_source(manifest, import)?;
+            import.sources.push(source);
+        }
+        "ContinuousQuery" => {
+            let query = translate_query(manifest, import)?;
+            import.queries.push(query);
+        }
+        "Reaction" => {
+            let reaction = translate_reaction(manifest, import)?;
+            import.reactions.push(reaction);
+        }
+        other => {
+            return Err(anyhow!(
+                "Unsupported manifest kind '{other}' for '{}': expected Source, ContinuousQuery or Reaction",
+                manifest.name
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn properties(manifest: &Manifest) -> Map<String, Value> {
+    manifest
+        .spec
+        .get("properties")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Platform manifests reference Kubernetes secrets; the standalone server
+/// reads the same values from environment variables instead. Returns the
+/// names of the variables that were introduced.
+fn secrets_to_env_vars(properties: &mut Map<String, Value>) -> Vec<String> {
+    let mut vars = Vec::new();
+    for value in properties.values_mut() {
+        let secret = value
+            .as_object()
+            .filter(|o| o.get("kind").and_then(Value::as_str) == Some("Secret"));
+        if let Some(secret) = secret {
+            let name = secret.get("name").and_then(Value::as_str).unwrap_or("");
+            let key = secret.get("key").and_then(Value::as_str).unwrap_or("");
+            let var = format!("{name}_{key}")
+                .to_uppercase()
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+            *value = json!({"kind": "EnvironmentVariable", "name": var});
+            vars.push(var);
+        }
+    }
+    vars
+}
+
+fn warn_env_vars(import: &mut ManifestImport, name: &str, vars: Vec<String>) {
+    for var in vars {
+        import.warnings.push(format!(
+            "'{name}': secret reference replaced by environment variable {var}"
+        ));
+    }
+}
+
+fn translate_source(manifest: &Manifest, import: &mut ManifestImport) -> Result<SourceConfig> {
+    let kind = manifest.spec.get("kind").and_then(Value::as_str).unwrap_or("");
+    let mut props = properties(manifest);
+    let vars = secrets_to_env_vars(&mut props);
+    warn_env_vars(import, &manifest.name, vars);
+
+    let mut config = Map::new();
+    config.insert("id".to_string(), json!(manifest.name));
+    match kind {
+        "PostgreSQL" => {
+            config.insert("kind".to_string(), json!("postgres"));
+            for (key, value) in props {
+                match key.as_str() {
+                    "ssl" => {
+                        let mode = if value.as_bool().unwrap_or(false) {
+                            "require"
+                        } else {
+                            "disable"
+                        };
+                        config.insert("ssl_mode".to_string(), json!(mode));
+                    }
+                    _ => {
+                        config.insert(key, value);
+                    }
+                }
+            }
+        }
+        other => {
+            return Err(anyhow!(
+                "Source '{}' has kind '{other}', which has no standalone equivalent (supported: PostgreSQL)",
+                manifest.name
+            ))
+        }
+    }
+
+    serde_json::from_value(Value::Object(config))
+        .with_context(|| format!("Failed to translate source '{}'", manifest.name))
+}
+
+fn translate_query(manifest: &Manifest, import: &mut ManifestImport) -> Result<QueryConfig> {
+    let spec: QuerySpec = serde_json::from_value(manifest.spec.clone())
+        .with_context(|| format!("Failed to read ContinuousQuery '{}'", manifest.name))?;
+
+    let query_language = match spec.query_language.as_deref() {
+        None | Some("Cypher") => QueryLanguage::Cypher,
+        Some("GQL") => QueryLanguage::GQL,
+        Some(other) => {
+            return Err(anyhow!(
+                "ContinuousQuery '{}' has unknown queryLanguage '{other}'",
+                manifest.name
+            ))
+        }
+    };
+
+    if !spec.sources.middleware.is_empty() {
+        import.warnings.push(format!(
+            "Query '{}': middleware is not imported and must be configured by hand",
+            manifest.name
+        ));
+    }
+
+    let mut sources = Vec::new();
+    for subscription in spec.sources.subscriptions {
+        if !subscription.pipeline.is_empty() {
+            import.warnings.push(format!(
+                "Query '{}': pipeline for source '{}' is not imported",
+                manifest.name, subscription.id
+            ));
+        }
+        sources.push(SourceSubscriptionConfig {
+            source_id: subscription.id,
+            nodes: subscription
+                .nodes
+                .into_iter()
+                .map(|l| l.source_label)
+                .collect(),
+            relations: subscription
+                .relations
+                .into_iter()
+                .map(|l| l.source_label)
+                .collect(),
+            pipeline: vec![],
+        });
+    }
+
+    let joins = (!spec.sources.joins.is_empty()).then(|| {
+        spec.sources
+            .joins
+            .into_iter()
+            .map(|join| QueryJoinConfig {
+                id: join.id,
+                keys: join
+                    .keys
+                    .into_iter()
+                    .map(|key| QueryJoinKeyConfig {
+                        label: key.label,
+                        property: key.property,
+                    })
+                    .collect(),
+            })
+            .collect()
+    });
+
+    Ok(QueryConfig {
+        id: manifest.name.clone(),
+        query: spec.query,
+        query_language,
+        auto_start: true,
+        enable_bootstrap: true,
+        bootstrap_buffer_size: 10000,
+        middleware: vec![],
+        sources,
+        joins,
+        priority_queue_capacity: None,
+        dispatch_buffer_capacity: None,
+        dispatch_mode: None,
+        storage_backend: None,
+    })
+}
+
+fn translate_reaction(manifest: &Manifest, import: &mut ManifestImport) -> Result<ReactionConfig> {
+    let kind = manifest.spec.get("kind").and_then(Value::as_str).unwrap_or("");
+    // Platform reactions map each query ID to per-query settings
+    let queries: Vec<String> = manifest
+        .spec
+        .get("queries")
+        .and_then(Value::as_object)
+        .map(|q| q.keys().cloned().collect())
+        .unwrap_or_default();
+    let mut props = properties(manifest);
+    let vars = secrets_to_env_vars(&mut props);
+    warn_env_vars(import, &manifest.name, vars);
+
+    let mut config = Map::new();
+    config.insert("id".to_string(), json!(manifest.name));
+    config.insert("queries".to_string(), json!(queries));
+    match kind {
+        "Debug" => {
+            config.insert("kind".to_string(), json!("log"));
+        }
+        "Http" => {
+            config.insert("kind".to_string(), json!("http"));
+            for (key, value) in props {
+                let key = match key.as_str() {
+                    "baseUrl" => "base_url".to_string(),
+                    "timeoutMs" => "timeout_ms".to_string(),
+                    _ => key,
+                };
+                config.insert(key, value);
+            }
+        }
+        other => {
+            return Err(anyhow!(
+                "Reaction '{}' has kind '{other}', which has no standalone equivalent (supported: Debug, Http)",
+                manifest.name
+            ))
+        }
+    }
+
+    serde_json::from_value(Value::Object(config))
+        .with_context(|| format!("Failed to translate reaction '{}'", manifest.name))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const MANIFESTS: &str = r#"
+apiVersion: v1
+kind: Source
+name: orders-db
+spec:
+  kind: PostgreSQL
+  properties:
+    host: postgres
+    port: 5432
+    user: drasi
+    password:
+      kind: Secret
+      name: pg-creds
+      key: password
+    database: orders
+    ssl: false
+    tables:
+      - public.orders
+---
+apiVersion: v1
+kind: ContinuousQuery
+name: large-orders
+spec:
+  mode: query
+  sources:
+    subscriptions:
+      - id: orders-db
+        nodes:
+          - sourceLabel: orders
+  query: >
+    MATCH (o:orders) WHERE o.total > 100 RETURN o.id
+---
+apiVersion: v1
+kind: Reaction
+name: order-log
+spec:
+  kind: Debug
+  queries:
+    large-orders:
+"#;
+
+    #[test]
+    fn test_parse_platform_manifests() {
+        let import = parse_manifests(MANIFESTS).unwrap();
+
+        assert_eq!(import.sources.len(), 1);
+        assert_eq!(import.sources[0].id(), "orders-db");
+        assert_eq!(import.sources[0].kind(), "postgres");
+        let source = serde_json::to_value(&import.sources[0]).unwrap();
+        assert_eq!(source["password"]["name"], "PG_CREDS_PASSWORD");
+        assert_eq!(source["ssl_mode"], "disable");
+
+        assert_eq!(import.queries.len(), 1);
+        assert_eq!(import.queries[0].id, "large-orders");
+        assert_eq!(import.queries[0].sources[0].source_id, "orders-db");
+        assert_eq!(import.queries[0].sources[0].nodes, vec!["orders"]);
+
+        assert_eq!(import.reactions.len(), 1);
+        assert_eq!(import.reactions[0].kind(), "log");
+        assert_eq!(import.reactions[0].queries(), ["large-orders".to_string()]);
+        assert_eq!(import.warnings.len(), 1);
+        assert!(import.warnings[0].contains("PG_CREDS_PASSWORD"));
+    }
+
+    #[test]
+    fn test_merge_replaces_components_with_same_id() {
+        let mut config = DrasiServerConfig::default();
+        parse_manifests(MANIFESTS).unwrap().merge_into(&mut config);
+        let changes = parse_manifests(MANIFESTS).unwrap().merge_into(&mut config);
+
+        assert_eq!(config.sources.len(), 1);
+        assert_eq!(config.queries.len(), 1);
+        assert_eq!(config.reactions.len(), 1);
+        assert_eq!(changes[0], "replaced source 'orders-db'");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_unsupported_source_kind_is_rejected() {
+        let manifest = r#"
+kind: Source
+name: graph
+spec:
+  kind: CosmosGremlin
+"#;
+        let err = parse_manifests(manifest).unwrap_err();
+        assert!(err.to_string().contains("CosmosGremlin"));
+    }
+
+    #[test]
+    fn test_query_middleware_is_reported() {
+        let manifest = r#"
+kind: ContinuousQuery
+name: q
+spec:
+  query: MATCH (n) RETURN n
+  sources:
+    subscriptions:
+      - id: s
+    middleware:
+      - kind: unwind
+        name: items
+"#;
+        let import = parse_manifests(manifest).unwrap();
+        assert_eq!(import.warnings.len(), 1);
+        assert!(import.warnings[0].contains("middleware"));
+    }
+}
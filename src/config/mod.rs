@@ -32,12 +32,19 @@
 //! println!("Server configuration loaded successfully");
 //! ```
 
+mod diagnostics;
 pub mod loader;
+pub mod manifests;
+pub mod split_files;
 pub mod types;
+pub mod yaml_patch;
 
 // Re-export commonly used types
 pub use loader::{from_json_str, from_yaml_str, load_config_file, save_config_file, ConfigError};
-pub use types::DrasiServerConfig;
+pub use types::{
+    BindHosts, DisabledComponents, DocsSettings, DrasiServerConfig, HeartbeatSettings, JwtSettings,
+    OpenApiContactSettings, OpenApiServerSettings, OpenApiSettings, RuntimeSettings, TlsSettings,
+};
 
 // Re-export config enums from api::models for backward compatibility
 pub use crate::api::models::{ReactionConfig, SourceConfig};
@@ -0,0 +1,244 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Config files whose component lists are kept in separate files.
+//!
+//! Instead of a list, `sources`, `queries` and `reactions` in the main config
+//! file may name a YAML file holding the list, relative to the main file:
+//!
+//! ```yaml
+//! sources: sources.yaml
+//! queries: queries.yaml
+//! ```
+//!
+//! When persisting, a section that names a file is written back to that file.
+//! With `split_files: true` every section is moved to its own file, so a
+//! change to one kind of component only shows up in that component's file.
+
+use log::debug;
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::loader::{parse_document, ConfigError};
+use super::yaml_patch::{patch_list_document, patch_split_document};
+use super::DrasiServerConfig;
+
+/// Top-level sections that can be kept in a separate file
+pub const SECTIONS: [&str; 3] = ["sources", "queries", "reactions"];
+
+/// Directory that file names in the main config file are relative to
+fn base_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or_else(|| Path::new(""))
+}
+
+/// Sections of a parsed main config file that name a file, with the name.
+pub fn section_files(document: &Value) -> Vec<(&'static str, String)> {
+    SECTIONS
+        .iter()
+        .filter_map(|section| {
+            document
+                .get(section)
+                .and_then(Value::as_str)
+                .map(|file| (*section, file.to_string()))
+        })
+        .collect()
+}
+
+/// Replace every section of the main config file at `path` that names a file
+/// with the list read from that file.
+pub fn include_section_files(document: &mut Value, path: &Path) -> Result<(), ConfigError> {
+    for (section, file) in section_files(document) {
+        let file_path = base_dir(path).join(&file);
+        let content = fs::read_to_string(&file_path).map_err(|e| ConfigError::ParseError {
+            path: path.display().to_string(),
+            message: format!("cannot read {section} file '{}': {e}", file_path.display()),
+        })?;
+        let list = match parse_document(&content, &file_path)? {
+            Value::Null => Value::Sequence(Vec::new()),
+            list @ Value::Sequence(_) => list,
+            _ => {
+                return Err(ConfigError::ParseError {
+                    path: file_path.display().to_string(),
+                    message: format!("expected a list of {section}"),
+                })
+            }
+        };
+        if let Value::Mapping(mapping) = document {
+            mapping.insert(Value::from(section), list);
+        }
+    }
+    Ok(())
+}
+
+/// Read a config file and the section files it names, without `DRASI__`
+/// overrides or validation.
+pub fn read_config_file(path: &Path) -> Result<DrasiServerConfig, ConfigError> {
+    let content = fs::read_to_string(path)?;
+    let mut document = parse_document(&content, path)?;
+    include_section_files(&mut document, path)?;
+    Ok(serde_yaml::from_value(document)?)
+}
+
+/// The files to write to persist `config` to the main config file at
+/// `path`: one per section kept separately, then the main file.
+///
+/// Each file is patched so comments and unchanged entries survive, falling
+/// back to a full rewrite when it cannot be patched.
+pub fn render_config_files(
+    path: &Path,
+    config: &DrasiServerConfig,
+) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let mut files = serde_yaml::from_str::<Value>(&existing)
+        .map(|document| section_files(&document))
+        .unwrap_or_default();
+    if config.split_files {
+        for section in SECTIONS {
+            if !files.iter().any(|(s, _)| *s == section) {
+                files.push((section, format!("{section}.yaml")));
+            }
+        }
+    }
+
+    let mut rendered = Vec::new();
+    if !files.is_empty() {
+        let normalized = serde_yaml::to_value(config)?;
+        for (section, file) in &files {
+            let file_path = base_dir(path).join(file);
+            let original = fs::read_to_string(&file_path).unwrap_or_default();
+            let content = match patch_list_document(section, &original, config) {
+                Ok(patched) => patched,
+                Err(e) => {
+                    debug!("Rewriting {} in full: {e}", file_path.display());
+                    let list = normalized
+                        .get(section)
+                        .cloned()
+                        .unwrap_or(Value::Sequence(Vec::new()));
+                    serde_yaml::to_string(&list)?
+                }
+            };
+            rendered.push((file_path, content));
+        }
+    }
+
+    let main = match patch_split_document(&existing, config, &files) {
+        Ok(patched) => patched,
+        Err(e) => {
+            debug!("Rewriting config file in full: {e}");
+            let mut document = serde_yaml::to_value(config)?;
+            if let Value::Mapping(mapping) = &mut document {
+                for (section, file) in &files {
+                    mapping.insert(Value::from(*section), Value::from(file.as_str()));
+                }
+            }
+            serde_yaml::to_string(&document)?
+        }
+    };
+    rendered.push((path.to_path_buf(), main));
+    Ok(rendered)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MAIN: &str = r#"# Production server
+id: my-server
+sources: sources.yaml
+queries:
+  - id: q1
+    query: MATCH (n) RETURN n
+    sources:
+      - source_id: s1
+"#;
+
+    const SOURCES: &str = r#"# Test data
+- kind: mock
+  id: s1 # keep me
+"#;
+
+    fn write_files(dir: &TempDir) -> PathBuf {
+        let path = dir.path().join("server.yaml");
+        fs::write(&path, MAIN).unwrap();
+        fs::write(dir.path().join("sources.yaml"), SOURCES).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_loads_sections_from_named_files() {
+        let dir = TempDir::new().unwrap();
+        let path = write_files(&dir);
+
+        let config = crate::config::load_config_file(&path).unwrap();
+
+        assert_eq!(config.sources.len(), 1);
+        assert_eq!(config.sources[0].id(), "s1");
+        assert_eq!(config.queries[0].id, "q1");
+    }
+
+    #[test]
+    fn test_missing_section_file_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("server.yaml");
+        fs::write(&path, "sources: missing.yaml\n").unwrap();
+
+        let err = read_config_file(&path).unwrap_err().to_string();
+
+        assert!(err.contains("missing.yaml"), "{err}");
+    }
+
+    #[test]
+    fn test_named_files_are_written_back_in_place() {
+        let dir = TempDir::new().unwrap();
+        let path = write_files(&dir);
+        let mut config = read_config_file(&path).unwrap();
+        config
+            .sources
+            .push(serde_yaml::from_str("{kind: mock, id: s2}").unwrap());
+
+        let files = render_config_files(&path, &config).unwrap();
+
+        assert_eq!(files.len(), 2);
+        let (sources_path, sources) = &files[0];
+        assert_eq!(sources_path, &dir.path().join("sources.yaml"));
+        assert!(sources.starts_with("# Test data\n"));
+        assert!(sources.contains("id: s1 # keep me"));
+        assert!(sources.contains("id: s2"));
+        assert_eq!(files[1].1, MAIN);
+    }
+
+    #[test]
+    fn test_split_files_moves_every_section() {
+        let dir = TempDir::new().unwrap();
+        let path = write_files(&dir);
+        let mut config = read_config_file(&path).unwrap();
+        config.split_files = true;
+
+        let files = render_config_files(&path, &config).unwrap();
+        for (file_path, content) in &files {
+            fs::write(file_path, content).unwrap();
+        }
+
+        let main = &files.last().unwrap().1;
+        assert!(main.contains("queries: queries.yaml"));
+        assert!(main.contains("reactions: reactions.yaml"));
+        let reloaded = read_config_file(&path).unwrap();
+        assert_eq!(reloaded.sources[0].id(), "s1");
+        assert_eq!(reloaded.queries[0].id, "q1");
+        assert!(reloaded.split_files);
+    }
+}
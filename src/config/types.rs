@@ -15,6 +15,7 @@
 use anyhow::Result;
 use drasi_lib::config::QueryConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::net::IpAddr;
 use std::path::Path;
@@ -34,9 +35,9 @@ pub struct DrasiServerConfig {
     /// Unique identifier for this server instance (defaults to UUID)
     #[serde(default = "default_id")]
     pub id: ConfigValue<String>,
-    /// Server bind address
+    /// Server bind address, or a list of addresses (e.g. `[0.0.0.0, "::"]`)
     #[serde(default = "default_host")]
-    pub host: ConfigValue<String>,
+    pub host: ConfigValue<BindHosts>,
     /// Server port
     #[serde(default = "default_port")]
     pub port: ConfigValue<u16>,
@@ -46,9 +47,68 @@ pub struct DrasiServerConfig {
     /// Disable automatic persistence of API changes to config file
     #[serde(default = "default_disable_persistence")]
     pub disable_persistence: bool,
+    /// Coalesce API-driven saves made within this many milliseconds into one
+    /// write (default: every change is written immediately)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persistence_debounce_ms: Option<u64>,
     /// Enable persistent indexing using RocksDB (default: false uses in-memory indexes)
     #[serde(default = "default_persist_index")]
     pub persist_index: bool,
+    /// Directory RocksDB keeps indexes in when `persist_index` is enabled
+    /// (default: ./data/index)
+    /// Supports environment variables: ${DRASI_INDEX_PATH}
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_path: Option<ConfigValue<String>>,
+    /// Treat the config file as desired state and periodically converge the
+    /// running components to it, reverting changes made through the API
+    #[serde(default = "default_reconcile")]
+    pub reconcile: bool,
+    /// Seconds between reconcile passes (default: 30)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconcile_interval_secs: Option<u64>,
+    /// Reject the config file if it contains fields the server does not
+    /// recognize, instead of silently ignoring them
+    #[serde(default = "default_strict")]
+    pub strict: bool,
+    /// Reject queries created through the API whose joins have problems,
+    /// instead of only logging warnings
+    #[serde(default = "default_strict_joins")]
+    pub strict_joins: bool,
+    /// Start the server even if some sources or reactions cannot be built.
+    /// They are reported as failed through the API instead of aborting
+    /// startup, and the rest of the server runs without them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tolerate_component_errors: bool,
+    /// Keys accepted as `Authorization: Bearer <key>` on API requests that
+    /// change the server. Without keys the API is open.
+    /// Supports environment variables: ${DRASI_API_KEY}
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<ConfigValue<String>>,
+    /// Also accept JWTs issued by this OIDC provider as bearer tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwt: Option<JwtSettings>,
+    /// Serve the REST API over HTTPS instead of plain HTTP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsSettings>,
+    /// Check that running sources are still alive and report the ones that
+    /// are not as stale
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat: Option<HeartbeatSettings>,
+    /// Whether the Swagger UI and OpenAPI document are served, and to whom
+    #[serde(default, skip_serializing_if = "DocsSettings::is_default")]
+    pub docs: DocsSettings,
+    /// Title, description, contact and server URLs shown in the OpenAPI
+    /// document and Swagger UI, instead of the built-in defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub openapi: Option<OpenApiSettings>,
+    /// Persist sources, queries and reactions to `sources.yaml`,
+    /// `queries.yaml` and `reactions.yaml` next to this file, which then
+    /// names those files instead of listing the components
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub split_files: bool,
+    /// Tokio runtime sizing
+    #[serde(default, skip_serializing_if = "RuntimeSettings::is_default")]
+    pub runtime: RuntimeSettings,
     /// Default priority queue capacity for queries and reactions (default: 10000 if not specified)
     /// Supports environment variables: ${PRIORITY_QUEUE_CAPACITY:-10000}
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -66,22 +126,327 @@ pub struct DrasiServerConfig {
     /// Reaction configurations (parsed into plugin instances)
     #[serde(default)]
     pub reactions: Vec<ReactionConfig>,
+    /// Components that are kept in the config but must not run
+    #[serde(default, skip_serializing_if = "DisabledComponents::is_empty")]
+    pub disabled: DisabledComponents,
+}
+
+/// IDs of components that are disabled.
+///
+/// Disabled components keep their definition and are still created on startup,
+/// but are never started automatically, regardless of their `auto_start` setting.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DisabledComponents {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub queries: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<String>,
+}
+
+impl DisabledComponents {
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty() && self.queries.is_empty() && self.reactions.is_empty()
+    }
+}
+
+/// OIDC provider whose JWTs are accepted on API requests that change the
+/// server. Each value supports environment variables, e.g. ${OIDC_ISSUER}.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JwtSettings {
+    /// Required `iss` claim, e.g. `https://login.example.com/`
+    pub issuer: ConfigValue<String>,
+    /// Required `aud` claim
+    pub audience: ConfigValue<String>,
+    /// URL of the provider's JSON Web Key Set
+    pub jwks_url: ConfigValue<String>,
+}
+
+/// Certificate and key the REST API is served with over HTTPS.
+///
+/// All files are PEM; `cert_path` may hold the full chain. Supports
+/// environment variables: ${TLS_CERT_PATH}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TlsSettings {
+    pub cert_path: ConfigValue<String>,
+    pub key_path: ConfigValue<String>,
+    /// CA bundle to verify client certificates against (mutual TLS).
+    /// Without it clients are not asked for a certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ca_path: Option<ConfigValue<String>>,
+    /// Reject clients that present no certificate signed by `client_ca_path`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_client_cert: bool,
+}
+
+/// Access to the Swagger UI at `/docs` and the OpenAPI document at
+/// `/api-docs/openapi.json`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DocsSettings {
+    /// Serve them at all (default: true)
+    #[serde(default = "default_docs_enabled")]
+    pub enabled: bool,
+    /// Require an API key or JWT to read them, like requests that change
+    /// the server. Browsers are asked for it as a Basic auth password.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_auth: bool,
+}
+
+impl Default for DocsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_docs_enabled(),
+            require_auth: false,
+        }
+    }
+}
+
+impl DocsSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_docs_enabled() -> bool {
+    true
+}
+
+/// Deployment-specific metadata for the generated OpenAPI document. Unset
+/// fields keep the built-in defaults. Each value supports environment
+/// variables, e.g. ${DRASI_PUBLIC_URL}.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OpenApiSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<ConfigValue<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<ConfigValue<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<OpenApiContactSettings>,
+    /// URLs the API is reached at, listed in the Swagger UI's server picker
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<OpenApiServerSettings>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OpenApiContactSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<ConfigValue<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<ConfigValue<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<ConfigValue<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OpenApiServerSettings {
+    pub url: ConfigValue<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<ConfigValue<String>>,
+}
+
+/// How often running sources are checked for liveness, and how long one may
+/// go without a heartbeat before it is reported stale.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HeartbeatSettings {
+    /// Seconds between checks (default: 15)
+    #[serde(default = "default_heartbeat_interval_secs")]
+    pub interval_secs: u64,
+    /// Seconds without a heartbeat after which a running source is stale
+    /// (default: 60)
+    #[serde(default = "default_stale_after_secs")]
+    pub stale_after_secs: u64,
+}
+
+impl Default for HeartbeatSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_heartbeat_interval_secs(),
+            stale_after_secs: default_stale_after_secs(),
+        }
+    }
+}
+
+impl HeartbeatSettings {
+    fn validate(&self) -> Result<()> {
+        if self.interval_secs == 0 {
+            return Err(anyhow::anyhow!(
+                "Invalid heartbeat.interval_secs 0: must be greater than zero"
+            ));
+        }
+        if self.stale_after_secs < self.interval_secs {
+            return Err(anyhow::anyhow!(
+                "Invalid heartbeat.stale_after_secs {}: must be at least interval_secs ({})",
+                self.stale_after_secs,
+                self.interval_secs
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_heartbeat_interval_secs() -> u64 {
+    15
+}
+
+fn default_stale_after_secs() -> u64 {
+    60
+}
+
+/// Thread counts for the server's Tokio runtimes.
+///
+/// Read before the runtime starts, so these are plain numbers; environment
+/// variable references are not supported here.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuntimeSettings {
+    /// Worker threads for the main runtime, which runs sources, queries and
+    /// reactions (default: one per CPU core)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_threads: Option<usize>,
+    /// Serve the REST API from a separate runtime with this many worker
+    /// threads, so busy queries cannot delay API requests (default: the API
+    /// shares the main runtime)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_worker_threads: Option<usize>,
+}
+
+impl RuntimeSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (name, threads) in [
+            ("worker_threads", self.worker_threads),
+            ("api_worker_threads", self.api_worker_threads),
+        ] {
+            if threads == Some(0) {
+                return Err(anyhow::anyhow!(
+                    "Invalid runtime.{name} 0: at least one thread is required"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One or more addresses the REST API listens on.
+///
+/// Written as a single host (`0.0.0.0`), a list (`[0.0.0.0, "::"]`), or a
+/// comma-separated string, which is also how an environment variable supplies
+/// several hosts. A single host is written back as a plain string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindHosts(Vec<String>);
+
+impl BindHosts {
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    fn split(list: &str) -> Self {
+        Self(
+            list.split(',')
+                .map(str::trim)
+                .filter(|host| !host.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )
+    }
+}
+
+impl From<&str> for BindHosts {
+    fn from(host: &str) -> Self {
+        Self(vec![host.to_string()])
+    }
+}
+
+impl From<String> for BindHosts {
+    fn from(host: String) -> Self {
+        Self(vec![host])
+    }
+}
+
+impl From<Vec<String>> for BindHosts {
+    fn from(hosts: Vec<String>) -> Self {
+        Self(hosts)
+    }
+}
+
+impl FromStr for BindHosts {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self::split(s))
+    }
+}
+
+impl std::fmt::Display for BindHosts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(", "))
+    }
+}
+
+impl Serialize for BindHosts {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.0.as_slice() {
+            [host] => host.serialize(serializer),
+            hosts => hosts.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BindHosts {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Hosts {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        Ok(match Hosts::deserialize(deserializer)? {
+            Hosts::One(host) => Self::split(&host),
+            Hosts::Many(hosts) => Self(hosts),
+        })
+    }
 }
 
 impl Default for DrasiServerConfig {
     fn default() -> Self {
         Self {
             id: default_id(),
-            host: ConfigValue::Static("0.0.0.0".to_string()),
+            host: default_host(),
             port: ConfigValue::Static(8080),
             log_level: ConfigValue::Static("info".to_string()),
             disable_persistence: false,
+            persistence_debounce_ms: None,
             persist_index: false,
+            index_path: None,
+            reconcile: false,
+            strict: false,
+            strict_joins: false,
+            tolerate_component_errors: false,
+            api_keys: Vec::new(),
+            jwt: None,
+            tls: None,
+            heartbeat: None,
+            docs: DocsSettings::default(),
+            openapi: None,
+            split_files: false,
+            runtime: RuntimeSettings::default(),
+            reconcile_interval_secs: None,
             default_priority_queue_capacity: None,
             default_dispatch_buffer_capacity: None,
             sources: Vec::new(),
             reactions: Vec::new(),
             queries: Vec::new(),
+            disabled: DisabledComponents::default(),
         }
     }
 }
@@ -90,8 +455,8 @@ fn default_id() -> ConfigValue<String> {
     ConfigValue::Static(uuid::Uuid::new_v4().to_string())
 }
 
-fn default_host() -> ConfigValue<String> {
-    ConfigValue::Static("0.0.0.0".to_string())
+fn default_host() -> ConfigValue<BindHosts> {
+    ConfigValue::Static(BindHosts::from("0.0.0.0"))
 }
 
 fn default_port() -> ConfigValue<u16> {
@@ -110,6 +475,18 @@ fn default_persist_index() -> bool {
     false
 }
 
+fn default_reconcile() -> bool {
+    false
+}
+
+fn default_strict() -> bool {
+    false
+}
+
+fn default_strict_joins() -> bool {
+    false
+}
+
 /// Validate hostname format according to RFC 1123
 fn is_valid_hostname(hostname: &str) -> bool {
     if hostname.is_empty() || hostname.len() > 253 {
@@ -156,16 +533,28 @@ impl DrasiServerConfig {
         let mapper = DtoMapper::new();
         let resolved_settings = map_server_settings(self, &mapper)?;
 
-        if !resolved_settings.host.is_empty()
-            && resolved_settings.host != "0.0.0.0"
-            && !is_valid_hostname(&resolved_settings.host)
-            && IpAddr::from_str(&resolved_settings.host).is_err()
-        {
-            return Err(anyhow::anyhow!(
-                "Invalid host '{}': must be a valid hostname or IP address",
-                resolved_settings.host
-            ));
+        if resolved_settings.host.iter().next().is_none() {
+            return Err(anyhow::anyhow!("Invalid host: at least one address is required"));
         }
+        let mut seen_hosts = Vec::new();
+        for host in resolved_settings.host.iter() {
+            let address = unbracketed(host);
+            if !address.is_empty()
+                && address != "0.0.0.0"
+                && !is_valid_hostname(address)
+                && IpAddr::from_str(address).is_err()
+            {
+                return Err(anyhow::anyhow!(
+                    "Invalid host '{host}': must be a valid hostname or IP address"
+                ));
+            }
+            if seen_hosts.contains(&address) {
+                return Err(anyhow::anyhow!("Duplicate host '{host}'"));
+            }
+            seen_hosts.push(address);
+        }
+
+        self.runtime.validate()?;
 
         if resolved_settings.port == 0 {
             return Err(anyhow::anyhow!(
@@ -181,9 +570,125 @@ impl DrasiServerConfig {
             ));
         }
 
+        let listeners = self.listeners(&resolved_settings.host, resolved_settings.port, &mapper);
+        check_port_conflicts(&listeners)?;
+
+        for key in &self.api_keys {
+            if mapper.resolve_typed(key)?.trim().is_empty() {
+                return Err(anyhow::anyhow!("Invalid api_keys entry: keys must not be empty"));
+            }
+        }
+
+        if let Some(jwt) = &self.jwt {
+            for (name, value) in [
+                ("issuer", &jwt.issuer),
+                ("audience", &jwt.audience),
+                ("jwks_url", &jwt.jwks_url),
+            ] {
+                if mapper.resolve_typed(value)?.trim().is_empty() {
+                    return Err(anyhow::anyhow!("Invalid jwt.{name}: must not be empty"));
+                }
+            }
+        }
+
+        if let Some(tls) = &self.tls {
+            for (name, value) in [("cert_path", &tls.cert_path), ("key_path", &tls.key_path)] {
+                let path = mapper.resolve_typed(value)?;
+                if path.trim().is_empty() {
+                    return Err(anyhow::anyhow!("Invalid tls.{name}: must not be empty"));
+                }
+            }
+            if tls.require_client_cert && tls.client_ca_path.is_none() {
+                return Err(anyhow::anyhow!(
+                    "Invalid tls: require_client_cert needs client_ca_path to verify client certificates"
+                ));
+            }
+        }
+
+        if let Some(heartbeat) = &self.heartbeat {
+            heartbeat.validate()?;
+        }
+
+        if self.docs.require_auth && self.api_keys.is_empty() && self.jwt.is_none() {
+            return Err(anyhow::anyhow!(
+                "Invalid docs: require_auth needs api_keys or jwt to check credentials against"
+            ));
+        }
+
+        if self.reconcile_interval_secs == Some(0) {
+            return Err(anyhow::anyhow!(
+                "Invalid reconcile_interval_secs 0: must be greater than zero"
+            ));
+        }
+
+        for source in &self.sources {
+            if let Some(reconnect) = source.reconnect() {
+                reconnect.validate(source.id())?;
+            }
+        }
+
+        for query in &self.queries {
+            validate_query_capacities(query)?;
+            validate_query_language(query)?;
+            let filter_errors = validate_subscription_labels(query);
+            if !filter_errors.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Invalid source subscription filters in query '{}': {}",
+                    query.id,
+                    filter_errors.join("; ")
+                ));
+            }
+        }
+        validate_reaction_groups(&self.reactions)?;
+
         Ok(())
     }
 
+    /// Every host:port the server and its components will bind.
+    ///
+    /// Components whose host or port cannot be resolved yet (e.g. an unset
+    /// environment variable) are left out.
+    fn listeners(
+        &self,
+        api_hosts: &BindHosts,
+        api_port: u16,
+        mapper: &crate::api::mappings::DtoMapper,
+    ) -> Vec<Listener> {
+        let mut listeners: Vec<Listener> = api_hosts
+            .iter()
+            .map(|host| Listener {
+                owner: "the REST API".to_string(),
+                host: host.to_string(),
+                port: api_port,
+            })
+            .collect();
+
+        let mut add = |owner: String, host: &ConfigValue<String>, port: &ConfigValue<u16>| {
+            if let (Ok(host), Ok(port)) =
+                (mapper.resolve_string(host), mapper.resolve_typed(port))
+            {
+                listeners.push(Listener { owner, host, port });
+            }
+        };
+        for source in &self.sources {
+            match source {
+                SourceConfig::Http { id, config, .. } => {
+                    add(format!("source '{id}'"), &config.host, &config.port)
+                }
+                SourceConfig::Grpc { id, config, .. } => {
+                    add(format!("source '{id}'"), &config.host, &config.port)
+                }
+                _ => {}
+            }
+        }
+        for reaction in &self.reactions {
+            if let ReactionConfig::Sse { id, config, .. } = reaction {
+                add(format!("reaction '{id}'"), &config.host, &config.port);
+            }
+        }
+        listeners
+    }
+
     /// Save configuration to a YAML file
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let yaml = serde_yaml::to_string(self)?;
@@ -192,6 +697,190 @@ impl DrasiServerConfig {
     }
 }
 
+/// A host:port bound by the server or one of its components
+struct Listener {
+    owner: String,
+    host: String,
+    port: u16,
+}
+
+/// An IPv6 address without the brackets of its URL form, e.g. `::` for `[::]`
+pub(crate) fn unbracketed(host: &str) -> &str {
+    host.strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+fn is_wildcard_host(host: &str) -> bool {
+    matches!(host, "" | "*" | "0.0.0.0" | "::" | "[::]")
+}
+
+/// Reject configurations where two listeners would bind the same address,
+/// which otherwise only shows up as a bind error once the server starts.
+/// Port 0 picks a free port at bind time, so it never conflicts.
+fn check_port_conflicts(listeners: &[Listener]) -> Result<()> {
+    let mut conflicts = Vec::new();
+    for (i, a) in listeners.iter().enumerate().filter(|(_, a)| a.port != 0) {
+        // The API's own addresses, e.g. `0.0.0.0` and `::`, are bound together on purpose
+        for b in listeners[i + 1..].iter().filter(|b| b.owner != a.owner) {
+            let hosts_overlap =
+                a.host == b.host || is_wildcard_host(&a.host) || is_wildcard_host(&b.host);
+            if a.port == b.port && hosts_overlap {
+                conflicts.push(format!(
+                    "{} ({}:{}) and {} ({}:{})",
+                    a.owner, a.host, a.port, b.owner, b.host, b.port
+                ));
+            }
+        }
+    }
+    if conflicts.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Port conflict: {} bind the same port",
+            conflicts.join("; ")
+        ))
+    }
+}
+
+/// Validate the per-query capacity overrides.
+///
+/// A capacity of zero would leave the query unable to accept events, so it is
+/// rejected here rather than failing later inside DrasiLib.
+pub fn validate_query_capacities(query: &QueryConfig) -> Result<()> {
+    if query.priority_queue_capacity == Some(0) {
+        return Err(anyhow::anyhow!(
+            "Query '{}' has invalid priority_queue_capacity 0: must be greater than zero",
+            query.id
+        ));
+    }
+    if query.dispatch_buffer_capacity == Some(0) {
+        return Err(anyhow::anyhow!(
+            "Query '{}' has invalid dispatch_buffer_capacity 0: must be greater than zero",
+            query.id
+        ));
+    }
+    Ok(())
+}
+
+/// Check that the members of each reaction failover group can stand in for
+/// one another: the same kind subscribed to the same queries.
+///
+/// Group members are started by the group supervisor, so they cannot also be
+/// gated on `wait_for_queries_ms`.
+pub fn validate_reaction_groups(reactions: &[ReactionConfig]) -> Result<()> {
+    let mut first_members: HashMap<&str, &ReactionConfig> = HashMap::new();
+    for reaction in reactions {
+        let Some(group) = reaction.group() else {
+            continue;
+        };
+        if reaction.wait_for_queries_ms().is_some() {
+            return Err(anyhow::anyhow!(
+                "Reaction '{}' is in group '{group}' and cannot also set wait_for_queries_ms",
+                reaction.id()
+            ));
+        }
+        let first = *first_members.entry(group).or_insert(reaction);
+        if first.kind() != reaction.kind() {
+            return Err(anyhow::anyhow!(
+                "Reaction '{}' is a {} reaction but group '{group}' holds {} reactions",
+                reaction.id(),
+                reaction.kind(),
+                first.kind()
+            ));
+        }
+        let mut queries = reaction.queries().to_vec();
+        let mut first_queries = first.queries().to_vec();
+        queries.sort();
+        first_queries.sort();
+        if queries != first_queries {
+            return Err(anyhow::anyhow!(
+                "Reaction '{}' subscribes to {queries:?} but group '{group}' subscribes to {first_queries:?}",
+                reaction.id()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check a query's text against its `query_language`.
+///
+/// Only a query that does not parse in its declared language but does in the
+/// other one is rejected, since that almost always means `query_language` is
+/// missing or wrong. Other parse errors are left to DrasiLib.
+pub fn validate_query_language(query: &QueryConfig) -> Result<()> {
+    use crate::api::models::QueryLanguageDto;
+    use drasi_lib::queries::LabelExtractor;
+
+    if LabelExtractor::extract_labels(&query.query, &query.query_language).is_ok() {
+        return Ok(());
+    }
+    let declared: QueryLanguageDto =
+        serde_json::from_value(serde_json::to_value(&query.query_language)?)?;
+    for other in QueryLanguageDto::ALL.into_iter().filter(|l| *l != declared) {
+        if LabelExtractor::extract_labels(&query.query, &other.into()).is_ok() {
+            return Err(anyhow::anyhow!(
+                "Query '{}' is not valid {declared} but parses as {other}; set query_language: {other}",
+                query.id
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that every label used in a source subscription's `nodes`/`relations`
+/// filter is referenced by the query pattern.
+///
+/// A filter on a label the query never matches would silently drop every change
+/// for it, so these are reported as errors. If the query cannot be parsed the
+/// check is skipped and the parse error is left to DrasiLib.
+pub fn validate_subscription_labels(config: &QueryConfig) -> Vec<String> {
+    use drasi_lib::queries::LabelExtractor;
+
+    let has_filters = config
+        .sources
+        .iter()
+        .any(|s| !s.nodes.is_empty() || !s.relations.is_empty());
+    if !has_filters {
+        return Vec::new();
+    }
+
+    let labels = match LabelExtractor::extract_labels(&config.query, &config.query_language) {
+        Ok(labels) => labels,
+        Err(e) => {
+            log::warn!(
+                "Failed to parse query '{}' for subscription filter validation: {e}",
+                config.id
+            );
+            return Vec::new();
+        }
+    };
+    let node_labels: std::collections::HashSet<String> = labels.node_labels.into_iter().collect();
+    let rel_labels: std::collections::HashSet<String> =
+        labels.relation_labels.into_iter().collect();
+
+    let mut errors = Vec::new();
+    for subscription in &config.sources {
+        for label in &subscription.nodes {
+            if !node_labels.contains(label) {
+                errors.push(format!(
+                    "Source '{}' filters on node label '{label}' which does not appear in the query",
+                    subscription.source_id
+                ));
+            }
+        }
+        for label in &subscription.relations {
+            if !rel_labels.contains(label) {
+                errors.push(format!(
+                    "Source '{}' filters on relation label '{label}' which does not appear in the query",
+                    subscription.source_id
+                ));
+            }
+        }
+    }
+    errors
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,6 +943,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_api_keys_resolve_environment_variables() {
+        std::env::set_var("DRASI_TYPES_TEST_API_KEY", "from-env");
+        let yaml = r#"
+            api_keys:
+              - static-key
+              - ${DRASI_TYPES_TEST_API_KEY}
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let mapper = crate::api::mappings::DtoMapper::new();
+        let keys: Vec<String> = config
+            .api_keys
+            .iter()
+            .map(|key| mapper.resolve_typed(key).unwrap())
+            .collect();
+
+        assert_eq!(keys, ["static-key", "from-env"]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_jwt_settings_resolve_environment_variables() {
+        std::env::set_var("DRASI_TYPES_TEST_OIDC_ISSUER", "https://login.example.com/");
+        let yaml = r#"
+            jwt:
+              issuer: ${DRASI_TYPES_TEST_OIDC_ISSUER}
+              audience: drasi-server
+              jwks_url: https://login.example.com/keys
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let mapper = crate::api::mappings::DtoMapper::new();
+        let settings = crate::api::mappings::map_server_settings(&config, &mapper).unwrap();
+
+        let jwt = settings.jwt.unwrap();
+        assert_eq!(jwt.issuer, "https://login.example.com/");
+        assert_eq!(jwt.audience, "drasi-server");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_require_client_cert_needs_client_ca() {
+        let yaml = r#"
+            tls:
+              cert_path: server.crt
+              key_path: server.key
+              require_client_cert: true
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+
+        assert!(err.contains("client_ca_path"), "{err}");
+    }
+
+    #[test]
+    fn test_heartbeat_stale_after_must_cover_an_interval() {
+        let yaml = r#"
+            heartbeat:
+              interval_secs: 30
+              stale_after_secs: 10
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+
+        assert!(err.contains("heartbeat.stale_after_secs"), "{err}");
+    }
+
+    #[test]
+    fn test_empty_api_key_is_rejected() {
+        let config: DrasiServerConfig = serde_yaml::from_str("api_keys: ['']").unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+
+        assert!(err.contains("api_keys"), "{err}");
+    }
+
     #[test]
     fn test_persist_index_serialization_roundtrip_true() {
         let config = DrasiServerConfig {
@@ -370,4 +1138,247 @@ mod tests {
             "Saved file should contain persist_index setting"
         );
     }
+
+    #[test]
+    fn test_disabled_components_roundtrip() {
+        let config = DrasiServerConfig::default();
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        assert!(!yaml.contains("disabled"), "Empty disabled set is omitted");
+
+        let yaml = r#"
+            disabled:
+              queries: [q1]
+        "#;
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.disabled.queries, vec!["q1"]);
+        assert!(config.disabled.sources.is_empty());
+
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        let reloaded: DrasiServerConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(reloaded.disabled, config.disabled);
+    }
+
+    // ==================== query capacity tests ====================
+
+    #[test]
+    fn test_config_validation_accepts_query_capacity_overrides() {
+        let yaml = r#"
+            queries:
+              - id: heavy-query
+                query: "MATCH (n) RETURN n"
+                priority_queue_capacity: 50000
+                dispatch_buffer_capacity: 2000
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_validation_rejects_zero_query_capacity() {
+        let yaml = r#"
+            queries:
+              - id: broken-query
+                query: "MATCH (n) RETURN n"
+                priority_queue_capacity: 0
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("broken-query"));
+        assert!(err.contains("priority_queue_capacity"));
+    }
+
+    #[test]
+    fn test_config_validation_rejects_zero_dispatch_buffer_capacity() {
+        let yaml = r#"
+            queries:
+              - id: broken-query
+                query: "MATCH (n) RETURN n"
+                dispatch_buffer_capacity: 0
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("dispatch_buffer_capacity"));
+    }
+
+    #[test]
+    fn test_config_validation_rejects_unknown_subscription_label() {
+        let yaml = r#"
+            queries:
+              - id: orders
+                query: "MATCH (o:Order) RETURN o.id"
+                sources:
+                  - source_id: db
+                    nodes: [Customer]
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("orders"));
+        assert!(err.contains("node label 'Customer'"));
+    }
+
+    // ==================== port conflict tests ====================
+
+    #[test]
+    fn test_config_validation_rejects_port_conflicts() {
+        let yaml = r#"
+            port: 8080
+            sources:
+              - kind: http
+                id: webhooks
+                host: 0.0.0.0
+                port: 9000
+            reactions:
+              - kind: sse
+                id: stream
+                queries: [q]
+                host: 127.0.0.1
+                port: 9000
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("source 'webhooks'"));
+        assert!(err.contains("reaction 'stream'"));
+    }
+
+    #[test]
+    fn test_config_validation_rejects_mismatched_reaction_group() {
+        let yaml = r#"
+            reactions:
+              - kind: log
+                id: primary
+                queries: [q1]
+                group: audit
+              - kind: log
+                id: backup
+                queries: [q1, q2]
+                group: audit
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("'backup'"), "{err}");
+        assert!(err.contains("group 'audit'"), "{err}");
+    }
+
+    #[test]
+    fn test_config_validation_allows_same_port_on_different_hosts() {
+        let yaml = r#"
+            host: 127.0.0.1
+            port: 8080
+            sources:
+              - kind: http
+                id: webhooks
+                host: 127.0.0.2
+                port: 8080
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_validation_allows_several_ephemeral_ports() {
+        let yaml = r#"
+            port: 8080
+            sources:
+              - kind: http
+                id: webhooks
+                host: 0.0.0.0
+                port: 0
+            reactions:
+              - kind: sse
+                id: stream
+                queries: [q]
+                host: 0.0.0.0
+                port: 0
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_host_list_round_trip() {
+        let yaml = r#"
+            host: [0.0.0.0, "::"]
+            port: 8080
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.host,
+            ConfigValue::Static(BindHosts::from(vec![
+                "0.0.0.0".to_string(),
+                "::".to_string()
+            ]))
+        );
+
+        let reloaded: DrasiServerConfig =
+            serde_yaml::from_str(&serde_yaml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(reloaded.host, config.host);
+    }
+
+    #[test]
+    fn test_host_list_from_comma_separated_string() {
+        let hosts: BindHosts = "127.0.0.1, ::1".parse().unwrap();
+        assert_eq!(hosts.iter().collect::<Vec<_>>(), vec!["127.0.0.1", "::1"]);
+
+        let single: BindHosts = serde_yaml::from_str("localhost").unwrap();
+        assert_eq!(serde_yaml::to_string(&single).unwrap(), "localhost\n");
+    }
+
+    #[test]
+    fn test_config_validation_rejects_duplicate_hosts() {
+        let yaml = r#"
+            host: ["::", "[::]"]
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_runtime_settings() {
+        let yaml = r#"
+            runtime:
+              worker_threads: 8
+              api_worker_threads: 2
+        "#;
+
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.runtime.worker_threads, Some(8));
+        assert_eq!(config.runtime.api_worker_threads, Some(2));
+        assert!(config.validate().is_ok());
+
+        let config: DrasiServerConfig =
+            serde_yaml::from_str("runtime: {api_worker_threads: 0}").unwrap();
+        assert!(config.validate().is_err());
+
+        let yaml = serde_yaml::to_string(&DrasiServerConfig::default()).unwrap();
+        assert!(!yaml.contains("runtime"));
+    }
+
+    #[test]
+    fn test_docs_require_auth_needs_credentials() {
+        let config: DrasiServerConfig = serde_yaml::from_str("docs: {require_auth: true}").unwrap();
+        assert!(config.docs.enabled);
+        assert!(config.validate().is_err());
+
+        let yaml = r#"
+            api_keys: [secret-key]
+            docs:
+              require_auth: true
+        "#;
+        let config: DrasiServerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let yaml = serde_yaml::to_string(&DrasiServerConfig::default()).unwrap();
+        assert!(!yaml.contains("docs"));
+    }
 }
@@ -0,0 +1,377 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Format-preserving updates of a YAML config file.
+//!
+//! `serde_yaml` drops comments and formatting, so re-serializing the whole
+//! config on every API change would wipe out a hand-maintained file. Instead
+//! the existing text is split into top-level sections, and list sections
+//! (`sources`, `queries`, `reactions`) further into entries keyed by `id`.
+//! Only the sections and entries whose value changed are re-serialized;
+//! everything else, including comments between them, is kept as written.
+//!
+//! Comments inside a changed section or entry are lost. The result is parsed
+//! back and compared with the config, so a file this approach cannot handle
+//! is reported as an error and the caller falls back to a full rewrite.
+
+use anyhow::{anyhow, Result};
+use serde_yaml::{Mapping, Value};
+
+use super::split_files::SECTIONS;
+use super::DrasiServerConfig;
+
+/// A run of lines from the original file
+struct Block {
+    /// Top-level key the block defines, or `None` for comments and markers
+    key: Option<String>,
+    text: String,
+}
+
+/// Serialize a config to a mapping, so that two configs can be compared
+/// regardless of defaults and formatting in the file they came from.
+fn normalized(config: &DrasiServerConfig) -> Result<Mapping> {
+    match serde_yaml::to_value(config)? {
+        Value::Mapping(mapping) => Ok(mapping),
+        _ => Err(anyhow!("config did not serialize to a mapping")),
+    }
+}
+
+fn top_level_key(line: &str) -> Option<String> {
+    if line.starts_with(|c: char| c.is_whitespace() || c == '#' || c == '-') {
+        return None;
+    }
+    let (key, _) = line.split_once(':')?;
+    Some(key.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+}
+
+fn split_top_level(text: &str) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    for line in text.split_inclusive('\n') {
+        let starts_trivia = line.starts_with('#') || line.starts_with("---");
+        let key = if starts_trivia {
+            None
+        } else {
+            top_level_key(line)
+        };
+        match blocks.last_mut() {
+            Some(block) if key.is_none() && !starts_trivia => block.text.push_str(line),
+            _ => blocks.push(Block {
+                key,
+                text: line.to_string(),
+            }),
+        }
+    }
+    blocks
+}
+
+/// Split a block into its content, ending in one newline, and the blank
+/// lines that follow it.
+fn split_trailing_blank(text: &str) -> (String, String) {
+    let content = text.trim_end();
+    let newlines = text[content.len()..].matches('\n').count();
+    (
+        format!("{content}\n"),
+        "\n".repeat(newlines.saturating_sub(1)),
+    )
+}
+
+fn render_entry(key: &str, value: &Value) -> Result<String> {
+    let mut mapping = Mapping::new();
+    mapping.insert(Value::String(key.to_string()), value.clone());
+    Ok(serde_yaml::to_string(&mapping)?)
+}
+
+fn render_item(item: &Value, indent: usize) -> Result<String> {
+    let yaml = serde_yaml::to_string(&vec![item])?;
+    let pad = " ".repeat(indent);
+    Ok(yaml
+        .lines()
+        .map(|line| format!("{pad}{line}\n"))
+        .collect())
+}
+
+fn item_id(item: &Value) -> Option<&str> {
+    item.get("id").and_then(Value::as_str)
+}
+
+/// Rewrite a list section entry by entry. Returns `None` if the text does
+/// not line up with the parsed entries, e.g. for a flow sequence.
+fn patch_list(text: &str, old_items: &[Value], new_items: &[Value]) -> Result<Option<String>> {
+    let (body, trailing) = split_trailing_blank(text);
+    let lines: Vec<&str> = body.split_inclusive('\n').collect();
+    let Some((header, rest)) = lines.split_first() else {
+        return Ok(None);
+    };
+    let inline = header.split_once(':').map_or("", |(_, value)| value.trim());
+    if !inline.is_empty() && !inline.starts_with('#') {
+        return Ok(None);
+    }
+
+    let is_item_start = |line: &str| {
+        let trimmed = line.trim_start();
+        trimmed.starts_with("- ") || trimmed.trim_end() == "-"
+    };
+    let Some(indent) = rest
+        .iter()
+        .find(|line| is_item_start(line))
+        .map(|line| line.len() - line.trim_start().len())
+    else {
+        return Ok(None);
+    };
+
+    let mut preamble = String::new();
+    let mut items: Vec<String> = Vec::new();
+    for line in rest {
+        let line_indent = line.len() - line.trim_start().len();
+        if line_indent == indent && is_item_start(line) {
+            items.push(line.to_string());
+        } else if let Some(item) = items.last_mut() {
+            item.push_str(line);
+        } else {
+            preamble.push_str(line);
+        }
+    }
+    if items.len() != old_items.len() {
+        return Ok(None);
+    }
+
+    let mut out = format!("{header}{preamble}");
+    for new_item in new_items {
+        let unchanged = item_id(new_item).and_then(|id| {
+            old_items
+                .iter()
+                .position(|old| item_id(old) == Some(id) && old == new_item)
+        });
+        match unchanged {
+            Some(index) => out.push_str(&items[index]),
+            None => out.push_str(&render_item(new_item, indent)?),
+        }
+    }
+    out.push_str(&trailing);
+    Ok(Some(out))
+}
+
+fn patch_section(key: &str, text: &str, old: Option<&Value>, new: &Value) -> Result<String> {
+    if let (Some(Value::Sequence(old_items)), Value::Sequence(new_items)) = (old, new) {
+        if let Some(patched) = patch_list(text, old_items, new_items)? {
+            return Ok(patched);
+        }
+    }
+    let (_, trailing) = split_trailing_blank(text);
+    Ok(render_entry(key, new)? + &trailing)
+}
+
+/// Serialize a config to a mapping like [`normalized`], with `files` sections
+/// holding the name of the file they are kept in.
+fn normalized_with_files(config: &DrasiServerConfig, files: &[(&str, String)]) -> Result<Mapping> {
+    let mut mapping = normalized(config)?;
+    for (section, file) in files {
+        mapping.insert(Value::from(*section), Value::from(file.as_str()));
+    }
+    Ok(mapping)
+}
+
+/// Parse and normalize a main config file. Sections that name a file keep
+/// the file name; their entries are not read.
+fn parse_normalized(text: &str) -> Result<Mapping> {
+    let mut document: Value = serde_yaml::from_str(text)?;
+    let mut files = Vec::new();
+    if let Value::Mapping(mapping) = &mut document {
+        for section in SECTIONS {
+            if let Some(Value::String(file)) = mapping.get(section) {
+                files.push((section, file.clone()));
+                mapping.insert(Value::from(section), Value::Sequence(Vec::new()));
+            }
+        }
+    }
+    normalized_with_files(&serde_yaml::from_value(document)?, &files)
+}
+
+/// Render `updated` as YAML, reusing the text of `original` for every
+/// top-level setting and list entry that did not change.
+///
+/// # Errors
+///
+/// Returns an error if `original` is not a YAML config file, or if the
+/// patched text would not load back as `updated`; callers should then write
+/// the config in full.
+pub fn patch_document(original: &str, updated: &DrasiServerConfig) -> Result<String> {
+    patch_split_document(original, updated, &[])
+}
+
+/// Like [`patch_document`], for a main file whose `files` sections are kept
+/// in separate files and hold just the file name.
+pub fn patch_split_document(
+    original: &str,
+    updated: &DrasiServerConfig,
+    files: &[(&str, String)],
+) -> Result<String> {
+    if original.trim().is_empty() || original.trim_start().starts_with('{') {
+        return Err(anyhow!("no YAML formatting to preserve"));
+    }
+    let before = parse_normalized(original)?;
+    let after = normalized_with_files(updated, files)?;
+
+    let mut out = String::new();
+    let mut seen = Vec::new();
+    for block in split_top_level(original) {
+        let Some(key) = block.key else {
+            out.push_str(&block.text);
+            continue;
+        };
+        let name = Value::String(key.clone());
+        let old = before.get(&name);
+        match after.get(&name) {
+            // Settings that are no longer written, e.g. an option set back to its default
+            None => {}
+            Some(new) if old == Some(new) => out.push_str(&block.text),
+            Some(new) => out.push_str(&patch_section(&key, &block.text, old, new)?),
+        }
+        seen.push(name);
+    }
+
+    for (name, value) in &after {
+        // Settings missing from the file that are still at their default
+        // stay missing
+        if seen.contains(name) || before.get(name) == Some(value) {
+            continue;
+        }
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        let key = name
+            .as_str()
+            .ok_or_else(|| anyhow!("non-string config key"))?;
+        out.push_str(&render_entry(key, value)?);
+    }
+
+    if parse_normalized(&out)? != after {
+        return Err(anyhow!("patched config file does not match the configuration"));
+    }
+    Ok(out)
+}
+
+/// Render the `section` list of `updated` as a file holding just that list,
+/// reusing the text of `original` for every entry that did not change.
+///
+/// # Errors
+///
+/// Returns an error if `original` is not such a list file, or if the patched
+/// text would not load back as the list; callers should then write the list
+/// in full.
+pub fn patch_list_document(
+    section: &str,
+    original: &str,
+    updated: &DrasiServerConfig,
+) -> Result<String> {
+    // Entries are normalized through the config, like the main file's lists
+    let list_of = |text: &str| -> Result<Value> {
+        let mut document = Mapping::new();
+        document.insert(Value::from(section), serde_yaml::from_str(text)?);
+        let config: DrasiServerConfig = serde_yaml::from_value(Value::Mapping(document))?;
+        normalized(&config)?
+            .remove(section)
+            .ok_or_else(|| anyhow!("config has no {section} section"))
+    };
+    let new = normalized(updated)?
+        .remove(section)
+        .ok_or_else(|| anyhow!("config has no {section} section"))?;
+    let (Value::Sequence(old_items), Value::Sequence(new_items)) = (list_of(original)?, &new)
+    else {
+        return Err(anyhow!("no YAML list to preserve"));
+    };
+
+    // patch_list expects the list under a key, as in the main file
+    let header = format!("{section}:\n");
+    let patched = patch_list(&format!("{header}{original}"), &old_items, new_items)?
+        .ok_or_else(|| anyhow!("list entries do not line up with the file"))?;
+    let out = patched
+        .strip_prefix(&header)
+        .ok_or_else(|| anyhow!("patched list lost its header"))?
+        .to_string();
+
+    if list_of(&out)? != new {
+        return Err(anyhow!("patched {section} file does not match the configuration"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = r#"# Production server
+id: my-server
+host: 0.0.0.0
+port: 8080 # behind the load balancer
+
+# Data sources
+sources:
+  # Test data
+  - kind: mock
+    id: s1 # keep me
+  - kind: mock
+    id: s2
+
+queries:
+  - id: q1
+    query: MATCH (n) RETURN n # all nodes
+    sources:
+      - source_id: s1
+"#;
+
+    fn load(text: &str) -> DrasiServerConfig {
+        serde_yaml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_unchanged_config_is_kept_verbatim() {
+        let config = load(ORIGINAL);
+        assert_eq!(patch_document(ORIGINAL, &config).unwrap(), ORIGINAL);
+    }
+
+    #[test]
+    fn test_only_changed_entries_are_rewritten() {
+        let mut config = load(ORIGINAL);
+        config.sources.retain(|s| s.id() != "s2");
+        config.port = crate::api::models::ConfigValue::Static(9090);
+
+        let patched = patch_document(ORIGINAL, &config).unwrap();
+
+        assert!(patched.starts_with("# Production server\n"));
+        assert!(patched.contains("# Data sources\nsources:\n  # Test data\n"));
+        assert!(patched.contains("id: s1 # keep me"));
+        assert!(patched.contains("MATCH (n) RETURN n # all nodes"));
+        assert!(!patched.contains("s2"));
+        assert!(patched.contains("port: 9090"));
+    }
+
+    #[test]
+    fn test_new_entries_are_appended() {
+        let mut config = load(ORIGINAL);
+        config.sources.push(load("sources: [{kind: mock, id: s3}]").sources.remove(0));
+
+        let patched = patch_document(ORIGINAL, &config).unwrap();
+
+        assert!(patched.contains("id: s1 # keep me"));
+        assert_eq!(load(&patched).sources[2].id(), "s3");
+    }
+
+    #[test]
+    fn test_json_is_not_patched() {
+        let config = DrasiServerConfig::default();
+        assert!(patch_document(r#"{"port": 8080}"#, &config).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Data directory checks for `drasi-server doctor`.
+//!
+//! Like the dependency checks, these use standard tools (`df`, `ulimit`) and
+//! `/proc/mounts` rather than platform APIs; a check whose tool is missing is
+//! skipped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use drasi_server::load_config_file;
+use drasi_server::server::INDEX_PATH;
+
+/// RocksDB keeps many files open; below this limit it can fail under load.
+const MIN_OPEN_FILES: u64 = 4096;
+
+/// Keep at least this much free space next to the index, since compaction
+/// temporarily needs room for rewritten files.
+const MIN_FREE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Filesystems on which RocksDB's locking and fsync guarantees are unreliable.
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smb3", "smbfs", "9p", "afs", "ceph", "glusterfs", "fuse.sshfs",
+];
+
+/// Check the index directory. Returns false if a required check failed.
+pub fn check_data_directory(config_path: &Path) -> bool {
+    // Without a readable config file, assume indexes will be persisted to
+    // the default directory so every check runs
+    let config = load_config_file(config_path).ok();
+    let index_path = config
+        .as_ref()
+        .and_then(|config| drasi_server::server::index_path(config).ok())
+        .unwrap_or_else(|| PathBuf::from(INDEX_PATH));
+    let index_path = index_path.as_path();
+    println!("Data directory ({}):", index_path.display());
+
+    if config.is_some_and(|config| !config.persist_index) {
+        println!("  [SKIP] persist_index is disabled; indexes are kept in memory");
+        return true;
+    }
+
+    let existing = nearest_existing(index_path);
+    let mut ok = true;
+
+    match probe_writable(&existing) {
+        Ok(()) => println!("  [OK] {} is writable", existing.display()),
+        Err(e) => {
+            println!("  [FAIL] {} is not writable: {e}", existing.display());
+            ok = false;
+        }
+    }
+
+    let index_size = directory_size(index_path);
+    match free_bytes(&existing) {
+        Some(free) if free < MIN_FREE_BYTES.max(index_size) => println!(
+            "  [WARN] {} free, index uses {}; compaction may run out of space",
+            human_bytes(free),
+            human_bytes(index_size)
+        ),
+        Some(free) => println!(
+            "  [OK] {} free, index uses {}",
+            human_bytes(free),
+            human_bytes(index_size)
+        ),
+        None => println!("  [SKIP] Free disk space (df not available)"),
+    }
+
+    match open_files_limit() {
+        Some(limit) if limit < MIN_OPEN_FILES => println!(
+            "  [WARN] Open file limit is {limit}; raise it to at least {MIN_OPEN_FILES} (ulimit -n)"
+        ),
+        Some(limit) => println!("  [OK] Open file limit is {limit}"),
+        None => println!("  [SKIP] Open file limit (ulimit not available)"),
+    }
+
+    let mounts = fs::read_to_string("/proc/mounts").unwrap_or_default();
+    let absolute = fs::canonicalize(&existing).unwrap_or(existing);
+    match filesystem_type(&mounts, &absolute) {
+        Some(fs_type) if NETWORK_FILESYSTEMS.contains(&fs_type.as_str()) => println!(
+            "  [WARN] Index is on a {fs_type} filesystem; RocksDB needs local storage for reliable locking and fsync"
+        ),
+        Some(fs_type) => println!("  [OK] Index filesystem is {fs_type}"),
+        None => {}
+    }
+
+    ok
+}
+
+/// The path itself if it exists, otherwise its closest existing ancestor,
+/// which is where the server would create it.
+fn nearest_existing(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|p| !p.as_os_str().is_empty() && p.exists())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!(".drasi-doctor-{}", std::process::id()));
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
+
+fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => directory_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn free_bytes(path: &Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_df_available(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Available bytes from POSIX `df -Pk` output: a header line, then
+/// `filesystem blocks used available capacity mount`.
+fn parse_df_available(output: &str) -> Option<u64> {
+    let line = output.lines().nth(1)?;
+    let available_kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb * 1024)
+}
+
+fn open_files_limit() -> Option<u64> {
+    let output = Command::new("sh").args(["-c", "ulimit -n"]).output().ok()?;
+    let limit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if limit == "unlimited" {
+        return Some(u64::MAX);
+    }
+    limit.parse().ok()
+}
+
+/// Type of the filesystem holding `path`, from the longest matching mount
+/// point in `/proc/mounts` content.
+fn filesystem_type(mounts: &str, path: &Path) -> Option<String> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = fields.next()?.replace("\\040", " ");
+            let fs_type = fields.next()?;
+            Some((mount_point, fs_type.to_string()))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.len())
+        .map(|(_, fs_type)| fs_type)
+}
+
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_df_available() {
+        let output = "Filesystem     1024-blocks     Used Available Capacity Mounted on\n\
+                      /dev/sda1         41152736 20000000  19039260      52% /\n";
+        assert_eq!(parse_df_available(output), Some(19039260 * 1024));
+        assert_eq!(parse_df_available("Filesystem\n"), None);
+    }
+
+    #[test]
+    fn test_filesystem_type_uses_longest_mount_point() {
+        let mounts = "/dev/sda1 / ext4 rw 0 0\n\
+                      server:/export /srv/data nfs4 rw 0 0\n";
+        assert_eq!(
+            filesystem_type(mounts, Path::new("/srv/data/index")).as_deref(),
+            Some("nfs4")
+        );
+        assert_eq!(
+            filesystem_type(mounts, Path::new("/srv/database")).as_deref(),
+            Some("ext4")
+        );
+    }
+
+    #[test]
+    fn test_human_bytes() {
+        assert_eq!(human_bytes(512), "512 B");
+        assert_eq!(human_bytes(3 * 1024 * 1024 * 1024 / 2), "1.5 GiB");
+    }
+}
@@ -17,10 +17,11 @@
 //! This module provides factory functions that match on the tagged enum config
 //! types and use the existing plugin constructors to create instances.
 
-use anyhow::Result;
 use drasi_lib::bootstrap::BootstrapProviderConfig;
+use drasi_lib::channels::DispatchMode;
 use drasi_lib::plugin_core::{Reaction, Source};
 use log::info;
+use thiserror::Error;
 
 use crate::api::mappings::{
     ConfigMapper,
@@ -33,6 +34,7 @@ use crate::api::mappings::{
     HttpReactionConfigMapper,
     HttpSourceConfigMapper,
     LogReactionConfigMapper,
+    MappingError,
     MockSourceConfigMapper,
     PlatformReactionConfigMapper,
     PlatformSourceConfigMapper,
@@ -43,6 +45,42 @@ use crate::api::mappings::{
 };
 use crate::config::{ReactionConfig, SourceConfig};
 
+/// Why a source or reaction could not be created from its configuration
+#[derive(Debug, Error)]
+pub enum FactoryError {
+    /// A config value could not be resolved, e.g. an unset environment
+    /// variable or a missing secret
+    #[error(transparent)]
+    Mapping(#[from] MappingError),
+
+    /// The configuration is not valid for the component
+    #[error("{0}")]
+    Validation(String),
+
+    /// Something the component needs, such as a database or a bootstrap
+    /// file, could not be reached
+    #[error("{0}")]
+    Connection(String),
+}
+
+type Result<T> = std::result::Result<T, FactoryError>;
+
+/// Classify an error from a plugin constructor: I/O failures anywhere in its
+/// chain mean a resource could not be reached, anything else is a problem
+/// with the configuration.
+fn build_error(err: impl Into<anyhow::Error>) -> FactoryError {
+    let err = err.into();
+    let message = format!("{err:#}");
+    if err
+        .chain()
+        .any(|cause| cause.downcast_ref::<std::io::Error>().is_some())
+    {
+        FactoryError::Connection(message)
+    } else {
+        FactoryError::Validation(message)
+    }
+}
+
 /// Create a source instance from a SourceConfig.
 ///
 /// This function matches on the config variant and creates the appropriate
@@ -67,12 +105,24 @@ use crate::config::{ReactionConfig, SourceConfig};
 ///     id: "test-source".to_string(),
 ///     auto_start: true,
 ///     bootstrap_provider: None,
+///     dispatch_mode: None,
+///     dispatch_buffer_capacity: None,
+///     lazy: false,
 ///     config: MockSourceConfig::default(),
 /// };
 ///
 /// let source = create_source(config).await?;
 /// ```
 pub async fn create_source(config: SourceConfig) -> Result<Box<dyn Source + 'static>> {
+    if config.dispatch_buffer_capacity() == Some(0) {
+        return Err(FactoryError::Validation(format!(
+            "Source '{}' has invalid dispatch_buffer_capacity 0: must be greater than zero",
+            config.id()
+        )));
+    }
+    let dispatch_mode = config.dispatch_mode().map(DispatchMode::from);
+    let dispatch_buffer_capacity = config.dispatch_buffer_capacity();
+
     let source: Box<dyn Source + 'static> = match &config {
         SourceConfig::Mock {
             id,
@@ -84,13 +134,17 @@ pub async fn create_source(config: SourceConfig) -> Result<Box<dyn Source + 'sta
             let mapper = DtoMapper::new();
             let mock_mapper = MockSourceConfigMapper;
             let domain_config = mock_mapper.map(c, &mapper)?;
-            Box::new(
-                MockSourceBuilder::new(id)
-                    .with_data_type(&domain_config.data_type)
-                    .with_interval_ms(domain_config.interval_ms)
-                    .with_auto_start(*auto_start)
-                    .build()?,
-            )
+            let mut builder = MockSourceBuilder::new(id)
+                .with_data_type(&domain_config.data_type)
+                .with_interval_ms(domain_config.interval_ms)
+                .with_auto_start(*auto_start);
+            if let Some(mode) = dispatch_mode {
+                builder = builder.with_dispatch_mode(mode);
+            }
+            if let Some(capacity) = dispatch_buffer_capacity {
+                builder = builder.with_dispatch_buffer_capacity(capacity);
+            }
+            Box::new(builder.build().map_err(build_error)?)
         }
         SourceConfig::Http {
             id,
@@ -102,12 +156,16 @@ pub async fn create_source(config: SourceConfig) -> Result<Box<dyn Source + 'sta
             let mapper = DtoMapper::new();
             let http_mapper = HttpSourceConfigMapper;
             let domain_config = http_mapper.map(c, &mapper)?;
-            Box::new(
-                HttpSourceBuilder::new(id)
-                    .with_config(domain_config)
-                    .with_auto_start(*auto_start)
-                    .build()?,
-            )
+            let mut builder = HttpSourceBuilder::new(id)
+                .with_config(domain_config)
+                .with_auto_start(*auto_start);
+            if let Some(mode) = dispatch_mode {
+                builder = builder.with_dispatch_mode(mode);
+            }
+            if let Some(capacity) = dispatch_buffer_capacity {
+                builder = builder.with_dispatch_buffer_capacity(capacity);
+            }
+            Box::new(builder.build().map_err(build_error)?)
         }
         SourceConfig::Grpc {
             id,
@@ -119,12 +177,16 @@ pub async fn create_source(config: SourceConfig) -> Result<Box<dyn Source + 'sta
             let mapper = DtoMapper::new();
             let grpc_mapper = GrpcSourceConfigMapper;
             let domain_config = grpc_mapper.map(c, &mapper)?;
-            Box::new(
-                GrpcSourceBuilder::new(id)
-                    .with_config(domain_config)
-                    .with_auto_start(*auto_start)
-                    .build()?,
-            )
+            let mut builder = GrpcSourceBuilder::new(id)
+                .with_config(domain_config)
+                .with_auto_start(*auto_start);
+            if let Some(mode) = dispatch_mode {
+                builder = builder.with_dispatch_mode(mode);
+            }
+            if let Some(capacity) = dispatch_buffer_capacity {
+                builder = builder.with_dispatch_buffer_capacity(capacity);
+            }
+            Box::new(builder.build().map_err(build_error)?)
         }
         SourceConfig::Postgres {
             id,
@@ -136,12 +198,16 @@ pub async fn create_source(config: SourceConfig) -> Result<Box<dyn Source + 'sta
             let mapper = DtoMapper::new();
             let postgres_mapper = PostgresConfigMapper;
             let domain_config = postgres_mapper.map(c, &mapper)?;
-            Box::new(
-                PostgresSourceBuilder::new(id)
-                    .with_config(domain_config)
-                    .with_auto_start(*auto_start)
-                    .build()?,
-            )
+            let mut builder = PostgresSourceBuilder::new(id)
+                .with_config(domain_config)
+                .with_auto_start(*auto_start);
+            if let Some(mode) = dispatch_mode {
+                builder = builder.with_dispatch_mode(mode);
+            }
+            if let Some(capacity) = dispatch_buffer_capacity {
+                builder = builder.with_dispatch_buffer_capacity(capacity);
+            }
+            Box::new(builder.build().map_err(build_error)?)
         }
         SourceConfig::Platform {
             id,
@@ -153,12 +219,16 @@ pub async fn create_source(config: SourceConfig) -> Result<Box<dyn Source + 'sta
             let mapper = DtoMapper::new();
             let platform_mapper = PlatformSourceConfigMapper;
             let domain_config = platform_mapper.map(c, &mapper)?;
-            Box::new(
-                PlatformSourceBuilder::new(id)
-                    .with_config(domain_config)
-                    .with_auto_start(*auto_start)
-                    .build()?,
-            )
+            let mut builder = PlatformSourceBuilder::new(id)
+                .with_config(domain_config)
+                .with_auto_start(*auto_start);
+            if let Some(mode) = dispatch_mode {
+                builder = builder.with_dispatch_mode(mode);
+            }
+            if let Some(capacity) = dispatch_buffer_capacity {
+                builder = builder.with_dispatch_buffer_capacity(capacity);
+            }
+            Box::new(builder.build().map_err(build_error)?)
         }
     };
 
@@ -189,8 +259,9 @@ fn create_bootstrap_provider(
                 let domain_config = postgres_mapper.map(config, &mapper)?;
                 Ok(Box::new(PostgresBootstrapProvider::new(domain_config)))
             } else {
-                Err(anyhow::anyhow!(
+                Err(FactoryError::Validation(
                     "Postgres bootstrap provider can only be used with Postgres sources"
+                        .to_string(),
                 ))
             }
         }
@@ -202,14 +273,15 @@ fn create_bootstrap_provider(
         }
         BootstrapProviderConfig::Platform(platform_config) => {
             use drasi_bootstrap_platform::PlatformBootstrapProvider;
-            Ok(Box::new(PlatformBootstrapProvider::new(
-                platform_config.clone(),
-            )?))
+            Ok(Box::new(
+                PlatformBootstrapProvider::new(platform_config.clone()).map_err(build_error)?,
+            ))
         }
         BootstrapProviderConfig::Application(_) => {
             // Application bootstrap is typically handled internally by application sources
-            Err(anyhow::anyhow!(
+            Err(FactoryError::Validation(
                 "Application bootstrap provider is managed internally by application sources"
+                    .to_string(),
             ))
         }
         BootstrapProviderConfig::Noop => {
@@ -256,6 +328,7 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
             queries,
             auto_start,
             config,
+            ..
         } => {
             use drasi_reaction_log::LogReactionBuilder;
             let log_mapper = LogReactionConfigMapper;
@@ -270,13 +343,14 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
             for (query_id, route_config) in domain_config.routes {
                 builder = builder.with_route(query_id, route_config);
             }
-            Ok(Box::new(builder.build()?))
+            Ok(Box::new(builder.build().map_err(build_error)?))
         }
         ReactionConfig::Http {
             id,
             queries,
             auto_start,
             config,
+            ..
         } => {
             use drasi_reaction_http::HttpReactionBuilder;
             let http_mapper = HttpReactionConfigMapper;
@@ -286,7 +360,8 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
                     .with_queries(queries)
                     .with_auto_start(auto_start)
                     .with_config(domain_config)
-                    .build()?,
+                    .build()
+                    .map_err(build_error)?,
             ))
         }
         ReactionConfig::HttpAdaptive {
@@ -294,6 +369,7 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
             queries,
             auto_start,
             config,
+            ..
         } => {
             use drasi_reaction_http_adaptive::HttpAdaptiveReactionBuilder;
             let http_adaptive_mapper = HttpAdaptiveReactionConfigMapper;
@@ -303,7 +379,8 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
                     .with_queries(queries)
                     .with_auto_start(auto_start)
                     .with_config(domain_config)
-                    .build()?,
+                    .build()
+                    .map_err(build_error)?,
             ))
         }
         ReactionConfig::Grpc {
@@ -311,6 +388,7 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
             queries,
             auto_start,
             config,
+            ..
         } => {
             use drasi_reaction_grpc::GrpcReactionBuilder;
             let grpc_mapper = GrpcReactionConfigMapper;
@@ -320,7 +398,8 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
                     .with_queries(queries)
                     .with_auto_start(auto_start)
                     .with_config(domain_config)
-                    .build()?,
+                    .build()
+                    .map_err(build_error)?,
             ))
         }
         ReactionConfig::GrpcAdaptive {
@@ -328,6 +407,7 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
             queries,
             auto_start,
             config,
+            ..
         } => {
             use drasi_reaction_grpc_adaptive::GrpcAdaptiveReactionBuilder;
             let grpc_adaptive_mapper = GrpcAdaptiveReactionConfigMapper;
@@ -337,7 +417,8 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
                     .with_queries(queries)
                     .with_auto_start(auto_start)
                     .with_config(domain_config)
-                    .build()?,
+                    .build()
+                    .map_err(build_error)?,
             ))
         }
         ReactionConfig::Sse {
@@ -345,6 +426,7 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
             queries,
             auto_start,
             config,
+            ..
         } => {
             use drasi_reaction_sse::SseReactionBuilder;
             let sse_mapper = SseReactionConfigMapper;
@@ -354,7 +436,8 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
                     .with_queries(queries)
                     .with_auto_start(auto_start)
                     .with_config(domain_config)
-                    .build()?,
+                    .build()
+                    .map_err(build_error)?,
             ))
         }
         ReactionConfig::Platform {
@@ -362,6 +445,7 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
             queries,
             auto_start,
             config,
+            ..
         } => {
             use drasi_reaction_platform::PlatformReactionBuilder;
             let platform_mapper = PlatformReactionConfigMapper;
@@ -371,7 +455,8 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
                     .with_queries(queries)
                     .with_auto_start(auto_start)
                     .with_config(domain_config)
-                    .build()?,
+                    .build()
+                    .map_err(build_error)?,
             ))
         }
         ReactionConfig::Profiler {
@@ -379,6 +464,7 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
             queries,
             auto_start,
             config,
+            ..
         } => {
             use drasi_reaction_profiler::ProfilerReactionBuilder;
             let profiler_mapper = ProfilerReactionConfigMapper;
@@ -388,8 +474,47 @@ pub fn create_reaction(config: ReactionConfig) -> Result<Box<dyn Reaction + 'sta
                     .with_queries(queries)
                     .with_auto_start(auto_start)
                     .with_config(domain_config)
-                    .build()?,
+                    .build()
+                    .map_err(build_error)?,
             ))
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn source(yaml: &str) -> SourceConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_value_is_a_mapping_error() {
+        let config = source("kind: mock\nid: s1\ndata_type: ${DRASI_FACTORY_TEST_UNSET}\n");
+
+        let err = create_source(config).await.err().unwrap();
+
+        assert!(matches!(err, FactoryError::Mapping(_)), "{err}");
+        assert!(err.to_string().contains("DRASI_FACTORY_TEST_UNSET"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_setting_is_a_validation_error() {
+        let config = source("kind: mock\nid: s1\ndispatch_buffer_capacity: 0\n");
+
+        let err = create_source(config).await.err().unwrap();
+
+        assert!(matches!(err, FactoryError::Validation(_)), "{err}");
+    }
+
+    #[test]
+    fn test_io_failures_are_connection_errors() {
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        let err = build_error(anyhow::Error::new(refused).context("connecting to postgres"));
+
+        assert!(matches!(err, FactoryError::Connection(_)), "{err}");
+        assert!(err.to_string().starts_with("connecting to postgres: "));
+    }
+}
@@ -0,0 +1,522 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deployment files generated next to a new configuration.
+//!
+//! `drasi-server init` can write a `docker-compose.yaml` that runs the server
+//! with the PostgreSQL and Redis services its sources and reactions use, or a
+//! systemd unit that runs it on the host.
+
+use drasi_server::api::models::{ConfigValue, ReactionConfig, SourceConfig};
+use drasi_server::DrasiServerConfig;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Image the generated compose file runs; built from this repository's
+/// Dockerfile
+const SERVER_IMAGE: &str = "drasi-server:latest";
+/// Where the compose file mounts the config directory
+const CONTAINER_CONFIG_DIR: &str = "/app/config";
+/// Index directory the image creates for the `drasi` user
+const CONTAINER_INDEX_DIR: &str = "/app/data/index";
+/// Hosts that only work when the server runs on the same machine
+const LOCAL_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "::1"];
+
+/// Deployment file to write after the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artifact {
+    None,
+    DockerCompose,
+    Systemd,
+}
+
+impl std::fmt::Display for Artifact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Artifact::None => write!(f, "None - Just the configuration file"),
+            Artifact::DockerCompose => {
+                write!(f, "Docker Compose - Server plus the databases it uses")
+            }
+            Artifact::Systemd => write!(f, "systemd - Unit file to run the server on this host"),
+        }
+    }
+}
+
+/// Backing services the configuration connects to.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Services {
+    postgres: Option<PostgresService>,
+    redis: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct PostgresService {
+    database: String,
+    user: String,
+    password: String,
+}
+
+fn static_or(value: &ConfigValue<String>, default: &str) -> String {
+    match value {
+        ConfigValue::Static(value) => value.clone(),
+        _ => default.to_string(),
+    }
+}
+
+fn services(config: &DrasiServerConfig) -> Services {
+    let mut services = Services::default();
+    for source in &config.sources {
+        match source {
+            SourceConfig::Postgres { config, .. } if services.postgres.is_none() => {
+                services.postgres = Some(PostgresService {
+                    database: static_or(&config.database, "drasi"),
+                    user: static_or(&config.user, "drasi"),
+                    password: static_or(&config.password, "drasi"),
+                });
+            }
+            SourceConfig::Platform { .. } => services.redis = true,
+            _ => {}
+        }
+    }
+    if config
+        .reactions
+        .iter()
+        .any(|reaction| matches!(reaction, ReactionConfig::Platform { .. }))
+    {
+        services.redis = true;
+    }
+    services
+}
+
+/// Point sources and reactions that connect to this machine at the compose
+/// services instead, since `localhost` inside the server's container is the
+/// container itself. Returns a note for each change.
+pub fn use_compose_services(config: &mut DrasiServerConfig) -> Vec<String> {
+    let mut notes = Vec::new();
+    for source in &mut config.sources {
+        match source {
+            SourceConfig::Postgres { id, config, .. } => {
+                if let ConfigValue::Static(host) = &mut config.host {
+                    if LOCAL_HOSTS.contains(&host.as_str()) {
+                        *host = "postgres".to_string();
+                        notes.push(format!("Source '{id}' now connects to host 'postgres'"));
+                    }
+                }
+            }
+            SourceConfig::Platform { id, config, .. } => {
+                if redirect_redis_url(&mut config.redis_url) {
+                    notes.push(format!("Source '{id}' now connects to host 'redis'"));
+                }
+            }
+            _ => {}
+        }
+    }
+    for reaction in &mut config.reactions {
+        if let ReactionConfig::Platform { id, config, .. } = reaction {
+            if redirect_redis_url(&mut config.redis_url) {
+                notes.push(format!("Reaction '{id}' now connects to host 'redis'"));
+            }
+        }
+    }
+    notes
+}
+
+fn redirect_redis_url(url: &mut ConfigValue<String>) -> bool {
+    let ConfigValue::Static(url) = url else {
+        return false;
+    };
+    for host in LOCAL_HOSTS {
+        for prefix in ["redis://", "rediss://"] {
+            let local = format!("{prefix}{host}");
+            if let Some(rest) = url.strip_prefix(&local) {
+                if rest.is_empty() || rest.starts_with([':', '/']) {
+                    *url = format!("{prefix}redis{rest}");
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+fn static_port(value: &ConfigValue<u16>) -> Option<u16> {
+    match value {
+        ConfigValue::Static(port) => Some(*port),
+        _ => None,
+    }
+}
+
+/// Ports the server listens on: the API and any SSE reactions.
+fn published_ports(config: &DrasiServerConfig) -> Vec<u16> {
+    let mut ports = vec![static_port(&config.port).unwrap_or(8080)];
+    for reaction in &config.reactions {
+        if let ReactionConfig::Sse { config, .. } = reaction {
+            if let Some(port) = static_port(&config.port) {
+                if !ports.contains(&port) {
+                    ports.push(port);
+                }
+            }
+        }
+    }
+    ports
+}
+
+/// A `docker-compose.yaml`, written to the current directory, running the
+/// server with the configuration at `config_path` and the services it uses.
+pub fn docker_compose(config: &DrasiServerConfig, config_path: &Path) -> String {
+    let services = services(config);
+    let config_dir = config_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let config_file = config_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "server.yaml".to_string());
+    let port = static_port(&config.port).unwrap_or(8080);
+
+    let mut out = String::new();
+    out.push_str("# Generated with: drasi-server init\n");
+    out.push_str("#\n");
+    out.push_str("# Build the image from the drasi-server repository first:\n");
+    let _ = writeln!(out, "#   docker build -t {SERVER_IMAGE} .");
+    out.push_str("# Then start everything:\n");
+    out.push_str("#   docker compose up -d\n\n");
+    out.push_str("services:\n");
+    out.push_str("  drasi-server:\n");
+    let _ = writeln!(out, "    image: {SERVER_IMAGE}");
+    let _ = writeln!(
+        out,
+        "    command: [\"--config\", \"{CONTAINER_CONFIG_DIR}/{config_file}\"]"
+    );
+    out.push_str("    ports:\n");
+    for published in published_ports(config) {
+        let _ = writeln!(out, "      - \"{published}:{published}\"");
+    }
+    out.push_str("    volumes:\n");
+    let _ = writeln!(
+        out,
+        "      - {}:{CONTAINER_CONFIG_DIR}:rw",
+        mount_source(config_dir)
+    );
+    if config.persist_index {
+        let index_path = container_index_path(config);
+        if index_path != CONTAINER_INDEX_DIR {
+            // Docker creates other mount points owned by root
+            let _ = writeln!(
+                out,
+                "      # The image only creates {CONTAINER_INDEX_DIR} for the drasi user; create and chown {index_path} in the image too"
+            );
+        }
+        let _ = writeln!(out, "      - drasi_index:{index_path}");
+    }
+    out.push_str("    environment:\n");
+    out.push_str("      - RUST_LOG=${LOG_LEVEL:-info}\n");
+    // Passed through from the shell or .env; left unset in the container
+    // when unset there, so defaults in the config still apply
+    for name in environment_variables(config) {
+        let _ = writeln!(out, "      - {name}");
+    }
+    if services.postgres.is_some() || services.redis {
+        out.push_str("    depends_on:\n");
+        if services.postgres.is_some() {
+            out.push_str("      postgres:\n        condition: service_healthy\n");
+        }
+        if services.redis {
+            out.push_str("      redis:\n        condition: service_healthy\n");
+        }
+    }
+    out.push_str("    healthcheck:\n");
+    let _ = writeln!(
+        out,
+        "      test: [\"CMD\", \"curl\", \"-f\", \"http://localhost:{port}/health\"]"
+    );
+    out.push_str("      interval: 10s\n      timeout: 5s\n      retries: 5\n");
+    out.push_str("    restart: unless-stopped\n");
+
+    if let Some(postgres) = &services.postgres {
+        out.push_str("\n  postgres:\n");
+        out.push_str("    image: postgres:14-alpine\n");
+        out.push_str("    environment:\n");
+        let _ = writeln!(
+            out,
+            "      POSTGRES_DB: {}",
+            yaml_string(&postgres.database)
+        );
+        let _ = writeln!(out, "      POSTGRES_USER: {}", yaml_string(&postgres.user));
+        let _ = writeln!(
+            out,
+            "      POSTGRES_PASSWORD: {}",
+            yaml_string(&postgres.password)
+        );
+        out.push_str("    # Logical replication is required for change data capture\n");
+        out.push_str("    command: [\"postgres\", \"-c\", \"wal_level=logical\"]\n");
+        out.push_str("    volumes:\n      - postgres_data:/var/lib/postgresql/data\n");
+        out.push_str("    healthcheck:\n");
+        let _ = writeln!(
+            out,
+            "      test: [\"CMD-SHELL\", \"pg_isready -U {} -d {}\"]",
+            postgres.user, postgres.database
+        );
+        out.push_str("      interval: 5s\n      timeout: 5s\n      retries: 5\n");
+        out.push_str("    restart: unless-stopped\n");
+    }
+
+    if services.redis {
+        out.push_str("\n  redis:\n");
+        out.push_str("    image: redis:7-alpine\n");
+        out.push_str("    healthcheck:\n");
+        out.push_str("      test: [\"CMD\", \"redis-cli\", \"ping\"]\n");
+        out.push_str("      interval: 5s\n      timeout: 5s\n      retries: 5\n");
+        out.push_str("    restart: unless-stopped\n");
+    }
+
+    if config.persist_index || services.postgres.is_some() {
+        out.push_str("\nvolumes:\n");
+        if config.persist_index {
+            out.push_str("  drasi_index:\n");
+        }
+        if services.postgres.is_some() {
+            out.push_str("  postgres_data:\n");
+        }
+    }
+    out
+}
+
+/// Volume source for a host directory; compose needs relative paths to
+/// start with `.`
+fn mount_source(dir: &Path) -> String {
+    let dir = dir.display().to_string();
+    if dir.starts_with('.') || dir.starts_with('/') {
+        dir
+    } else {
+        format!("./{dir}")
+    }
+}
+
+/// The index directory inside the server's container, which runs in /app
+fn container_index_path(config: &DrasiServerConfig) -> String {
+    let path = match &config.index_path {
+        Some(ConfigValue::Static(path)) => path.clone(),
+        _ => drasi_server::server::INDEX_PATH.to_string(),
+    };
+    match path.strip_prefix("./") {
+        Some(relative) => format!("/app/{relative}"),
+        None if path.starts_with('/') => path,
+        None => format!("/app/{path}"),
+    }
+}
+
+/// Names of the environment variables the configuration references, sorted
+fn environment_variables(config: &DrasiServerConfig) -> Vec<String> {
+    fn collect(value: &serde_yaml::Value, names: &mut Vec<String>) {
+        match value {
+            serde_yaml::Value::Mapping(map) => {
+                if map.get("kind").and_then(|kind| kind.as_str()) == Some("EnvironmentVariable") {
+                    if let Some(name) = map.get("name").and_then(|name| name.as_str()) {
+                        names.push(name.to_string());
+                    }
+                    return;
+                }
+                for value in map.values() {
+                    collect(value, names);
+                }
+            }
+            serde_yaml::Value::Sequence(values) => {
+                for value in values {
+                    collect(value, names);
+                }
+            }
+            serde_yaml::Value::Tagged(tagged) => collect(&tagged.value, names),
+            _ => {}
+        }
+    }
+
+    let mut names = Vec::new();
+    if let Ok(value) = serde_yaml::to_value(config) {
+        collect(&value, &mut names);
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn yaml_string(value: &str) -> String {
+    serde_yaml::to_string(value)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_else(|_| format!("{value:?}"))
+}
+
+/// A systemd unit running the server with the configuration at
+/// `config_path`, started by the binary at `executable` from `working_dir`,
+/// which relative paths in the configuration resolve against.
+pub fn systemd_unit(
+    config: &DrasiServerConfig,
+    config_path: &Path,
+    executable: &Path,
+    working_dir: &Path,
+) -> String {
+    let log_level = match &config.log_level {
+        ConfigValue::Static(level) => level.clone(),
+        _ => "info".to_string(),
+    };
+    let mut out = String::new();
+    out.push_str("# Generated with: drasi-server init\n");
+    out.push_str("#\n");
+    out.push_str("# Install with:\n");
+    out.push_str("#   sudo cp drasi-server.service /etc/systemd/system/\n");
+    out.push_str("#   sudo systemctl daemon-reload\n");
+    out.push_str("#   sudo systemctl enable --now drasi-server\n\n");
+    out.push_str("[Unit]\n");
+    out.push_str("Description=Drasi Server\n");
+    out.push_str("After=network-online.target\n");
+    out.push_str("Wants=network-online.target\n\n");
+    out.push_str("[Service]\n");
+    let _ = writeln!(
+        out,
+        "ExecStart={} --config {}",
+        executable.display(),
+        config_path.display()
+    );
+    // Only the TLS listener handles SIGHUP; without it the signal would
+    // terminate the server
+    if config.tls.is_some() {
+        out.push_str("# Reloads the TLS certificate\n");
+        out.push_str("ExecReload=/bin/kill -HUP $MAINPID\n");
+    }
+    let _ = writeln!(out, "WorkingDirectory={}", working_dir.display());
+    let _ = writeln!(out, "Environment=RUST_LOG={log_level}");
+    out.push_str("Restart=on-failure\n");
+    out.push_str("RestartSec=5\n");
+    if config.persist_index {
+        out.push_str("# RocksDB keeps many files open\n");
+        out.push_str("LimitNOFILE=65536\n");
+    }
+    out.push_str("\n[Install]\n");
+    out.push_str("WantedBy=multi-user.target\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> DrasiServerConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_compose_includes_services_the_config_uses() {
+        let mut config = config(
+            r#"
+            sources:
+              - kind: postgres
+                id: orders
+                host: localhost
+                database: shop
+                user: drasi
+                password: secret
+              - kind: platform
+                id: events
+                redis_url: redis://localhost:6379
+                stream_key: events
+            "#,
+        );
+
+        let notes = use_compose_services(&mut config);
+        let compose = docker_compose(&config, Path::new("config/server.yaml"));
+
+        assert_eq!(notes.len(), 2);
+        assert!(compose.contains("  postgres:\n"));
+        assert!(compose.contains("POSTGRES_DB: shop"));
+        assert!(compose.contains("  redis:\n"));
+        assert!(compose.contains("- ./config:/app/config:rw"));
+        assert!(compose.contains("/app/config/server.yaml"));
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&compose).unwrap();
+        assert!(yaml["services"]["drasi-server"].is_mapping());
+        match &config.sources[1] {
+            SourceConfig::Platform { config, .. } => assert_eq!(
+                config.redis_url,
+                ConfigValue::Static("redis://redis:6379".to_string())
+            ),
+            other => panic!("unexpected source {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_compose_without_services_has_only_the_server() {
+        let config = config("persist_index: true");
+
+        let compose = docker_compose(&config, Path::new("server.yaml"));
+
+        assert!(!compose.contains("postgres"));
+        assert!(!compose.contains("redis"));
+        assert!(compose.contains("- .:/app/config:rw"));
+        assert!(compose.contains("- drasi_index:/app/data/index"));
+    }
+
+    #[test]
+    fn test_compose_passes_referenced_environment_variables() {
+        let config = config(
+            r#"
+            api_keys:
+              - ${DRASI_API_KEY}
+            sources:
+              - kind: postgres
+                id: orders
+                host: postgres
+                database: shop
+                user: drasi
+                password: ${DB_PASSWORD:-secret}
+            "#,
+        );
+
+        let compose = docker_compose(&config, Path::new("server.yaml"));
+
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&compose).unwrap();
+        let environment: Vec<&str> = yaml["services"]["drasi-server"]["environment"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter_map(|entry| entry.as_str())
+            .collect();
+        assert_eq!(
+            environment,
+            vec![
+                "RUST_LOG=${LOG_LEVEL:-info}",
+                "DB_PASSWORD",
+                "DRASI_API_KEY"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_systemd_unit_runs_the_config() {
+        let config = config("log_level: debug");
+
+        let unit = systemd_unit(
+            &config,
+            Path::new("/srv/drasi/config/server.yaml"),
+            Path::new("/usr/local/bin/drasi-server"),
+            Path::new("/srv/drasi"),
+        );
+
+        assert!(unit.contains(
+            "ExecStart=/usr/local/bin/drasi-server --config /srv/drasi/config/server.yaml"
+        ));
+        assert!(unit.contains("WorkingDirectory=/srv/drasi"));
+        assert!(unit.contains("Environment=RUST_LOG=debug"));
+        assert!(!unit.contains("ExecReload"));
+    }
+}
@@ -16,16 +16,19 @@
 
 use anyhow::Result;
 
-use drasi_server::api::models::{ConfigValue, ReactionConfig, SourceConfig};
+use drasi_server::api::models::{ConfigValue, QueryLanguageDto, ReactionConfig, SourceConfig};
 use drasi_server::DrasiServerConfig;
 
 use super::prompts::ServerSettings;
 
 /// Build a complete DrasiServerConfig from user selections.
+///
+/// `query_language` is used for the sample query created when there are sources.
 pub fn build_config(
     server_settings: ServerSettings,
     sources: Vec<SourceConfig>,
     reactions: Vec<ReactionConfig>,
+    query_language: QueryLanguageDto,
 ) -> DrasiServerConfig {
     // Generate a unique server ID
     let server_id = uuid::Uuid::new_v4().to_string();
@@ -39,7 +42,7 @@ pub fn build_config(
         vec![drasi_lib::config::QueryConfig {
             id: "my-query".to_string(),
             query: "MATCH (n) RETURN n".to_string(),
-            query_language: drasi_lib::config::QueryLanguage::Cypher,
+            query_language: query_language.into(),
             auto_start: true,
             enable_bootstrap: true,
             bootstrap_buffer_size: 10000,
@@ -62,16 +65,16 @@ pub fn build_config(
 
     DrasiServerConfig {
         id: ConfigValue::Static(server_id),
-        host: ConfigValue::Static(server_settings.host),
+        host: ConfigValue::Static(server_settings.host.into()),
         port: ConfigValue::Static(server_settings.port),
         log_level: ConfigValue::Static(server_settings.log_level),
-        disable_persistence: false,
         persist_index: server_settings.persist_index,
-        default_priority_queue_capacity: None, // Use lib defaults
-        default_dispatch_buffer_capacity: None, // Use lib defaults
+        index_path: server_settings.index_path.map(ConfigValue::Static),
         sources,
         reactions,
         queries,
+        // Everything else keeps its default, including the lib's capacities
+        ..DrasiServerConfig::default()
     }
 }
 
@@ -95,6 +98,7 @@ pub fn generate_yaml(config: &DrasiServerConfig) -> Result<String> {
     yaml.push_str("# - Use environment variables: ${VAR_NAME:-default}\n");
     yaml.push_str("# - Update 'my-query' with your actual Cypher query\n");
     yaml.push_str("# - Connect reactions to your queries by updating the 'queries' field\n");
+    yaml.push_str("# - Pre-filter busy sources per query with 'nodes'/'relations' under queries[].sources\n");
 
     Ok(yaml)
 }
@@ -113,6 +117,7 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: false,
+            index_path: None,
         }
     }
 
@@ -122,6 +127,9 @@ mod tests {
             id: id.to_string(),
             auto_start: true,
             bootstrap_provider: None,
+            dispatch_mode: None,
+            dispatch_buffer_capacity: None,
+            lazy: false,
             config: MockSourceConfigDto {
                 interval_ms: ConfigValue::Static(5000),
                 data_type: ConfigValue::Static("generic".to_string()),
@@ -135,6 +143,10 @@ mod tests {
             id: id.to_string(),
             auto_start: true,
             bootstrap_provider: None,
+            dispatch_mode: None,
+            dispatch_buffer_capacity: None,
+            lazy: false,
+            reconnect: None,
             config: HttpSourceConfigDto {
                 host: ConfigValue::Static("0.0.0.0".to_string()),
                 port: ConfigValue::Static(9000),
@@ -156,6 +168,8 @@ mod tests {
             id: id.to_string(),
             queries: vec!["my-query".to_string()],
             auto_start: true,
+            wait_for_queries_ms: None,
+            group: None,
             config: LogReactionConfigDto::default(),
         }
     }
@@ -166,6 +180,8 @@ mod tests {
             id: id.to_string(),
             queries: vec!["my-query".to_string()],
             auto_start: true,
+            wait_for_queries_ms: None,
+            group: None,
             config: SseReactionConfigDto {
                 host: ConfigValue::Static("0.0.0.0".to_string()),
                 port: ConfigValue::Static(8081),
@@ -182,10 +198,10 @@ mod tests {
     #[test]
     fn test_build_config_empty_sources_and_reactions() {
         let settings = test_server_settings();
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         // Check server settings are applied
-        assert_eq!(config.host, ConfigValue::Static("0.0.0.0".to_string()));
+        assert_eq!(config.host, ConfigValue::Static("0.0.0.0".into()));
         assert_eq!(config.port, ConfigValue::Static(8080));
         assert_eq!(config.log_level, ConfigValue::Static("info".to_string()));
         assert!(!config.disable_persistence);
@@ -210,7 +226,7 @@ mod tests {
     fn test_build_config_with_single_source() {
         let settings = test_server_settings();
         let sources = vec![mock_source_config("my-mock")];
-        let config = build_config(settings, sources, vec![]);
+        let config = build_config(settings, sources, vec![], QueryLanguageDto::Cypher);
 
         // Check source is included
         assert_eq!(config.sources.len(), 1);
@@ -230,6 +246,16 @@ mod tests {
         assert_eq!(query.sources[0].source_id, "my-mock");
     }
 
+    #[test]
+    fn test_build_config_sample_query_uses_selected_language() {
+        let settings = test_server_settings();
+        let sources = vec![mock_source_config("my-mock")];
+        let config = build_config(settings, sources, vec![], QueryLanguageDto::Gql);
+
+        let yaml = generate_yaml(&config).unwrap();
+        assert!(yaml.contains("GQL"), "{yaml}");
+    }
+
     #[test]
     fn test_build_config_with_multiple_sources() {
         let settings = test_server_settings();
@@ -237,7 +263,7 @@ mod tests {
             mock_source_config("source-1"),
             http_source_config("source-2"),
         ];
-        let config = build_config(settings, sources, vec![]);
+        let config = build_config(settings, sources, vec![], QueryLanguageDto::Cypher);
 
         // Check all sources are included
         assert_eq!(config.sources.len(), 2);
@@ -253,7 +279,7 @@ mod tests {
     fn test_build_config_with_reactions() {
         let settings = test_server_settings();
         let reactions = vec![log_reaction_config("log-1"), sse_reaction_config("sse-1")];
-        let config = build_config(settings, vec![], reactions);
+        let config = build_config(settings, vec![], reactions, QueryLanguageDto::Cypher);
 
         // Check reactions are included
         assert_eq!(config.reactions.len(), 2);
@@ -268,14 +294,15 @@ mod tests {
             port: 9090,
             log_level: "debug".to_string(),
             persist_index: false,
+            index_path: None,
         };
         let sources = vec![mock_source_config("data-source")];
         let reactions = vec![log_reaction_config("my-log")];
 
-        let config = build_config(settings, sources, reactions);
+        let config = build_config(settings, sources, reactions, QueryLanguageDto::Cypher);
 
         // Check custom server settings
-        assert_eq!(config.host, ConfigValue::Static("127.0.0.1".to_string()));
+        assert_eq!(config.host, ConfigValue::Static("127.0.0.1".into()));
         assert_eq!(config.port, ConfigValue::Static(9090));
         assert_eq!(config.log_level, ConfigValue::Static("debug".to_string()));
 
@@ -290,8 +317,8 @@ mod tests {
         let settings1 = test_server_settings();
         let settings2 = test_server_settings();
 
-        let config1 = build_config(settings1, vec![], vec![]);
-        let config2 = build_config(settings2, vec![], vec![]);
+        let config1 = build_config(settings1, vec![], vec![], QueryLanguageDto::Cypher);
+        let config2 = build_config(settings2, vec![], vec![], QueryLanguageDto::Cypher);
 
         // Each call should generate a unique ID
         assert_ne!(config1.id, config2.id);
@@ -302,7 +329,7 @@ mod tests {
     #[test]
     fn test_generate_yaml_includes_header() {
         let settings = test_server_settings();
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -314,13 +341,14 @@ mod tests {
     #[test]
     fn test_generate_yaml_includes_tips() {
         let settings = test_server_settings();
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
         assert!(yaml.contains("# Tips:"));
         assert!(yaml.contains("# - Use environment variables: ${VAR_NAME:-default}"));
         assert!(yaml.contains("# - Update 'my-query' with your actual Cypher query"));
+        assert!(yaml.contains("'nodes'/'relations' under queries[].sources"));
     }
 
     #[test]
@@ -330,8 +358,9 @@ mod tests {
             port: 3000,
             log_level: "warn".to_string(),
             persist_index: false,
+            index_path: None,
         };
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -344,7 +373,7 @@ mod tests {
     fn test_generate_yaml_contains_sources() {
         let settings = test_server_settings();
         let sources = vec![mock_source_config("test-source")];
-        let config = build_config(settings, sources, vec![]);
+        let config = build_config(settings, sources, vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -357,7 +386,7 @@ mod tests {
     fn test_generate_yaml_contains_queries() {
         let settings = test_server_settings();
         let sources = vec![mock_source_config("src")];
-        let config = build_config(settings, sources, vec![]);
+        let config = build_config(settings, sources, vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -370,7 +399,7 @@ mod tests {
     fn test_generate_yaml_contains_reactions() {
         let settings = test_server_settings();
         let reactions = vec![log_reaction_config("my-log-reaction")];
-        let config = build_config(settings, vec![], reactions);
+        let config = build_config(settings, vec![], reactions, QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -383,7 +412,7 @@ mod tests {
         let settings = test_server_settings();
         let sources = vec![mock_source_config("src")];
         let reactions = vec![log_reaction_config("react")];
-        let config = build_config(settings, sources, reactions);
+        let config = build_config(settings, sources, reactions, QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -404,7 +433,7 @@ mod tests {
         let settings = test_server_settings();
         let sources = vec![mock_source_config("roundtrip-source")];
         let reactions = vec![log_reaction_config("roundtrip-reaction")];
-        let original_config = build_config(settings, sources, reactions);
+        let original_config = build_config(settings, sources, reactions, QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&original_config).unwrap();
 
@@ -433,7 +462,7 @@ mod tests {
     #[test]
     fn test_generate_yaml_empty_config() {
         let settings = test_server_settings();
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -453,8 +482,9 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: false,
+            index_path: None,
         };
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         assert!(
             !config.persist_index,
@@ -469,8 +499,9 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: true,
+            index_path: None,
         };
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         assert!(
             config.persist_index,
@@ -485,8 +516,9 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: false,
+            index_path: None,
         };
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -503,8 +535,9 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: true,
+            index_path: None,
         };
-        let config = build_config(settings, vec![], vec![]);
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&config).unwrap();
 
@@ -521,8 +554,9 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: true,
+            index_path: None,
         };
-        let original_config = build_config(settings, vec![], vec![]);
+        let original_config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&original_config).unwrap();
 
@@ -549,8 +583,9 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: false,
+            index_path: None,
         };
-        let original_config = build_config(settings, vec![], vec![]);
+        let original_config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
 
         let yaml = generate_yaml(&original_config).unwrap();
 
@@ -570,6 +605,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_yaml_includes_custom_index_path() {
+        let settings = ServerSettings {
+            host: "0.0.0.0".to_string(),
+            port: 8080,
+            log_level: "info".to_string(),
+            persist_index: true,
+            index_path: Some("/var/lib/drasi/index".to_string()),
+        };
+        let config = build_config(settings, vec![], vec![], QueryLanguageDto::Cypher);
+
+        let yaml = generate_yaml(&config).unwrap();
+
+        assert!(yaml.contains("index_path: /var/lib/drasi/index"), "{yaml}");
+    }
+
     #[test]
     fn test_build_config_persist_index_with_sources_and_reactions() {
         let settings = ServerSettings {
@@ -577,10 +628,11 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: true,
+            index_path: None,
         };
         let sources = vec![mock_source_config("test-source")];
         let reactions = vec![log_reaction_config("test-reaction")];
-        let config = build_config(settings, sources, reactions);
+        let config = build_config(settings, sources, reactions, QueryLanguageDto::Cypher);
 
         // Verify persist_index is set correctly even with sources and reactions
         assert!(config.persist_index);
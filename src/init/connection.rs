@@ -0,0 +1,185 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Connection checks offered by the init wizard.
+//!
+//! The checks speak just enough of each wire protocol to tell a real server
+//! apart from some other process on the port, without pulling in client
+//! libraries. PostgreSQL credentials are not verified; Redis credentials are
+//! when the URL carries a password.
+
+use anyhow::{anyhow, Context, Result};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// PostgreSQL `SSLRequest` message: length 8, request code 80877103.
+const PG_SSL_REQUEST: [u8; 8] = [0, 0, 0, 8, 0x04, 0xd2, 0x16, 0x2f];
+
+fn connect(host: &str, port: u16) -> Result<TcpStream> {
+    let addr = (host, port)
+        .to_socket_addrs()
+        .with_context(|| format!("Could not resolve host '{host}'"))?
+        .next()
+        .ok_or_else(|| anyhow!("Host '{host}' has no addresses"))?;
+    let stream = TcpStream::connect_timeout(&addr, TIMEOUT)
+        .with_context(|| format!("Could not connect to {host}:{port}"))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    Ok(stream)
+}
+
+/// Check that a PostgreSQL server answers at `host:port`.
+pub fn check_postgres(host: &str, port: u16) -> Result<()> {
+    let mut stream = connect(host, port)?;
+    stream.write_all(&PG_SSL_REQUEST)?;
+    let mut reply = [0u8; 1];
+    stream
+        .read_exact(&mut reply)
+        .with_context(|| format!("No reply from {host}:{port}"))?;
+    match reply[0] {
+        b'S' | b'N' => Ok(()),
+        _ => Err(anyhow!("{host}:{port} does not look like a PostgreSQL server")),
+    }
+}
+
+/// Connection details parsed from a `redis://` URL.
+#[derive(Debug, PartialEq, Eq)]
+struct RedisTarget {
+    host: String,
+    port: u16,
+    password: Option<String>,
+}
+
+fn parse_redis_url(url: &str) -> Result<RedisTarget> {
+    let rest = url
+        .strip_prefix("redis://")
+        .ok_or_else(|| anyhow!("Only redis:// URLs can be tested, got '{url}'"))?;
+    let authority = rest.split('/').next().unwrap_or_default();
+    let (password, host_port) = match authority.rsplit_once('@') {
+        Some((credentials, host_port)) => {
+            let password = credentials.rsplit(':').next().unwrap_or_default();
+            (Some(password.to_string()), host_port)
+        }
+        None => (None, authority),
+    };
+    let (host, port) = match host_port.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse().with_context(|| format!("Invalid port in '{url}'"))?,
+        ),
+        None => (host_port, 6379),
+    };
+    if host.is_empty() {
+        return Err(anyhow!("Missing host in '{url}'"));
+    }
+    Ok(RedisTarget {
+        host: host.to_string(),
+        port,
+        password: password.filter(|p| !p.is_empty()),
+    })
+}
+
+fn redis_command(stream: &mut TcpStream, args: &[&str]) -> Result<String> {
+    let mut command = format!("*{}\r\n", args.len());
+    for arg in args {
+        command.push_str(&format!("${}\r\n{arg}\r\n", arg.len()));
+    }
+    stream.write_all(command.as_bytes())?;
+
+    let mut reply = Vec::new();
+    let mut byte = [0u8; 1];
+    while !reply.ends_with(b"\r\n") {
+        stream.read_exact(&mut byte)?;
+        reply.push(byte[0]);
+    }
+    Ok(String::from_utf8_lossy(&reply).trim_end().to_string())
+}
+
+/// Check that a Redis server answers at `url`, authenticating if the URL
+/// contains a password.
+pub fn check_redis(url: &str) -> Result<()> {
+    let target = parse_redis_url(url)?;
+    let mut stream = connect(&target.host, target.port)?;
+    if let Some(password) = &target.password {
+        let reply = redis_command(&mut stream, &["AUTH", password.as_str()])?;
+        if reply.starts_with('-') {
+            return Err(anyhow!("Authentication failed: {}", &reply[1..]));
+        }
+    }
+    let reply = redis_command(&mut stream, &["PING"])?;
+    match reply.as_str() {
+        "+PONG" => Ok(()),
+        other if other.starts_with("-NOAUTH") => {
+            Err(anyhow!("Server requires a password; add it to the URL"))
+        }
+        other => Err(anyhow!("Unexpected reply to PING: {other}")),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Accept one connection, read `expect` bytes and answer with `reply`.
+    fn fake_server(expect: usize, reply: &'static [u8]) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = vec![0u8; expect];
+            stream.read_exact(&mut buf).unwrap();
+            stream.write_all(reply).unwrap();
+        });
+        port
+    }
+
+    #[test]
+    fn test_parse_redis_url() {
+        assert_eq!(
+            parse_redis_url("redis://:secret@cache:6380/0").unwrap(),
+            RedisTarget {
+                host: "cache".to_string(),
+                port: 6380,
+                password: Some("secret".to_string()),
+            }
+        );
+        assert_eq!(parse_redis_url("redis://localhost").unwrap().port, 6379);
+        assert!(parse_redis_url("rediss://localhost").is_err());
+        assert!(parse_redis_url("redis://:6379").is_err());
+    }
+
+    #[test]
+    fn test_check_postgres_accepts_ssl_reply() {
+        let port = fake_server(PG_SSL_REQUEST.len(), b"N");
+        assert!(check_postgres("127.0.0.1", port).is_ok());
+    }
+
+    #[test]
+    fn test_check_postgres_rejects_other_servers() {
+        let port = fake_server(PG_SSL_REQUEST.len(), b"HTTP/1.1 400");
+        assert!(check_postgres("127.0.0.1", port).is_err());
+    }
+
+    #[test]
+    fn test_check_redis_ping() {
+        let port = fake_server("*1\r\n$4\r\nPING\r\n".len(), b"+PONG\r\n");
+        assert!(check_redis(&format!("redis://127.0.0.1:{port}")).is_ok());
+    }
+}
@@ -21,20 +21,30 @@
 // Allow println! in init module for CLI user-facing output
 #![allow(clippy::print_stdout)]
 
+mod artifacts;
 mod builder;
+mod connection;
 mod prompts;
 
 use anyhow::Result;
 use std::fs;
 use std::path::PathBuf;
 
+use artifacts::Artifact;
+
+/// File names of the deployment files, written to the current directory
+const COMPOSE_FILE: &str = "docker-compose.yaml";
+const SYSTEMD_UNIT_FILE: &str = "drasi-server.service";
+
 /// Run the interactive configuration initialization.
 ///
 /// This function guides the user through selecting:
 /// 1. Server settings (host, port, log level)
 /// 2. Data sources (PostgreSQL, HTTP, gRPC, Mock, Platform)
 /// 3. Bootstrap providers for each source
-/// 4. Reactions (Log, HTTP, SSE, gRPC, Platform)
+/// 4. The language (Cypher or GQL) of the sample query
+/// 5. Reactions (Log, HTTP, SSE, gRPC, Platform)
+/// 6. Optionally, a docker-compose file or systemd unit to run it with
 ///
 /// The resulting configuration is written to the specified output file.
 pub fn run_init(output_path: PathBuf, force: bool) -> Result<()> {
@@ -61,11 +71,25 @@ pub fn run_init(output_path: PathBuf, force: bool) -> Result<()> {
     // Step 2: Select and configure sources
     let sources = prompts::prompt_sources()?;
 
-    // Step 3: Select and configure reactions
+    // Step 3: Language of the sample query, which is only created with sources
+    let query_language = if sources.is_empty() {
+        Default::default()
+    } else {
+        prompts::prompt_query_language()?
+    };
+
+    // Step 4: Select and configure reactions
     let reactions = prompts::prompt_reactions(&sources)?;
 
     // Build the configuration
-    let config = builder::build_config(server_settings, sources, reactions);
+    let mut config = builder::build_config(server_settings, sources, reactions, query_language);
+
+    // Step 5: Deployment file, which may point the config at its services
+    let artifact = prompts::prompt_artifact()?;
+    let notes = match artifact {
+        Artifact::DockerCompose => artifacts::use_compose_services(&mut config),
+        _ => Vec::new(),
+    };
 
     // Create parent directories
     if let Some(parent) = output_path.parent() {
@@ -78,10 +102,58 @@ pub fn run_init(output_path: PathBuf, force: bool) -> Result<()> {
 
     println!();
     println!("Configuration saved to: {}", output_path.display());
+    for note in &notes {
+        println!("  {note}");
+    }
+
+    let artifact_path = match artifact {
+        Artifact::None => None,
+        Artifact::DockerCompose => Some(PathBuf::from(COMPOSE_FILE)),
+        Artifact::Systemd => Some(PathBuf::from(SYSTEMD_UNIT_FILE)),
+    };
+    let artifact_path = match artifact_path {
+        Some(path) if path.exists() && !force => {
+            println!(
+                "Not writing {}: it already exists (use --force to overwrite)",
+                path.display()
+            );
+            None
+        }
+        Some(path) => {
+            let content = match artifact {
+                Artifact::DockerCompose => artifacts::docker_compose(&config, &output_path),
+                _ => {
+                    let working_dir = std::env::current_dir()?;
+                    let config_path = working_dir.join(&output_path);
+                    let executable = std::env::current_exe()
+                        .unwrap_or_else(|_| PathBuf::from("/usr/local/bin/drasi-server"));
+                    artifacts::systemd_unit(&config, &config_path, &executable, &working_dir)
+                }
+            };
+            fs::write(&path, content)?;
+            println!("Deployment file saved to: {}", path.display());
+            Some(path)
+        }
+        None => None,
+    };
+
     println!();
     println!("Next steps:");
     println!("  1. Review and edit {} as needed", output_path.display());
-    println!("  2. Run: drasi-server --config {}", output_path.display());
+    match (artifact, artifact_path) {
+        (Artifact::DockerCompose, Some(_)) => {
+            println!("  2. Build the image: docker build -t drasi-server:latest .");
+            println!("  3. Run: docker compose -f {COMPOSE_FILE} up -d");
+        }
+        (Artifact::Systemd, Some(path)) => {
+            println!(
+                "  2. Install: sudo cp {} /etc/systemd/system/ && sudo systemctl daemon-reload",
+                path.display()
+            );
+            println!("  3. Run: sudo systemctl enable --now drasi-server");
+        }
+        _ => println!("  2. Run: drasi-server --config {}", output_path.display()),
+    }
 
     Ok(())
 }
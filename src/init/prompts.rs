@@ -17,11 +17,14 @@
 use anyhow::Result;
 use inquire::{Confirm, MultiSelect, Password, Select, Text};
 
+use super::artifacts::Artifact;
+use super::connection;
+
 use drasi_server::api::models::{
     ConfigValue, GrpcReactionConfigDto, GrpcSourceConfigDto, HttpReactionConfigDto,
     HttpSourceConfigDto, LogReactionConfigDto, MockSourceConfigDto, PlatformReactionConfigDto,
-    PlatformSourceConfigDto, PostgresSourceConfigDto, ReactionConfig, SourceConfig,
-    SseReactionConfigDto, SslModeDto,
+    PlatformSourceConfigDto, PostgresSourceConfigDto, QueryLanguageDto, ReactionConfig,
+    SourceConfig, SseReactionConfigDto, SslModeDto,
 };
 
 /// Server settings collected from user prompts.
@@ -30,6 +33,8 @@ pub struct ServerSettings {
     pub port: u16,
     pub log_level: String,
     pub persist_index: bool,
+    /// Index directory, if not the default
+    pub index_path: Option<String>,
 }
 
 /// Source type selection options.
@@ -126,6 +131,18 @@ pub fn prompt_server_settings() -> Result<ServerSettings> {
         .with_help_message("Persists query index data to disk. Use for production workloads.")
         .prompt()?;
 
+    let index_path = if persist_index {
+        let path = Text::new("Index directory:")
+            .with_default(drasi_server::server::INDEX_PATH)
+            .with_help_message(
+                "Where RocksDB stores indexes; use a persistent volume in containers",
+            )
+            .prompt()?;
+        (path != drasi_server::server::INDEX_PATH).then_some(path)
+    } else {
+        None
+    };
+
     println!();
 
     Ok(ServerSettings {
@@ -133,9 +150,22 @@ pub fn prompt_server_settings() -> Result<ServerSettings> {
         port,
         log_level,
         persist_index,
+        index_path,
     })
 }
 
+/// Prompt for a deployment file to write along with the configuration.
+pub fn prompt_artifact() -> Result<Artifact> {
+    println!();
+    let artifact = Select::new(
+        "Generate a deployment file?",
+        vec![Artifact::None, Artifact::DockerCompose, Artifact::Systemd],
+    )
+    .with_help_message("Written to the current directory, next to the configuration")
+    .prompt()?;
+    Ok(artifact)
+}
+
 /// Prompt for source selection and configuration.
 pub fn prompt_sources() -> Result<Vec<SourceConfig>> {
     println!("Data Sources");
@@ -187,6 +217,40 @@ fn prompt_source_details(source_type: SourceType) -> Result<SourceConfig> {
     }
 }
 
+/// Offer to test a connection before the settings are accepted.
+///
+/// Returns `false` if the test failed and the user wants to re-enter the
+/// details. The test is skipped when any of `values` is an environment
+/// variable reference, since those are only resolved when the server starts.
+fn offer_connection_test(values: &[&str], check: impl FnOnce() -> Result<()>) -> Result<bool> {
+    if values.iter().any(|v| v.contains("${")) {
+        println!("Skipping connection test: settings use environment variables.");
+        return Ok(true);
+    }
+
+    let test = Confirm::new("Test connection now?")
+        .with_default(true)
+        .with_help_message("Attempts to reach the server with the details above")
+        .prompt()?;
+    if !test {
+        return Ok(true);
+    }
+
+    match check() {
+        Ok(()) => {
+            println!("[OK] Connection succeeded");
+            Ok(true)
+        }
+        Err(e) => {
+            println!("[ERROR] Connection failed: {e:#}");
+            Ok(Confirm::new("Keep these settings anyway?")
+                .with_default(false)
+                .with_help_message("Choose No to enter the details again")
+                .prompt()?)
+        }
+    }
+}
+
 /// Prompt for PostgreSQL source configuration.
 fn prompt_postgres_source() -> Result<SourceConfig> {
     println!("Configuring PostgreSQL Source");
@@ -219,6 +283,10 @@ fn prompt_postgres_source() -> Result<SourceConfig> {
         .without_confirmation()
         .prompt()?;
 
+    if !offer_connection_test(&[&host], || connection::check_postgres(&host, port))? {
+        return prompt_postgres_source();
+    }
+
     let tables_str = Text::new("Tables to monitor (comma-separated):")
         .with_default("my_table")
         .with_help_message("e.g., users,orders,products")
@@ -237,6 +305,10 @@ fn prompt_postgres_source() -> Result<SourceConfig> {
         id,
         auto_start: true,
         bootstrap_provider,
+        dispatch_mode: None,
+        dispatch_buffer_capacity: None,
+        lazy: false,
+        reconnect: None,
         config: PostgresSourceConfigDto {
             host: ConfigValue::Static(host),
             port: ConfigValue::Static(port),
@@ -307,6 +379,10 @@ fn prompt_http_source() -> Result<SourceConfig> {
         id,
         auto_start: true,
         bootstrap_provider,
+        dispatch_mode: None,
+        dispatch_buffer_capacity: None,
+        lazy: false,
+        reconnect: None,
         config: HttpSourceConfigDto {
             host: ConfigValue::Static(host),
             port: ConfigValue::Static(port),
@@ -346,6 +422,10 @@ fn prompt_grpc_source() -> Result<SourceConfig> {
         id,
         auto_start: true,
         bootstrap_provider,
+        dispatch_mode: None,
+        dispatch_buffer_capacity: None,
+        lazy: false,
+        reconnect: None,
         config: GrpcSourceConfigDto {
             host: ConfigValue::Static(host),
             port: ConfigValue::Static(port),
@@ -374,6 +454,9 @@ fn prompt_mock_source() -> Result<SourceConfig> {
         id,
         auto_start: true,
         bootstrap_provider: None,
+        dispatch_mode: None,
+        dispatch_buffer_capacity: None,
+        lazy: false,
         config: MockSourceConfigDto {
             interval_ms: ConfigValue::Static(interval_ms),
             data_type: ConfigValue::Static("generic".to_string()),
@@ -395,6 +478,10 @@ fn prompt_platform_source() -> Result<SourceConfig> {
         .with_help_message("Redis connection URL for streams")
         .prompt()?;
 
+    if !offer_connection_test(&[&redis_url], || connection::check_redis(&redis_url))? {
+        return prompt_platform_source();
+    }
+
     let stream_key = Text::new("Stream key in Redis:")
         .with_default("external-source:changes")
         .with_help_message("Redis stream key to consume from")
@@ -411,6 +498,10 @@ fn prompt_platform_source() -> Result<SourceConfig> {
         id,
         auto_start: true,
         bootstrap_provider,
+        dispatch_mode: None,
+        dispatch_buffer_capacity: None,
+        lazy: false,
+        reconnect: None,
         config: PlatformSourceConfigDto {
             redis_url: ConfigValue::Static(redis_url),
             stream_key: ConfigValue::Static(stream_key),
@@ -479,6 +570,20 @@ fn prompt_platform_bootstrap() -> Result<Option<drasi_lib::bootstrap::BootstrapP
     ))
 }
 
+/// Prompt for the language of the sample query.
+pub fn prompt_query_language() -> Result<QueryLanguageDto> {
+    println!("Query");
+    println!("-----");
+
+    let language = Select::new("Query language:", QueryLanguageDto::ALL.to_vec())
+        .with_help_message("Language of the sample query 'my-query'; edit its text afterwards")
+        .prompt()?;
+
+    println!();
+
+    Ok(language)
+}
+
 /// Prompt for reaction selection and configuration.
 pub fn prompt_reactions(sources: &[SourceConfig]) -> Result<Vec<ReactionConfig>> {
     println!("Reactions");
@@ -549,6 +654,8 @@ fn prompt_log_reaction() -> Result<ReactionConfig> {
         id,
         queries: vec!["my-query".to_string()], // Placeholder - user needs to edit
         auto_start: true,
+        wait_for_queries_ms: None,
+        group: None,
         config: LogReactionConfigDto::default(),
     })
 }
@@ -571,6 +678,8 @@ fn prompt_http_reaction() -> Result<ReactionConfig> {
         id,
         queries: vec!["my-query".to_string()],
         auto_start: true,
+        wait_for_queries_ms: None,
+        group: None,
         config: HttpReactionConfigDto {
             base_url: ConfigValue::Static(base_url),
             token: None,
@@ -603,6 +712,8 @@ fn prompt_sse_reaction() -> Result<ReactionConfig> {
         id,
         queries: vec!["my-query".to_string()],
         auto_start: true,
+        wait_for_queries_ms: None,
+        group: None,
         config: SseReactionConfigDto {
             host: ConfigValue::Static(host),
             port: ConfigValue::Static(port),
@@ -632,6 +743,8 @@ fn prompt_grpc_reaction() -> Result<ReactionConfig> {
         id,
         queries: vec!["my-query".to_string()],
         auto_start: true,
+        wait_for_queries_ms: None,
+        group: None,
         config: GrpcReactionConfigDto {
             endpoint: ConfigValue::Static(endpoint),
             timeout_ms: ConfigValue::Static(5000),
@@ -641,6 +754,7 @@ fn prompt_grpc_reaction() -> Result<ReactionConfig> {
             connection_retry_attempts: ConfigValue::Static(5),
             initial_connection_timeout_ms: ConfigValue::Static(10000),
             metadata: std::collections::HashMap::new(),
+            batching: Default::default(),
         },
     })
 }
@@ -659,10 +773,16 @@ fn prompt_platform_reaction() -> Result<ReactionConfig> {
         .with_help_message("Redis connection for publishing results")
         .prompt()?;
 
+    if !offer_connection_test(&[&redis_url], || connection::check_redis(&redis_url))? {
+        return prompt_platform_reaction();
+    }
+
     Ok(ReactionConfig::Platform {
         id,
         queries: vec!["my-query".to_string()],
         auto_start: true,
+        wait_for_queries_ms: None,
+        group: None,
         config: PlatformReactionConfigDto {
             redis_url: ConfigValue::Static(redis_url),
             pubsub_name: None,
@@ -672,6 +792,7 @@ fn prompt_platform_reaction() -> Result<ReactionConfig> {
             batch_enabled: ConfigValue::Static(false),
             batch_max_size: ConfigValue::Static(100),
             batch_max_wait_ms: ConfigValue::Static(100),
+            batching: Default::default(),
         },
     })
 }
@@ -689,6 +810,7 @@ mod tests {
             port: 9090,
             log_level: "debug".to_string(),
             persist_index: true,
+            index_path: None,
         };
 
         assert_eq!(settings.host, "127.0.0.1");
@@ -704,6 +826,7 @@ mod tests {
             port: 8080,
             log_level: "info".to_string(),
             persist_index: false,
+            index_path: None,
         };
 
         assert_eq!(settings.host, "0.0.0.0");
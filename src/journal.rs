@@ -0,0 +1,289 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Write-ahead journal of API mutations.
+//!
+//! Each mutation is appended to `<config file>.journal` (one JSON object per
+//! line) before the config file is rewritten, and the journal is cleared once
+//! the rewrite succeeds. If the server stops in between, for example while a
+//! debounced save is still pending, [`recover`] replays the journal onto the
+//! config file on the next startup.
+
+use anyhow::{Context, Result};
+use drasi_lib::config::QueryConfig;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::split_files::{read_config_file, render_config_files};
+use crate::config::{DisabledComponents, DrasiServerConfig, ReactionConfig, SourceConfig};
+use crate::registry::ComponentKind;
+
+/// A change made through the API, in terms of the config file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Mutation {
+    /// Create or replace a source
+    PutSource { config: SourceConfig },
+    /// Create or replace a query
+    PutQuery { config: QueryConfig },
+    /// Create or replace a reaction
+    PutReaction { config: ReactionConfig },
+    /// Delete a component
+    Remove { kind: ComponentKind, id: String },
+    /// Disable or enable a component
+    SetDisabled {
+        kind: ComponentKind,
+        id: String,
+        disabled: bool,
+    },
+    /// Change the log level
+    SetLogLevel { level: String },
+}
+
+fn disabled_ids(disabled: &mut DisabledComponents, kind: ComponentKind) -> &mut Vec<String> {
+    match kind {
+        ComponentKind::Source => &mut disabled.sources,
+        ComponentKind::Query => &mut disabled.queries,
+        ComponentKind::Reaction => &mut disabled.reactions,
+    }
+}
+
+impl Mutation {
+    /// Short description for log messages, e.g. `deleting query`
+    pub fn describe(&self) -> String {
+        match self {
+            Mutation::PutSource { .. } => "saving source".to_string(),
+            Mutation::PutQuery { .. } => "saving query".to_string(),
+            Mutation::PutReaction { .. } => "saving reaction".to_string(),
+            Mutation::Remove { kind, .. } => format!("deleting {}", kind.label()),
+            Mutation::SetDisabled { kind, disabled, .. } => {
+                let verb = if *disabled { "disabling" } else { "enabling" };
+                format!("{verb} {}", kind.label())
+            }
+            Mutation::SetLogLevel { .. } => "changing log level".to_string(),
+        }
+    }
+
+    /// Apply the mutation to a config file's contents.
+    pub fn apply(self, config: &mut DrasiServerConfig) {
+        match self {
+            Mutation::PutSource { config: source } => {
+                match config.sources.iter_mut().find(|s| s.id() == source.id()) {
+                    Some(existing) => *existing = source,
+                    None => config.sources.push(source),
+                }
+            }
+            Mutation::PutQuery { config: query } => {
+                match config.queries.iter_mut().find(|q| q.id == query.id) {
+                    Some(existing) => *existing = query,
+                    None => config.queries.push(query),
+                }
+            }
+            Mutation::PutReaction { config: reaction } => {
+                match config.reactions.iter_mut().find(|r| r.id() == reaction.id()) {
+                    Some(existing) => *existing = reaction,
+                    None => config.reactions.push(reaction),
+                }
+            }
+            Mutation::Remove { kind, id } => {
+                match kind {
+                    ComponentKind::Source => config.sources.retain(|s| s.id() != id),
+                    ComponentKind::Query => config.queries.retain(|q| q.id != id),
+                    ComponentKind::Reaction => config.reactions.retain(|r| r.id() != id),
+                }
+                disabled_ids(&mut config.disabled, kind).retain(|d| *d != id);
+            }
+            Mutation::SetDisabled { kind, id, disabled } => {
+                let ids = disabled_ids(&mut config.disabled, kind);
+                ids.retain(|d| *d != id);
+                if disabled {
+                    ids.push(id);
+                    ids.sort();
+                }
+            }
+            Mutation::SetLogLevel { level } => {
+                config.log_level = crate::api::models::ConfigValue::Static(level);
+            }
+        }
+    }
+}
+
+/// Location of the journal for a config file.
+pub fn journal_path(config_path: &Path) -> PathBuf {
+    let mut path = config_path.as_os_str().to_owned();
+    path.push(".journal");
+    PathBuf::from(path)
+}
+
+/// Append-only journal file next to a config file.
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    pub fn new(config_path: &Path) -> Self {
+        Self {
+            path: journal_path(config_path),
+        }
+    }
+
+    /// Append a mutation and sync it to disk.
+    pub fn append(&self, mutation: &Mutation) -> Result<()> {
+        let mut line = serde_json::to_string(mutation)?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open journal {}", self.path.display()))?;
+        file.write_all(line.as_bytes())?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// Read every complete entry. A torn last line from a crash mid-append is
+    /// skipped, since its mutation never reached the config file either.
+    pub fn read(&self) -> Result<Vec<Mutation>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+        let mut mutations = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(mutation) => mutations.push(mutation),
+                Err(e) if i + 1 == lines.len() => {
+                    warn!("Ignoring incomplete last journal entry: {e}");
+                }
+                Err(e) => {
+                    return Err(anyhow::anyhow!("Corrupt journal entry {}: {e}", i + 1));
+                }
+            }
+        }
+        Ok(mutations)
+    }
+
+    /// Remove the journal once its entries are in the config file.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Replay a leftover journal onto the config file and clear it.
+///
+/// Returns the number of mutations replayed. The file is parsed without
+/// `DRASI__` overrides so they are not baked into it.
+pub fn recover(config_path: &Path) -> Result<usize> {
+    let journal = Journal::new(config_path);
+    let mutations = journal.read()?;
+    if mutations.is_empty() {
+        journal.clear()?;
+        return Ok(0);
+    }
+
+    let mut config = read_config_file(config_path)?;
+    let count = mutations.len();
+    for mutation in mutations {
+        mutation.apply(&mut config);
+    }
+    config.validate()?;
+
+    for (path, content) in render_config_files(config_path, &config)? {
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &path)?;
+    }
+    journal.clear()?;
+
+    info!(
+        "Recovered {count} unsaved change(s) from {}",
+        journal.path.display()
+    );
+    Ok(count)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CONFIG: &str = r#"
+sources:
+  - kind: mock
+    id: s1
+queries:
+  - id: q1
+    query: MATCH (n) RETURN n
+    sources:
+      - source_id: s1
+"#;
+
+    #[test]
+    fn test_recover_replays_journal() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("server.yaml");
+        fs::write(&config_path, CONFIG).unwrap();
+
+        let journal = Journal::new(&config_path);
+        journal
+            .append(&Mutation::Remove {
+                kind: ComponentKind::Query,
+                id: "q1".to_string(),
+            })
+            .unwrap();
+        journal
+            .append(&Mutation::SetDisabled {
+                kind: ComponentKind::Source,
+                id: "s1".to_string(),
+                disabled: true,
+            })
+            .unwrap();
+
+        assert_eq!(recover(&config_path).unwrap(), 2);
+        assert!(!journal_path(&config_path).exists());
+
+        let config = crate::config::load_config_file(&config_path).unwrap();
+        assert!(config.queries.is_empty());
+        assert_eq!(config.disabled.sources, vec!["s1"]);
+    }
+
+    #[test]
+    fn test_read_skips_torn_last_entry() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("server.yaml");
+        let journal = Journal::new(&config_path);
+        journal
+            .append(&Mutation::SetLogLevel {
+                level: "debug".to_string(),
+            })
+            .unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(journal_path(&config_path))
+            .unwrap();
+        file.write_all(br#"{"op":"remove","kind":"#).unwrap();
+
+        assert_eq!(journal.read().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deferred start for sources that set `lazy: true`.
+//!
+//! Connecting a source can be expensive, e.g. a Postgres replication slot, so
+//! lazy sources are created with auto-start turned off and started here once a
+//! running query subscribes to them. A lazy source is not stopped again when
+//! its last query goes away; stopping it is left to the API.
+
+use drasi_lib::channels::ComponentStatus;
+use log::{info, warn};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Stopped lazy sources that gained a subscriber since the previous poll.
+///
+/// Only newly subscribed sources are returned, so a lazy source stopped
+/// through the API stays stopped until a query subscribes to it again.
+fn sources_to_start(
+    lazy: &[String],
+    statuses: &[(String, ComponentStatus)],
+    subscribed: &HashSet<String>,
+    previously_subscribed: &HashSet<String>,
+) -> Vec<String> {
+    lazy.iter()
+        .filter(|id| subscribed.contains(*id) && !previously_subscribed.contains(*id))
+        .filter(|id| {
+            statuses.iter().any(|(source_id, status)| {
+                source_id == *id && matches!(status, ComponentStatus::Stopped)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Sources subscribed by queries that are running or starting.
+async fn subscribed_sources(core: &drasi_lib::DrasiLib) -> HashSet<String> {
+    let mut subscribed = HashSet::new();
+    for (query_id, status) in core.list_queries().await.unwrap_or_default() {
+        if !matches!(status, ComponentStatus::Running | ComponentStatus::Starting) {
+            continue;
+        }
+        if let Ok(config) = core.get_query_config(&query_id).await {
+            subscribed.extend(config.sources.into_iter().map(|s| s.source_id));
+        }
+    }
+    subscribed
+}
+
+/// Start lazy sources in the background as queries subscribe to them.
+///
+/// Sources with `auto_start: false` and disabled sources are left alone. The
+/// task ends once `shutdown` is cancelled.
+pub fn spawn_lazy_start(
+    core: Arc<drasi_lib::DrasiLib>,
+    registry: Arc<ComponentRegistry>,
+    shutdown: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut previously_subscribed = HashSet::new();
+        loop {
+            let mut lazy = Vec::new();
+            for config in registry.source_configs().await {
+                if config.lazy()
+                    && config.auto_start()
+                    && !registry
+                        .is_disabled(ComponentKind::Source, config.id())
+                        .await
+                {
+                    lazy.push(config.id().to_string());
+                }
+            }
+
+            if !lazy.is_empty() {
+                let subscribed = subscribed_sources(&core).await;
+                let statuses = core.list_sources().await.unwrap_or_default();
+                for id in sources_to_start(&lazy, &statuses, &subscribed, &previously_subscribed) {
+                    match core.start_source(&id).await {
+                        Ok(_) => {
+                            registry.mark_started(ComponentKind::Source, &id).await;
+                            info!("Lazy source '{id}' started for its first subscriber");
+                        }
+                        Err(e) => warn!("Failed to start lazy source '{id}': {e}"),
+                    }
+                }
+                previously_subscribed = subscribed;
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(values: &[&str]) -> HashSet<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn statuses() -> Vec<(String, ComponentStatus)> {
+        vec![
+            ("pg".to_string(), ComponentStatus::Stopped),
+            ("kafka".to_string(), ComponentStatus::Stopped),
+            ("running".to_string(), ComponentStatus::Running),
+        ]
+    }
+
+    #[test]
+    fn test_only_subscribed_stopped_sources_are_started() {
+        let lazy = vec!["pg".to_string(), "kafka".to_string(), "running".to_string()];
+
+        let started = sources_to_start(
+            &lazy,
+            &statuses(),
+            &ids(&["pg", "running"]),
+            &HashSet::new(),
+        );
+
+        assert_eq!(started, vec!["pg".to_string()]);
+    }
+
+    #[test]
+    fn test_source_is_not_restarted_while_still_subscribed() {
+        let lazy = vec!["pg".to_string()];
+
+        let started = sources_to_start(&lazy, &statuses(), &ids(&["pg"]), &ids(&["pg"]));
+
+        assert!(started.is_empty());
+    }
+}
@@ -15,10 +15,22 @@
 pub mod api;
 pub mod builder;
 pub mod builder_result;
+pub mod compare;
 pub mod config;
 pub mod factories;
+pub mod journal;
+pub mod lazy_sources;
+pub mod liveness;
 pub mod persistence;
+pub mod platform_import;
+pub mod reaction_gate;
+pub mod reaction_groups;
+pub mod reconciler;
+pub mod reconnect;
+pub mod registry;
 pub mod server;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 // Main exports for library users
 pub use builder::DrasiServerBuilder;
@@ -27,8 +39,8 @@ pub use config::{
     load_config_file, save_config_file, ConfigError, DrasiServerConfig, ReactionConfig,
     SourceConfig,
 };
-pub use factories::{create_reaction, create_source};
-pub use server::DrasiServer;
+pub use factories::{create_reaction, create_source, FactoryError};
+pub use server::{DrasiServer, RunningServer};
 
 // Re-export API models and mappings for external use
 pub use api::mappings;
@@ -0,0 +1,298 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Liveness heartbeats for running sources, enabled by the `heartbeat`
+//! setting.
+//!
+//! A source can report `Running` while the connection it reads from is long
+//! gone, e.g. a Postgres replication connection that died without an error.
+//! Sources do not emit heartbeats themselves, so the server records them on
+//! their behalf: a Postgres source gets one while its replication slot is
+//! active, which the server checks over a connection of its own. That
+//! connection is opened with the source's `ssl_mode` for each check and
+//! closed again, so every Postgres source costs one short-lived connection
+//! per interval. Sources are checked side by side, a few at a time, so one
+//! unreachable database does not hold up the others. A running source
+//! without a heartbeat for `stale_after_secs` is reported as stale.
+//!
+//! Other kinds have no upstream connection the server can look at. They get
+//! no heartbeat and are never reported as stale.
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use log::{debug, info, warn};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{ring::default_provider, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::api::mappings::DtoMapper;
+use crate::api::models::{PostgresSourceConfigDto, SslModeDto};
+use crate::config::{HeartbeatSettings, SourceConfig};
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+/// A Postgres check that takes longer than this counts as a missed heartbeat
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How many sources are checked at the same time
+const MAX_CONCURRENT_PROBES: usize = 8;
+
+/// Whether a running source has gone too long without a heartbeat.
+///
+/// A heartbeat from before the current run does not count against it, so a
+/// restarted source gets the full `stale_after` to report in.
+fn is_stale(
+    last_heartbeat: Option<DateTime<Utc>>,
+    running_for: Duration,
+    stale_after: Duration,
+    now: DateTime<Utc>,
+) -> bool {
+    let silent_for = match last_heartbeat {
+        Some(at) => (now - at).to_std().unwrap_or_default().min(running_for),
+        None => running_for,
+    };
+    silent_for > stale_after
+}
+
+/// Accepts any server certificate. This is what libpq does for `prefer` and
+/// `require`, which encrypt the connection without authenticating the
+/// server, so the check connects wherever the source itself does.
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn tls_connector() -> anyhow::Result<tokio_postgres_rustls::MakeRustlsConnect> {
+    let provider = Arc::new(default_provider());
+    let config = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+        .with_no_client_auth();
+    Ok(tokio_postgres_rustls::MakeRustlsConnect::new(config))
+}
+
+/// Check that a Postgres source's replication slot has a live connection.
+async fn postgres_slot_active(config: &PostgresSourceConfigDto) -> anyhow::Result<bool> {
+    let mapper = DtoMapper::new();
+    let ssl_mode = mapper.resolve_typed(&config.ssl_mode)?;
+    let mut pg = tokio_postgres::Config::new();
+    pg.host(&mapper.resolve_typed(&config.host)?)
+        .port(mapper.resolve_typed(&config.port)?)
+        .dbname(&mapper.resolve_typed(&config.database)?)
+        .user(&mapper.resolve_typed(&config.user)?)
+        .password(mapper.resolve_typed(&config.password)?)
+        .connect_timeout(PROBE_TIMEOUT)
+        .ssl_mode(match ssl_mode {
+            SslModeDto::Disable => tokio_postgres::config::SslMode::Disable,
+            SslModeDto::Prefer => tokio_postgres::config::SslMode::Prefer,
+            SslModeDto::Require => tokio_postgres::config::SslMode::Require,
+        });
+
+    let (client, connection) = pg.connect(tls_connector()?).await?;
+    tokio::spawn(connection);
+    let row = client
+        .query_opt(
+            "SELECT active FROM pg_replication_slots WHERE slot_name = $1",
+            &[&config.slot_name],
+        )
+        .await?;
+    Ok(row.is_some_and(|row| row.get::<_, bool>(0)))
+}
+
+/// Whether a running source is alive, or `None` if the server cannot tell.
+async fn probe(config: &SourceConfig) -> Option<bool> {
+    let SourceConfig::Postgres { id, config, .. } = config else {
+        return None;
+    };
+    let alive = match tokio::time::timeout(PROBE_TIMEOUT, postgres_slot_active(config)).await {
+        Ok(Ok(active)) => {
+            if !active {
+                debug!(
+                    "Replication slot '{}' of source '{id}' is not active",
+                    config.slot_name
+                );
+            }
+            active
+        }
+        Ok(Err(e)) => {
+            debug!("Liveness check of source '{id}' failed: {e:#}");
+            false
+        }
+        Err(_) => {
+            debug!("Liveness check of source '{id}' timed out");
+            false
+        }
+    };
+    Some(alive)
+}
+
+/// Check every running source once, recording heartbeats and staleness.
+async fn check_sources(
+    core: &drasi_lib::DrasiLib,
+    registry: &ComponentRegistry,
+    settings: &HeartbeatSettings,
+) {
+    let stale_after = Duration::from_secs(settings.stale_after_secs);
+    let mut running = Vec::new();
+    for (id, status) in core.list_sources().await.unwrap_or_default() {
+        match registry
+            .observe_status(ComponentKind::Source, &id, &status)
+            .await
+        {
+            Some(running_for) => running.push((id, running_for)),
+            None => {
+                registry.set_stale(&id, false).await;
+            }
+        }
+    }
+
+    let mut checks = futures::stream::iter(running)
+        .map(|(id, running_for)| async move {
+            // Sources added without a stored definition cannot be probed
+            let alive = match registry.get_source(&id).await {
+                Some(config) => probe(&config).await,
+                None => None,
+            };
+            (id, running_for, alive)
+        })
+        .buffer_unordered(MAX_CONCURRENT_PROBES);
+    while let Some((id, running_for, alive)) = checks.next().await {
+        let Some(alive) = alive else {
+            registry.set_stale(&id, false).await;
+            continue;
+        };
+        if alive {
+            registry.record_heartbeat(&id).await;
+        }
+
+        let stale = is_stale(
+            registry.last_heartbeat(&id).await,
+            Duration::from_secs(running_for),
+            stale_after,
+            Utc::now(),
+        );
+        let was_stale = registry.set_stale(&id, stale).await;
+        if stale && !was_stale {
+            warn!(
+                "Source '{id}' is running but has had no heartbeat for over {}s",
+                settings.stale_after_secs
+            );
+        } else if !stale && was_stale {
+            info!("Source '{id}' is alive again");
+        }
+    }
+}
+
+/// Record heartbeats for running sources and mark silent ones stale, in the
+/// background, until `shutdown` is cancelled.
+pub fn spawn_liveness_monitor(
+    core: Arc<drasi_lib::DrasiLib>,
+    registry: Arc<ComponentRegistry>,
+    settings: HeartbeatSettings,
+    shutdown: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let interval = Duration::from_secs(settings.interval_secs);
+    info!(
+        "Checking source liveness every {}s; sources are stale after {}s without a heartbeat",
+        settings.interval_secs, settings.stale_after_secs
+    );
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = check_sources(&core, &registry, &settings) => {}
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STALE_AFTER: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_source_without_recent_heartbeat_is_stale() {
+        let now = Utc::now();
+        let running_for = Duration::from_secs(600);
+
+        let recent = Some(now - chrono::Duration::seconds(30));
+        assert!(!is_stale(recent, running_for, STALE_AFTER, now));
+
+        let old = Some(now - chrono::Duration::seconds(90));
+        assert!(is_stale(old, running_for, STALE_AFTER, now));
+        assert!(is_stale(None, running_for, STALE_AFTER, now));
+    }
+
+    #[test]
+    fn test_restarted_source_gets_a_grace_period() {
+        let now = Utc::now();
+        let old = Some(now - chrono::Duration::seconds(600));
+
+        assert!(!is_stale(old, Duration::from_secs(10), STALE_AFTER, now));
+        assert!(!is_stale(None, Duration::from_secs(10), STALE_AFTER, now));
+    }
+}
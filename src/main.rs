@@ -12,20 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// Allow println! in main.rs for CLI user-facing output (validate, doctor, init commands)
+// Allow println! in main.rs for CLI user-facing output (validate, apply, import-platform, doctor, init commands)
 #![allow(clippy::print_stdout)]
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use log::{debug, info, warn};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use utoipa::OpenApi;
 
 use drasi_server::api::mappings::{map_server_settings, DtoMapper};
 use drasi_server::api::models::ConfigValue;
-use drasi_server::{load_config_file, save_config_file, DrasiServer, DrasiServerConfig};
-
+use drasi_server::api::request_id::init_logger;
+use drasi_server::api::ApiDoc;
+use drasi_server::compare::{diff_results, fetch_results};
+use drasi_server::config::loader::{load_config_file_with, parse_set_override, CliOverrides};
+use drasi_server::config::manifests::{load_manifests, ManifestImport};
+use drasi_server::config::RuntimeSettings;
+use drasi_server::platform_import::{import_platform, PortForward};
+use drasi_server::{
+    create_reaction, create_source, load_config_file, save_config_file, DrasiServer,
+    DrasiServerConfig,
+};
+
+mod doctor;
 mod init;
 
 #[derive(Parser)]
@@ -56,6 +68,14 @@ enum Commands {
         /// Override the server port
         #[arg(short, long)]
         port: Option<u16>,
+
+        /// Override a config value, e.g. --set sources[0].port=9001 (repeatable)
+        #[arg(long = "set", value_name = "PATH=VALUE")]
+        set: Vec<String>,
+
+        /// Reject unknown fields in the config file
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Validate a configuration file without starting the server
@@ -67,6 +87,88 @@ enum Commands {
         /// Show resolved configuration with environment variables expanded
         #[arg(long)]
         show_resolved: bool,
+
+        /// Override a config value, e.g. --set sources[0].port=9001 (repeatable)
+        #[arg(long = "set", value_name = "PATH=VALUE")]
+        set: Vec<String>,
+
+        /// Reject unknown fields in the config file
+        #[arg(long)]
+        strict: bool,
+
+        /// Also build every source and reaction, without starting them, to
+        /// catch errors that parsing alone misses
+        #[arg(long)]
+        build: bool,
+    },
+
+    /// Import Drasi platform manifests (Source, ContinuousQuery, Reaction)
+    Apply {
+        /// Manifest file, or a directory of .yaml/.yml manifest files
+        #[arg(short = 'f', long = "file")]
+        file: PathBuf,
+
+        /// Configuration file to merge the imported components into
+        #[arg(short, long, default_value = "config/server.yaml")]
+        config: PathBuf,
+
+        /// Show what would change without writing the configuration file
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Import the components of a running Drasi platform instance
+    ImportPlatform {
+        /// Kube context of the cluster the platform runs in; its API is
+        /// reached through `kubectl port-forward`
+        #[arg(long, required_unless_present = "url", conflicts_with = "url")]
+        kube_context: Option<String>,
+
+        /// Namespace the platform is installed into
+        #[arg(long, default_value = drasi_server::platform_import::DEFAULT_NAMESPACE)]
+        namespace: String,
+
+        /// Base URL of the platform API, instead of a kube context
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Configuration file to merge the imported components into
+        #[arg(short, long, default_value = "config/server.yaml")]
+        config: PathBuf,
+
+        /// Show what would change without writing the configuration file
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Compare the results of queries on two running servers
+    Compare {
+        /// Base URL of the first server, e.g. http://localhost:8080
+        #[arg(long)]
+        left: String,
+
+        /// Base URL of the second server
+        #[arg(long)]
+        right: String,
+
+        /// Query to compare (repeatable)
+        #[arg(long = "query", required = true)]
+        queries: Vec<String>,
+
+        /// Result field identifying a row, to report rows whose values changed
+        #[arg(long)]
+        key: Option<String>,
+    },
+
+    /// Print the OpenAPI specification of the REST API
+    Openapi {
+        /// Write the specification to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Configuration file whose `openapi` metadata is applied, if it exists
+        #[arg(short, long, default_value = "config/server.yaml")]
+        config: PathBuf,
     },
 
     /// Check system dependencies and requirements
@@ -88,27 +190,102 @@ enum Commands {
     },
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    let overrides = match &cli.command {
+        Some(Commands::Run { set, strict, .. } | Commands::Validate { set, strict, .. }) => {
+            cli_overrides(set, *strict)?
+        }
+        _ => CliOverrides::default(),
+    };
+    let runtime_settings = match &cli.command {
+        Some(Commands::Run { config, .. }) => read_runtime_settings(config, &overrides)?,
+        None => read_runtime_settings(&cli.config, &overrides)?,
+        Some(_) => RuntimeSettings::default(),
+    };
+
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    // Zero is rejected when the config is validated
+    if let Some(threads) = runtime_settings.worker_threads {
+        builder.worker_threads(threads);
+    }
+    builder.enable_all().build()?.block_on(run(cli, overrides))
+}
+
+async fn run(cli: Cli, overrides: CliOverrides) -> Result<()> {
     match cli.command {
-        Some(Commands::Run { config, port }) => run_server(config, port).await,
+        Some(Commands::Run { config, port, .. }) => run_server(config, port, overrides).await,
         Some(Commands::Validate {
             config,
             show_resolved,
-        }) => validate_config(config, show_resolved),
-        Some(Commands::Doctor { all }) => run_doctor(all),
+            build,
+            ..
+        }) => validate_config(config, show_resolved, build, &overrides).await,
+        Some(Commands::Apply {
+            file,
+            config,
+            dry_run,
+        }) => apply_manifests(file, config, dry_run),
+        Some(Commands::ImportPlatform {
+            kube_context,
+            namespace,
+            url,
+            config,
+            dry_run,
+        }) => import_platform_components(kube_context, &namespace, url, config, dry_run).await,
+        Some(Commands::Compare {
+            left,
+            right,
+            queries,
+            key,
+        }) => compare_servers(&left, &right, &queries, key.as_deref()).await,
+        Some(Commands::Openapi { output, config }) => export_openapi(output, config),
+        Some(Commands::Doctor { all }) => run_doctor(all, &cli.config),
         Some(Commands::Init { output, force }) => init::run_init(output, force),
         None => {
             // Default behavior: run the server (backward compatible)
-            run_server(cli.config, cli.port).await
+            run_server(cli.config, cli.port, overrides).await
         }
     }
 }
 
+/// The `runtime` settings, which are needed before the server starts.
+///
+/// The config is loaded the way the server loads it, with the `.env` file
+/// next to it and `DRASI__` and `--set` overrides, so the runtime matches what
+/// `/about` reports. Without a config file the defaults are used.
+fn read_runtime_settings(config_path: &Path, overrides: &CliOverrides) -> Result<RuntimeSettings> {
+    if let Some(env_file) = config_path.parent().map(|dir| dir.join(".env")) {
+        // Failures are reported when the server loads it again
+        let _ = dotenvy::from_path(env_file);
+    }
+    if !config_path.exists() {
+        return Ok(RuntimeSettings::default());
+    }
+    Ok(load_config_file_with(config_path, overrides)?.runtime)
+}
+
+/// Collect the `--set` and `--strict` arguments.
+///
+/// They are handed to every load of the config file, including reloads in
+/// reconcile mode, rather than set in the environment where processes the
+/// server runs would inherit them. A `--set` flag takes precedence over a
+/// `DRASI__` variable for the same path.
+fn cli_overrides(sets: &[String], strict: bool) -> Result<CliOverrides> {
+    let set = sets
+        .iter()
+        .map(|arg| parse_set_override(arg))
+        .collect::<Result<_, _>>()?;
+    Ok(CliOverrides { set, strict })
+}
+
 /// Run the Drasi Server
-async fn run_server(config_path: PathBuf, port_override: Option<u16>) -> Result<()> {
+async fn run_server(
+    config_path: PathBuf,
+    port_override: Option<u16>,
+    overrides: CliOverrides,
+) -> Result<()> {
     // Load .env file if it exists (for environment variable interpolation)
     // Look for .env in the same directory as the config file
     let env_file_loaded = if let Some(config_dir) = config_path.parent() {
@@ -137,7 +314,7 @@ async fn run_server(config_path: PathBuf, port_override: Option<u16>) -> Result<
                 std::env::set_var("RUST_LOG", "info");
             }
         }
-        env_logger::init();
+        init_logger();
 
         warn!(
             "Config file '{}' not found. Creating default configuration.",
@@ -169,7 +346,7 @@ async fn run_server(config_path: PathBuf, port_override: Option<u16>) -> Result<
         (default_config, true)
     } else {
         // Load config first to get log level
-        (load_config_file(&config_path)?, false)
+        (load_config_file_with(&config_path, &overrides)?, false)
     };
 
     // Resolve server settings for use in main
@@ -185,7 +362,7 @@ async fn run_server(config_path: PathBuf, port_override: Option<u16>) -> Result<
                 std::env::set_var("RUST_LOG", &resolved_settings.log_level);
             }
         }
-        env_logger::init();
+        init_logger();
     }
 
     info!("Starting Drasi Server");
@@ -201,14 +378,19 @@ async fn run_server(config_path: PathBuf, port_override: Option<u16>) -> Result<
     info!("Port: {final_port}");
     debug!("Server configuration: {resolved_settings:?}");
 
-    let server = DrasiServer::new(config_path, final_port).await?;
+    let server = DrasiServer::with_overrides(config_path, final_port, overrides).await?;
     server.run().await?;
 
     Ok(())
 }
 
 /// Validate a configuration file
-fn validate_config(config_path: PathBuf, show_resolved: bool) -> Result<()> {
+async fn validate_config(
+    config_path: PathBuf,
+    show_resolved: bool,
+    build: bool,
+    overrides: &CliOverrides,
+) -> Result<()> {
     println!("Validating configuration: {}", config_path.display());
     println!();
 
@@ -222,7 +404,7 @@ fn validate_config(config_path: PathBuf, show_resolved: bool) -> Result<()> {
     }
 
     // Try to load and parse the config
-    match load_config_file(&config_path) {
+    match load_config_file_with(&config_path, overrides) {
         Ok(config) => {
             println!("[OK] Configuration file is valid");
             println!();
@@ -250,6 +432,10 @@ fn validate_config(config_path: PathBuf, show_resolved: bool) -> Result<()> {
                 }
             }
 
+            if build && !build_components(&config).await {
+                std::process::exit(1);
+            }
+
             Ok(())
         }
         Err(e) => {
@@ -260,8 +446,229 @@ fn validate_config(config_path: PathBuf, show_resolved: bool) -> Result<()> {
     }
 }
 
+/// Resolve the server settings and build every source and reaction the way
+/// the server would, without starting anything. Returns false if any failed.
+async fn build_components(config: &DrasiServerConfig) -> bool {
+    println!();
+    println!("Building components:");
+    let mut failed = 0;
+
+    let settings = map_server_settings(config, &DtoMapper::new())
+        .and_then(|settings| match &settings.tls {
+            Some(tls) => tls.load().map(|_| ()),
+            None => Ok(()),
+        });
+    match settings {
+        Ok(()) => println!("  [OK] server settings"),
+        Err(e) => {
+            println!("  [ERROR] server settings: {e:#}");
+            failed += 1;
+        }
+    }
+
+    for source in &config.sources {
+        match create_source(source.clone()).await {
+            Ok(_) => println!("  [OK] source '{}'", source.id()),
+            Err(e) => {
+                println!("  [ERROR] source '{}': {e}", source.id());
+                failed += 1;
+            }
+        }
+    }
+    for reaction in &config.reactions {
+        match create_reaction(reaction.clone()) {
+            Ok(_) => println!("  [OK] reaction '{}'", reaction.id()),
+            Err(e) => {
+                println!("  [ERROR] reaction '{}': {e}", reaction.id());
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        println!();
+        println!("[ERROR] {failed} component(s) failed to build");
+    }
+    failed == 0
+}
+
+/// Import platform manifests into a configuration file
+fn apply_manifests(manifest_path: PathBuf, config_path: PathBuf, dry_run: bool) -> Result<()> {
+    println!("Importing manifests: {}", manifest_path.display());
+    println!();
+
+    let import = match load_manifests(&manifest_path) {
+        Ok(import) => import,
+        Err(e) => {
+            println!("[ERROR] Could not import manifests:");
+            println!("  {e:#}");
+            std::process::exit(1);
+        }
+    };
+
+    write_import(import, &config_path, dry_run)
+}
+
+/// Import the components of a running platform into a configuration file
+async fn import_platform_components(
+    kube_context: Option<String>,
+    namespace: &str,
+    url: Option<String>,
+    config_path: PathBuf,
+    dry_run: bool,
+) -> Result<()> {
+    // Kept alive until the import is done
+    let mut port_forward = None;
+    let base_url = match (url, kube_context) {
+        (Some(url), _) => url,
+        (None, Some(context)) => {
+            println!("Connecting to the platform API in context '{context}'");
+            match PortForward::open(&context, namespace).await {
+                Ok(forward) => port_forward.insert(forward).base_url.clone(),
+                Err(e) => {
+                    println!("[ERROR] Could not reach the platform API:");
+                    println!("  {e:#}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        (None, None) => anyhow::bail!("Either --url or --kube-context is required"),
+    };
+    println!("Importing platform components: {base_url}");
+    println!();
+
+    let import = match import_platform(&reqwest::Client::new(), &base_url).await {
+        Ok(import) => import,
+        Err(e) => {
+            println!("[ERROR] Could not import platform components:");
+            println!("  {e:#}");
+            std::process::exit(1);
+        }
+    };
+    drop(port_forward);
+
+    write_import(import, &config_path, dry_run)
+}
+
+/// Merge imported components into the configuration file at `config_path`
+fn write_import(import: ManifestImport, config_path: &Path, dry_run: bool) -> Result<()> {
+    let mut config = if config_path.exists() {
+        load_config_file(config_path)?
+    } else {
+        DrasiServerConfig::default()
+    };
+
+    let warnings = import.warnings.clone();
+    let changes = import.merge_into(&mut config);
+
+    if let Err(e) = config.validate() {
+        println!("[ERROR] Resulting configuration is invalid:");
+        println!("  {e}");
+        std::process::exit(1);
+    }
+
+    for change in &changes {
+        println!("  {change}");
+    }
+    for warning in &warnings {
+        println!("[WARN] {warning}");
+    }
+    println!();
+
+    if dry_run {
+        println!("Dry run: {} not modified", config_path.display());
+        return Ok(());
+    }
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    save_config_file(&config, config_path)?;
+    println!(
+        "[OK] Wrote {} change(s) to {}",
+        changes.len(),
+        config_path.display()
+    );
+    Ok(())
+}
+
+/// Compare query results on two servers, exiting with status 1 if any differ
+async fn compare_servers(
+    left: &str,
+    right: &str,
+    queries: &[String],
+    key: Option<&str>,
+) -> Result<()> {
+    println!("Comparing {left} (left) with {right} (right)");
+    println!();
+
+    let client = reqwest::Client::new();
+    let mut differences = false;
+    for query in queries {
+        let (left_rows, right_rows) = tokio::try_join!(
+            fetch_results(&client, left, query),
+            fetch_results(&client, right, query)
+        )?;
+        let diff = diff_results(&left_rows, &right_rows, key);
+        if diff.is_empty() {
+            println!("[OK] {query}: {} matching row(s)", diff.matching);
+            continue;
+        }
+
+        differences = true;
+        println!(
+            "[DIFF] {query}: {} matching, {} only in left, {} only in right, {} changed",
+            diff.matching,
+            diff.only_left.len(),
+            diff.only_right.len(),
+            diff.changed.len()
+        );
+        for row in &diff.only_left {
+            println!("  - {row}");
+        }
+        for row in &diff.only_right {
+            println!("  + {row}");
+        }
+        for (left_row, right_row) in &diff.changed {
+            println!("  ~ {left_row}");
+            println!("    -> {right_row}");
+        }
+    }
+
+    if differences {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Print or save the OpenAPI specification served at /api-docs/openapi.json
+///
+/// The config file's `openapi` metadata is applied as the server applies it.
+/// Without a config file the built-in metadata is kept.
+fn export_openapi(output: Option<PathBuf>, config_path: PathBuf) -> Result<()> {
+    let mut doc = ApiDoc::openapi();
+    if config_path.exists() {
+        if let Some(env_file) = config_path.parent().map(|dir| dir.join(".env")) {
+            let _ = dotenvy::from_path(env_file);
+        }
+        let config = load_config_file(&config_path)?;
+        map_server_settings(&config, &DtoMapper::new())?
+            .openapi
+            .apply(&mut doc);
+    }
+    let spec = doc.to_pretty_json()?;
+    match output {
+        Some(path) => {
+            fs::write(&path, spec + "\n")?;
+            println!("OpenAPI specification written to {}", path.display());
+        }
+        None => println!("{spec}"),
+    }
+    Ok(())
+}
+
 /// Check system dependencies
-fn run_doctor(check_all: bool) -> Result<()> {
+fn run_doctor(check_all: bool, config_path: &Path) -> Result<()> {
     println!("Drasi Server Dependency Check");
     println!("==============================");
     println!();
@@ -307,6 +714,9 @@ fn run_doctor(check_all: bool) -> Result<()> {
         all_ok = false;
     }
 
+    println!();
+    let data_ok = doctor::check_data_directory(config_path);
+
     if check_all {
         println!();
         println!("Optional (for examples and Docker deployment):");
@@ -370,9 +780,15 @@ fn run_doctor(check_all: bool) -> Result<()> {
 
     if all_ok {
         println!("All required dependencies are available.");
-        Ok(())
     } else {
         println!("Some required dependencies are missing.");
+    }
+    if !data_ok {
+        println!("The data directory is not usable.");
+    }
+    if all_ok && data_ok {
+        Ok(())
+    } else {
         std::process::exit(1);
     }
 }
@@ -12,50 +12,151 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::config::split_files::render_config_files;
 use crate::config::DrasiServerConfig;
+use crate::journal::{Journal, Mutation};
+use crate::registry::ComponentRegistry;
 use anyhow::Result;
 use log::{debug, error, info};
+use serde::Serialize;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
 
 /// Handles persistence of DrasiServerConfig to a YAML file.
-/// Uses atomic writes (temp file + rename) to prevent corruption.
+/// Uses atomic writes (temp file + rename) to prevent corruption, and records
+/// each mutation in a write-ahead journal until it has been saved.
+///
+/// Saving starts from the config as it is written in the file, without
+/// `DRASI__` overrides, and replaces only what the API changes: sources,
+/// queries, reactions, the disabled set and the log level.
+///
+/// With a debounce window, save requests made in quick succession are
+/// coalesced into a single write; call [`ConfigPersistence::flush`] before
+/// shutting down so a pending write is not lost.
 pub struct ConfigPersistence {
     config_file_path: PathBuf,
     core: Arc<drasi_lib::DrasiLib>,
-    host: String,
-    port: u16,
-    log_level: String,
-    disable_persistence: bool,
-    persist_index: bool,
+    /// The config as written in the file
+    written: DrasiServerConfig,
+    /// The config the server was started with, overrides applied
+    loaded: DrasiServerConfig,
+    /// Log level set through the API since the server started
+    log_level: RwLock<Option<String>>,
+    registry: Option<Arc<ComponentRegistry>>,
+    debounce: Option<Duration>,
+    /// A debounced save is scheduled and has not written yet
+    save_pending: AtomicBool,
+    /// Serializes writes, which share the same temp file and journal
+    write_lock: Mutex<()>,
+    journal: Journal,
 }
 
 impl ConfigPersistence {
-    /// Create a new ConfigPersistence instance
+    /// Create a new ConfigPersistence instance for the config `written` in
+    /// the file at `config_file_path`, before `DRASI__` overrides.
     pub fn new(
         config_file_path: PathBuf,
         core: Arc<drasi_lib::DrasiLib>,
-        host: String,
-        port: u16,
-        log_level: String,
-        disable_persistence: bool,
-        persist_index: bool,
+        written: DrasiServerConfig,
     ) -> Self {
         Self {
+            journal: Journal::new(&config_file_path),
             config_file_path,
             core,
-            host,
-            port,
-            log_level,
-            disable_persistence,
-            persist_index,
+            debounce: written
+                .persistence_debounce_ms
+                .map(Duration::from_millis)
+                .filter(|w| !w.is_zero()),
+            loaded: written.clone(),
+            written,
+            log_level: RwLock::new(None),
+            registry: None,
+            save_pending: AtomicBool::new(false),
+            write_lock: Mutex::new(()),
         }
     }
 
+    /// The config the server was started with, `DRASI__` overrides applied.
+    ///
+    /// Components still as they were loaded are saved as written in the
+    /// file, so overridden values such as passwords are not written into it.
+    pub fn with_loaded_config(mut self, loaded: DrasiServerConfig) -> Self {
+        self.loaded = loaded;
+        self
+    }
+
+    /// Write source and reaction definitions and the disabled set from the
+    /// given registry when saving.
+    pub fn with_registry(mut self, registry: Arc<ComponentRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Change the log level written on the next save.
+    pub async fn set_log_level(&self, log_level: String) {
+        *self.log_level.write().await = Some(log_level);
+    }
+
+    /// Append a mutation to the journal ahead of the next save.
+    pub async fn record(&self, mutation: &Mutation) -> Result<()> {
+        if self.loaded.disable_persistence {
+            return Ok(());
+        }
+        let _write_guard = self.write_lock.lock().await;
+        self.journal.append(mutation)
+    }
+
+    /// Request a save after a mutation.
+    ///
+    /// Without a debounce window this saves immediately. Otherwise a save is
+    /// scheduled at the end of the window, and further requests made before it
+    /// runs are folded into it; errors from a scheduled save are logged.
+    pub async fn request_save(self: &Arc<Self>) -> Result<()> {
+        let Some(window) = self.debounce else {
+            return self.save().await;
+        };
+        if self.save_pending.swap(true, Ordering::SeqCst) {
+            debug!("Save already scheduled, coalescing");
+            return Ok(());
+        }
+        let persistence = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(window).await;
+            let _write_guard = persistence.write_lock.lock().await;
+            // flush() may have written already
+            if persistence.save_pending.swap(false, Ordering::SeqCst) {
+                if let Err(e) = persistence.save_locked().await {
+                    error!("Failed to persist configuration: {e}");
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Write a scheduled save now instead of waiting for the debounce window.
+    ///
+    /// A scheduled save that is already writing is waited for.
+    pub async fn flush(&self) -> Result<()> {
+        let _write_guard = self.write_lock.lock().await;
+        if self.save_pending.swap(false, Ordering::SeqCst) {
+            self.save_locked().await?;
+        }
+        Ok(())
+    }
+
     /// Save the current configuration to the config file using atomic writes.
     /// Uses Core's public API to get current configuration snapshot.
     pub async fn save(&self) -> Result<()> {
-        if self.disable_persistence {
+        let _write_guard = self.write_lock.lock().await;
+        self.save_locked().await
+    }
+
+    /// Save with `write_lock` already held.
+    async fn save_locked(&self) -> Result<()> {
+        if self.loaded.disable_persistence {
             debug!("Persistence disabled, skipping save");
             return Ok(());
         }
@@ -72,39 +173,72 @@ impl ConfigPersistence {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to get current config from DrasiLib: {e}"))?;
 
-        // Construct DrasiServerConfig from lib config fields
-        // Note: sources and reactions are empty here because they are owned by the core
-        // and we don't have access to the original config enums. The core manages them
-        // dynamically through the builder pattern or API.
-        let wrapper_config = DrasiServerConfig {
-            id: crate::api::models::ConfigValue::Static(lib_config.id.clone()),
-            host: crate::api::models::ConfigValue::Static(self.host.clone()),
-            port: crate::api::models::ConfigValue::Static(self.port),
-            log_level: crate::api::models::ConfigValue::Static(self.log_level.clone()),
-            disable_persistence: self.disable_persistence,
-            persist_index: self.persist_index,
-            default_priority_queue_capacity: lib_config
-                .priority_queue_capacity
-                .map(crate::api::models::ConfigValue::Static),
-            default_dispatch_buffer_capacity: lib_config
-                .dispatch_buffer_capacity
-                .map(crate::api::models::ConfigValue::Static),
-            sources: Vec::new(),
-            reactions: Vec::new(),
-            queries: lib_config.queries.clone(),
-        };
+        let mut wrapper_config = self.written.clone();
+        if let Some(log_level) = self.log_level.read().await.clone() {
+            wrapper_config.log_level = crate::api::models::ConfigValue::Static(log_level);
+        }
+
+        // Sources and reactions are owned by the core as trait objects, so their
+        // original config enums come from the registry. Without a registry they
+        // are written as they are in the file.
+        let mut queries = lib_config.queries.clone();
+        if let Some(registry) = &self.registry {
+            // Write back the auto_start of disabled queries as configured,
+            // not as overridden at boot
+            for query in queries.iter_mut() {
+                if registry.is_query_auto_start_suppressed(&query.id).await {
+                    query.auto_start = true;
+                }
+            }
+            wrapper_config.sources = as_written(
+                registry.source_configs().await,
+                &self.loaded.sources,
+                &self.written.sources,
+                |source| source.id(),
+            );
+            wrapper_config.reactions = as_written(
+                registry.reaction_configs().await,
+                &self.loaded.reactions,
+                &self.written.reactions,
+                |reaction| reaction.id(),
+            );
+            wrapper_config.disabled = registry.disabled_components().await;
+        }
+        wrapper_config.queries = as_written(
+            queries,
+            &self.loaded.queries,
+            &self.written.queries,
+            |query| query.id.as_str(),
+        );
 
         // Validate before saving
         wrapper_config.validate()?;
 
-        // Use atomic write: write to temp file, then rename
-        let temp_path = self.config_file_path.with_extension("tmp");
+        // Patch the existing files so comments and unchanged sections survive.
+        // Section files come first, so the main file never names one that has
+        // not been written yet
+        for (path, content) in render_config_files(&self.config_file_path, &wrapper_config)? {
+            Self::write_atomic(&path, content)?;
+        }
 
-        // Serialize to YAML
-        let yaml_content = serde_yaml::to_string(&wrapper_config)?;
+        // Everything journaled so far is part of the snapshot just written
+        if let Err(e) = self.journal.clear() {
+            error!("Failed to clear persistence journal: {e}");
+        }
+
+        info!(
+            "Configuration saved successfully to {}",
+            self.config_file_path.display()
+        );
+        Ok(())
+    }
+
+    /// Write a file via a temp file and rename, so it is never left half written.
+    fn write_atomic(path: &Path, content: String) -> Result<()> {
+        let temp_path = path.with_extension("tmp");
 
         // Write to temp file
-        std::fs::write(&temp_path, yaml_content).map_err(|e| {
+        std::fs::write(&temp_path, content).map_err(|e| {
             error!(
                 "Failed to write temp config file {}: {e}",
                 temp_path.display()
@@ -113,22 +247,16 @@ impl ConfigPersistence {
         })?;
 
         // Atomically rename temp file to actual config file
-        std::fs::rename(&temp_path, &self.config_file_path).map_err(|e| {
+        std::fs::rename(&temp_path, path).map_err(|e| {
             error!(
                 "Failed to rename temp config file {} to {}: {e}",
                 temp_path.display(),
-                self.config_file_path.display()
+                path.display()
             );
             // Clean up temp file if rename fails
             let _ = std::fs::remove_file(&temp_path);
             anyhow::anyhow!("Failed to rename config file: {e}")
-        })?;
-
-        info!(
-            "Configuration saved successfully to {}",
-            self.config_file_path.display()
-        );
-        Ok(())
+        })
     }
 
     /// Check if the config file is writable
@@ -143,85 +271,34 @@ impl ConfigPersistence {
     }
 }
 
+/// The `current` components, each as it is written in the file if it has not
+/// changed since the server loaded it with overrides applied.
+fn as_written<T, F>(current: Vec<T>, loaded: &[T], written: &[T], id: F) -> Vec<T>
+where
+    T: Clone + Serialize,
+    F: Fn(&T) -> &str,
+{
+    let same = |a: &T, b: &T| serde_yaml::to_value(a).ok() == serde_yaml::to_value(b).ok();
+    current
+        .into_iter()
+        .map(|component| {
+            let unchanged = loaded
+                .iter()
+                .any(|other| id(other) == id(&component) && same(other, &component));
+            match written.iter().find(|other| id(other) == id(&component)) {
+                Some(original) if unchanged => original.clone(),
+                _ => component,
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use async_trait::async_trait;
-    use drasi_lib::channels::dispatcher::ChangeDispatcher;
-    use drasi_lib::channels::{ComponentEventSender, ComponentStatus, SubscriptionResponse};
-    use drasi_lib::plugin_core::Source as SourceTrait;
-    use std::collections::HashMap;
+    use crate::testing::MockSource;
     use std::sync::Arc;
     use tempfile::TempDir;
-    use tokio::sync::RwLock;
-
-    // Mock source for testing
-    struct MockSource {
-        id: String,
-        status: Arc<RwLock<ComponentStatus>>,
-    }
-
-    impl MockSource {
-        fn new(id: &str) -> Self {
-            Self {
-                id: id.to_string(),
-                status: Arc::new(RwLock::new(ComponentStatus::Stopped)),
-            }
-        }
-    }
-
-    #[async_trait]
-    impl SourceTrait for MockSource {
-        fn id(&self) -> &str {
-            &self.id
-        }
-
-        fn type_name(&self) -> &str {
-            "mock"
-        }
-
-        fn properties(&self) -> HashMap<String, serde_json::Value> {
-            HashMap::new()
-        }
-
-        async fn start(&self) -> anyhow::Result<()> {
-            *self.status.write().await = ComponentStatus::Running;
-            Ok(())
-        }
-
-        async fn stop(&self) -> anyhow::Result<()> {
-            *self.status.write().await = ComponentStatus::Stopped;
-            Ok(())
-        }
-
-        async fn status(&self) -> ComponentStatus {
-            self.status.read().await.clone()
-        }
-
-        async fn subscribe(
-            &self,
-            settings: drasi_lib::config::SourceSubscriptionSettings,
-        ) -> anyhow::Result<SubscriptionResponse> {
-            use drasi_lib::channels::dispatcher::ChannelChangeDispatcher;
-            let dispatcher =
-                ChannelChangeDispatcher::<drasi_lib::channels::SourceEventWrapper>::new(100);
-            let receiver = dispatcher.create_receiver().await?;
-            Ok(SubscriptionResponse {
-                query_id: settings.query_id,
-                source_id: self.id.clone(),
-                receiver,
-                bootstrap_receiver: None,
-            })
-        }
-
-        fn as_any(&self) -> &dyn std::any::Any {
-            self
-        }
-
-        async fn inject_event_tx(&self, _tx: ComponentEventSender) {
-            // No-op for testing
-        }
-    }
 
     async fn create_test_core() -> Arc<drasi_lib::DrasiLib> {
         use drasi_lib::Query;
@@ -245,6 +322,14 @@ mod tests {
         Arc::new(core)
     }
 
+    fn test_config() -> DrasiServerConfig {
+        DrasiServerConfig {
+            host: crate::api::models::ConfigValue::Static("127.0.0.1".into()),
+            port: crate::api::models::ConfigValue::Static(8080),
+            ..DrasiServerConfig::default()
+        }
+    }
+
     #[tokio::test]
     async fn test_persistence_saves_config() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -255,15 +340,7 @@ mod tests {
 
         let core = create_test_core().await;
 
-        let persistence = ConfigPersistence::new(
-            config_path.clone(),
-            core,
-            "127.0.0.1".to_string(),
-            8080,
-            "info".to_string(),
-            false,
-            false, // persist_index
-        );
+        let persistence = ConfigPersistence::new(config_path.clone(), core, test_config());
 
         // Save should succeed
         persistence.save().await.expect("Save failed");
@@ -296,6 +373,131 @@ mod tests {
         assert_eq!(loaded_config.queries[0].id, "test-query");
     }
 
+    #[tokio::test]
+    async fn test_persistence_writes_registry_components() {
+        use crate::api::models::{ConfigValue, MockSourceConfigDto};
+        use crate::config::SourceConfig;
+        use crate::registry::ComponentKind;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("test-config.yaml");
+        std::fs::write(&config_path, "").expect("Failed to create test file");
+
+        let registry = Arc::new(ComponentRegistry::new());
+        registry
+            .insert_source(SourceConfig::Mock {
+                id: "test-source".to_string(),
+                auto_start: true,
+                bootstrap_provider: None,
+                dispatch_mode: None,
+                dispatch_buffer_capacity: None,
+                lazy: false,
+                config: MockSourceConfigDto {
+                    data_type: ConfigValue::Static("generic".to_string()),
+                    interval_ms: ConfigValue::Static(1000),
+                },
+            })
+            .await;
+        registry
+            .set_disabled(ComponentKind::Source, "test-source", true)
+            .await;
+
+        let persistence =
+            ConfigPersistence::new(config_path.clone(), create_test_core().await, test_config())
+                .with_registry(registry);
+
+        persistence.save().await.expect("Save failed");
+
+        let content = std::fs::read_to_string(&config_path).expect("Failed to read config");
+        let loaded_config: DrasiServerConfig =
+            crate::config::loader::from_yaml_str(&content).expect("Failed to parse saved config");
+
+        assert_eq!(loaded_config.sources.len(), 1);
+        assert_eq!(loaded_config.sources[0].id(), "test-source");
+        assert_eq!(loaded_config.disabled.sources, vec!["test-source"]);
+    }
+
+    #[tokio::test]
+    async fn test_persistence_does_not_write_overridden_values() {
+        use crate::api::models::{ConfigValue, MockSourceConfigDto};
+        use crate::config::SourceConfig;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("test-config.yaml");
+        std::fs::write(&config_path, "").expect("Failed to create test file");
+
+        let source = |id: &str, data_type: &str| SourceConfig::Mock {
+            id: id.to_string(),
+            auto_start: true,
+            bootstrap_provider: None,
+            dispatch_mode: None,
+            dispatch_buffer_capacity: None,
+            lazy: false,
+            config: MockSourceConfigDto {
+                data_type: ConfigValue::Static(data_type.to_string()),
+                interval_ms: ConfigValue::Static(1000),
+            },
+        };
+        let written = DrasiServerConfig {
+            api_keys: vec![ConfigValue::EnvironmentVariable {
+                name: "API_KEY".to_string(),
+                default: Some("dev-key".to_string()),
+            }],
+            sources: vec![source("kept", "generic"), source("changed", "generic")],
+            ..test_config()
+        };
+        // As loaded with DRASI__ overrides
+        let loaded = DrasiServerConfig {
+            api_keys: vec![ConfigValue::Static("overridden-key".to_string())],
+            sources: vec![source("kept", "sensor"), source("changed", "generic")],
+            ..written.clone()
+        };
+
+        let registry = Arc::new(ComponentRegistry::new());
+        registry.insert_source(source("kept", "sensor")).await;
+        // Replaced through the API
+        registry.insert_source(source("changed", "counter")).await;
+
+        let persistence =
+            ConfigPersistence::new(config_path.clone(), create_test_core().await, written)
+                .with_loaded_config(loaded)
+                .with_registry(registry);
+        persistence.save().await.expect("Save failed");
+
+        let content = std::fs::read_to_string(&config_path).expect("Failed to read config");
+        assert!(content.contains("API_KEY"), "{content}");
+        assert!(content.contains("counter"), "{content}");
+        assert!(!content.contains("overridden-key"), "{content}");
+        assert!(!content.contains("sensor"), "{content}");
+    }
+
+    #[tokio::test]
+    async fn test_debounced_saves_are_coalesced_and_flushed() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("test-config.yaml");
+        std::fs::write(&config_path, "").expect("Failed to create test file");
+
+        let persistence = Arc::new(ConfigPersistence::new(
+            config_path.clone(),
+            create_test_core().await,
+            DrasiServerConfig {
+                persistence_debounce_ms: Some(60_000),
+                ..test_config()
+            },
+        ));
+
+        persistence.request_save().await.expect("Request failed");
+        persistence.request_save().await.expect("Request failed");
+
+        // Nothing is written until the window ends or the save is flushed
+        let content = std::fs::read_to_string(&config_path).expect("Failed to read config");
+        assert!(content.is_empty());
+
+        persistence.flush().await.expect("Flush failed");
+        let content = std::fs::read_to_string(&config_path).expect("Failed to read config");
+        assert!(content.contains("persistence_debounce_ms: 60000"));
+    }
+
     #[tokio::test]
     async fn test_persistence_skips_when_disabled() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -306,11 +508,10 @@ mod tests {
         let persistence = ConfigPersistence::new(
             config_path.clone(),
             core,
-            "127.0.0.1".to_string(),
-            8080,
-            "info".to_string(),
-            true,  // disable_persistence = true
-            false, // persist_index
+            DrasiServerConfig {
+                disable_persistence: true,
+                ..test_config()
+            },
         );
 
         // Save should succeed but not write anything
@@ -330,15 +531,7 @@ mod tests {
 
         let core = create_test_core().await;
 
-        let persistence = ConfigPersistence::new(
-            config_path.clone(),
-            core,
-            "127.0.0.1".to_string(),
-            8080,
-            "info".to_string(),
-            false,
-            false, // persist_index
-        );
+        let persistence = ConfigPersistence::new(config_path.clone(), core, test_config());
 
         // Save should succeed
         persistence.save().await.expect("Save failed");
@@ -364,30 +557,15 @@ mod tests {
 
         let core = create_test_core().await;
 
-        let persistence = ConfigPersistence::new(
-            config_path.clone(),
-            core,
-            "127.0.0.1".to_string(),
-            8080,
-            "info".to_string(),
-            false,
-            false, // persist_index
-        );
+        let persistence = ConfigPersistence::new(config_path.clone(), core, test_config());
 
         // Should be writable
         assert!(persistence.is_writable());
 
         // Test non-existent file
         let non_existent = temp_dir.path().join("does-not-exist.yaml");
-        let persistence_non_existent = ConfigPersistence::new(
-            non_existent,
-            create_test_core().await,
-            "127.0.0.1".to_string(),
-            8080,
-            "info".to_string(),
-            false,
-            false, // persist_index
-        );
+        let persistence_non_existent =
+            ConfigPersistence::new(non_existent, create_test_core().await, test_config());
 
         // Should not be writable
         assert!(!persistence_non_existent.is_writable());
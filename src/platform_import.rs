@@ -0,0 +1,218 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Import of the components of a running Drasi platform instance.
+//!
+//! Used by `drasi-server import-platform`. The platform's management API
+//! (`drasi-api`) lists the Sources, ContinuousQueries and Reactions it runs;
+//! each is translated like the matching manifest in
+//! [`crate::config::manifests`]. The API is reached either at a URL or, given
+//! a kube context, through `kubectl port-forward` to the `drasi-api` service.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::{Child, Command};
+
+use crate::config::manifests::ManifestImport;
+
+/// Namespace the platform is installed into by default
+pub const DEFAULT_NAMESPACE: &str = "drasi-system";
+const API_SERVICE: &str = "service/drasi-api";
+const API_PORT: u16 = 8080;
+const PORT_FORWARD_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// API collections to import, with the resource kind they hold
+const COLLECTIONS: [(&str, &str); 3] = [
+    ("sources", "Source"),
+    ("continuousQueries", "ContinuousQuery"),
+    ("reactions", "Reaction"),
+];
+
+/// A `kubectl port-forward` to the platform API, stopped when dropped.
+pub struct PortForward {
+    _child: Child,
+    pub base_url: String,
+}
+
+impl PortForward {
+    /// Forward a free local port to the `drasi-api` service in `namespace` of
+    /// the cluster selected by `context`, and wait until it accepts
+    /// connections.
+    pub async fn open(context: &str, namespace: &str) -> Result<Self> {
+        let local_port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        let mut child = Command::new("kubectl")
+            .args(["--context", context, "--namespace", namespace])
+            .args(["port-forward", API_SERVICE])
+            .arg(format!("{local_port}:{API_PORT}"))
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to run kubectl; is it installed and on PATH?")?;
+
+        let deadline = tokio::time::Instant::now() + PORT_FORWARD_TIMEOUT;
+        loop {
+            if let Some(status) = child.try_wait()? {
+                let output = child.wait_with_output().await?;
+                return Err(anyhow!(
+                    "kubectl port-forward exited with {status}: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            if tokio::net::TcpStream::connect(("127.0.0.1", local_port))
+                .await
+                .is_ok()
+            {
+                break;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(anyhow!(
+                    "Timed out waiting for kubectl port-forward to {API_SERVICE} in '{namespace}'"
+                ));
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+
+        Ok(Self {
+            _child: child,
+            base_url: format!("http://127.0.0.1:{local_port}"),
+        })
+    }
+}
+
+/// Fetch every Source, ContinuousQuery and Reaction from the platform API at
+/// `base_url` and translate them.
+pub async fn import_platform(client: &reqwest::Client, base_url: &str) -> Result<ManifestImport> {
+    let mut import = ManifestImport::default();
+    for (collection, kind) in COLLECTIONS {
+        for resource in fetch_collection(client, base_url, collection).await? {
+            let name = resource
+                .get("id")
+                .or_else(|| resource.get("name"))
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("{kind} without an id in /v1/{collection}"))?
+                .to_string();
+            let spec = resource.get("spec").cloned().unwrap_or(Value::Null);
+            import
+                .add_resource(kind, &name, spec)
+                .with_context(|| format!("Failed to import {kind} '{name}'"))?;
+        }
+    }
+    Ok(import)
+}
+
+async fn fetch_collection(
+    client: &reqwest::Client,
+    base_url: &str,
+    collection: &str,
+) -> Result<Vec<Value>> {
+    let url = format!("{}/v1/{collection}", base_url.trim_end_matches('/'));
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("{url} returned {status}"));
+    }
+    response
+        .json()
+        .await
+        .with_context(|| format!("Unexpected response from {url}"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mount(server: &MockServer, route: &str, body: Value) {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_imports_resources_from_platform_api() {
+        let server = MockServer::start().await;
+        mount(
+            &server,
+            "/v1/sources",
+            json!([{
+                "id": "orders-db",
+                "spec": {"kind": "PostgreSQL", "properties": {
+                    "host": "postgres", "port": 5432, "user": "drasi", "password": "secret",
+                    "database": "orders", "tables": ["public.orders"]
+                }},
+                "status": {"available": true}
+            }]),
+        )
+        .await;
+        mount(
+            &server,
+            "/v1/continuousQueries",
+            json!([{
+                "id": "large-orders",
+                "spec": {
+                    "query": "MATCH (o:orders) RETURN o.id",
+                    "sources": {"subscriptions": [{"id": "orders-db"}]}
+                }
+            }]),
+        )
+        .await;
+        mount(
+            &server,
+            "/v1/reactions",
+            json!([{"id": "order-log", "spec": {"kind": "Debug", "queries": {"large-orders": null}}}]),
+        )
+        .await;
+
+        let import = import_platform(&reqwest::Client::new(), &server.uri())
+            .await
+            .unwrap();
+
+        assert_eq!(import.sources[0].id(), "orders-db");
+        assert_eq!(import.queries[0].id, "large-orders");
+        assert_eq!(import.queries[0].sources[0].source_id, "orders-db");
+        assert_eq!(import.reactions[0].id(), "order-log");
+        assert_eq!(import.reactions[0].queries(), ["large-orders".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_platform_resource_names_the_resource() {
+        let server = MockServer::start().await;
+        mount(
+            &server,
+            "/v1/sources",
+            json!([{"id": "cosmos", "spec": {"kind": "CosmosGremlin"}}]),
+        )
+        .await;
+
+        let err = import_platform(&reqwest::Client::new(), &server.uri())
+            .await
+            .unwrap_err();
+
+        assert!(format!("{err:#}").contains("Source 'cosmos'"), "{err:#}");
+    }
+}
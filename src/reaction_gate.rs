@@ -0,0 +1,116 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deferred auto-start for reactions that set `wait_for_queries_ms`.
+//!
+//! A reaction that starts while its queries are still bootstrapping can see a
+//! burst of early diffs, or miss them entirely. Gated reactions are created
+//! with auto-start turned off and started here once every subscribed query
+//! reports `Running`, or when the timeout expires, whichever comes first.
+
+use drasi_lib::channels::ComponentStatus;
+use log::{info, warn};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Whether every query in `queries` is listed as `Running`.
+fn queries_ready(statuses: &[(String, ComponentStatus)], queries: &[String]) -> bool {
+    queries.iter().all(|query_id| {
+        statuses
+            .iter()
+            .any(|(id, status)| id == query_id && matches!(status, ComponentStatus::Running))
+    })
+}
+
+/// Start a reaction in the background once its subscribed queries are running.
+///
+/// If the queries are not ready within `timeout`, the reaction is started
+/// anyway and a warning is logged. Nothing is started if the reaction has been
+/// disabled in the meantime.
+pub fn spawn_gated_start(
+    core: Arc<drasi_lib::DrasiLib>,
+    registry: Arc<ComponentRegistry>,
+    reaction_id: String,
+    queries: Vec<String>,
+    timeout: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        info!(
+            "Reaction '{reaction_id}' waiting up to {}ms for queries {queries:?}",
+            timeout.as_millis()
+        );
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let statuses = core.list_queries().await.unwrap_or_default();
+            if queries_ready(&statuses, &queries) {
+                break;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                warn!(
+                    "Queries for reaction '{reaction_id}' not running after {}ms, starting anyway",
+                    timeout.as_millis()
+                );
+                break;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        if registry
+            .is_disabled(ComponentKind::Reaction, &reaction_id)
+            .await
+        {
+            info!("Reaction '{reaction_id}' was disabled while waiting, not starting it");
+            return;
+        }
+        match core.start_reaction(&reaction_id).await {
+            Ok(_) => {
+                registry
+                    .mark_started(ComponentKind::Reaction, &reaction_id)
+                    .await;
+                info!("Reaction '{reaction_id}' started");
+            }
+            Err(e) => warn!("Failed to start gated reaction '{reaction_id}': {e}"),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses() -> Vec<(String, ComponentStatus)> {
+        vec![
+            ("q1".to_string(), ComponentStatus::Running),
+            ("q2".to_string(), ComponentStatus::Starting),
+        ]
+    }
+
+    #[test]
+    fn test_queries_ready_requires_all_running() {
+        assert!(queries_ready(&statuses(), &["q1".to_string()]));
+        assert!(!queries_ready(
+            &statuses(),
+            &["q1".to_string(), "q2".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_queries_ready_treats_missing_query_as_not_ready() {
+        assert!(!queries_ready(&statuses(), &["missing".to_string()]));
+    }
+}
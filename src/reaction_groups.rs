@@ -0,0 +1,217 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Primary/backup failover for reactions that share a `group`.
+//!
+//! Members of a group have the same kind and queries, and are created with
+//! auto-start turned off. The supervisor keeps exactly one member running so
+//! each diff is delivered once. When no member is running, the first stopped
+//! member in configuration order is started, so the first member acts as the
+//! primary and the others as backups in order. A failed member is not
+//! restarted. If a member is started through the API while another is
+//! running, the later of the two in configuration order is stopped. A member
+//! stopped through the API is left stopped until it is started or enabled
+//! again, so stopping every member stops the group.
+
+use drasi_lib::channels::ComponentStatus;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct GroupAction {
+    start: Option<String>,
+    stop: Vec<String>,
+}
+
+/// What to do for a group, given its members' statuses in priority order.
+fn plan(members: &[(String, ComponentStatus)]) -> GroupAction {
+    let mut running = members
+        .iter()
+        .filter(|(_, status)| {
+            matches!(status, ComponentStatus::Running | ComponentStatus::Starting)
+        })
+        .map(|(id, _)| id.clone());
+    match running.next() {
+        Some(_) => GroupAction {
+            start: None,
+            stop: running.collect(),
+        },
+        None => GroupAction {
+            start: members
+                .iter()
+                .find(|(_, status)| matches!(status, ComponentStatus::Stopped))
+                .map(|(id, _)| id.clone()),
+            stop: Vec::new(),
+        },
+    }
+}
+
+/// Members of each group in configuration order. Disabled members, members
+/// with `auto_start: false` and members stopped through the API do not take
+/// part.
+async fn groups(registry: &ComponentRegistry) -> Vec<(String, Vec<String>)> {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for config in registry.reaction_configs().await {
+        let Some(group) = config.group() else {
+            continue;
+        };
+        if !config.auto_start()
+            || registry
+                .is_disabled(ComponentKind::Reaction, config.id())
+                .await
+            || registry
+                .is_stopped_by_api(ComponentKind::Reaction, config.id())
+                .await
+        {
+            continue;
+        }
+        match groups.iter_mut().find(|(name, _)| name == group) {
+            Some((_, members)) => members.push(config.id().to_string()),
+            None => groups.push((group.to_string(), vec![config.id().to_string()])),
+        }
+    }
+    groups
+}
+
+/// Keep one member of every reaction group running, in the background, until
+/// `shutdown` is cancelled.
+pub fn spawn_group_supervisor(
+    core: Arc<drasi_lib::DrasiLib>,
+    registry: Arc<ComponentRegistry>,
+    shutdown: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let groups = groups(&registry).await;
+            if !groups.is_empty() {
+                let statuses: HashMap<String, ComponentStatus> = core
+                    .list_reactions()
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect();
+                for (group, member_ids) in groups {
+                    let members: Vec<(String, ComponentStatus)> = member_ids
+                        .into_iter()
+                        .filter_map(|id| statuses.get(&id).map(|s| (id, s.clone())))
+                        .collect();
+                    let action = plan(&members);
+                    for id in action.stop {
+                        match core.stop_reaction(&id).await {
+                            Ok(_) => info!("Reaction group '{group}': stopped standby '{id}'"),
+                            Err(e) => warn!("Reaction group '{group}': failed to stop '{id}': {e}"),
+                        }
+                    }
+                    if let Some(id) = action.start {
+                        match core.start_reaction(&id).await {
+                            Ok(_) => {
+                                registry.mark_started(ComponentKind::Reaction, &id).await;
+                                info!("Reaction group '{group}': '{id}' is now active");
+                            }
+                            Err(e) => {
+                                warn!("Reaction group '{group}': failed to start '{id}': {e}")
+                            }
+                        }
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members(statuses: &[(&str, ComponentStatus)]) -> Vec<(String, ComponentStatus)> {
+        statuses
+            .iter()
+            .map(|(id, status)| (id.to_string(), status.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_first_stopped_member_is_started() {
+        let action = plan(&members(&[
+            ("primary", ComponentStatus::Error),
+            ("backup", ComponentStatus::Stopped),
+        ]));
+
+        assert_eq!(action.start.as_deref(), Some("backup"));
+        assert!(action.stop.is_empty());
+    }
+
+    #[test]
+    fn test_later_running_members_are_stopped() {
+        let action = plan(&members(&[
+            ("primary", ComponentStatus::Running),
+            ("backup", ComponentStatus::Running),
+        ]));
+
+        assert_eq!(
+            action,
+            GroupAction {
+                start: None,
+                stop: vec!["backup".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_nothing_to_do_with_one_running_member() {
+        let action = plan(&members(&[
+            ("primary", ComponentStatus::Stopped),
+            ("backup", ComponentStatus::Running),
+        ]));
+
+        assert_eq!(action, GroupAction::default());
+    }
+
+    #[tokio::test]
+    async fn test_members_stopped_through_the_api_are_left_out() {
+        let registry = ComponentRegistry::new();
+        for id in ["primary", "backup"] {
+            registry
+                .insert_reaction(crate::config::ReactionConfig::Log {
+                    id: id.to_string(),
+                    queries: vec!["q1".to_string()],
+                    auto_start: true,
+                    wait_for_queries_ms: None,
+                    group: Some("webhooks".to_string()),
+                    config: Default::default(),
+                })
+                .await;
+        }
+        registry
+            .set_stopped_by_api(ComponentKind::Reaction, "primary", true)
+            .await;
+
+        assert_eq!(
+            groups(&registry).await,
+            vec![("webhooks".to_string(), vec!["backup".to_string()])]
+        );
+    }
+}
@@ -0,0 +1,611 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reconcile mode (`reconcile: true`).
+//!
+//! The config file is treated as the desired state. A background loop reloads
+//! it on an interval and converges the running server to it: components missing
+//! from the runtime are created, changed definitions are replaced, components
+//! that are not in the file are removed, the `disabled` set is applied, and
+//! components that should be running but were stopped are started again. API
+//! changes are therefore temporary; edit the file to make them stick.
+//!
+//! Replacing a source or query restarts what depends on it, as the `PUT`
+//! endpoints do. A component that cannot be changed is logged and retried on
+//! the next pass without holding up the others.
+
+use anyhow::Result;
+use drasi_lib::channels::ComponentStatus;
+use drasi_lib::{DrasiLib, QueryConfig};
+use log::{debug, info, warn};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+use crate::api::handlers::{replace_query_instance, replace_source_instance};
+use crate::config::loader::{load_config_file_with, CliOverrides};
+use crate::config::{DrasiServerConfig, ReactionConfig, SourceConfig};
+use crate::factories::{create_reaction, create_source};
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+/// Interval between passes when `reconcile_interval_secs` is not set
+pub const DEFAULT_RECONCILE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Changes needed to bring one kind of component in line with the config.
+#[derive(Debug, Default, PartialEq)]
+struct Plan {
+    create: Vec<String>,
+    replace: Vec<String>,
+    remove: Vec<String>,
+}
+
+/// Compare desired definitions with the running ones.
+///
+/// `actual` maps each running ID to its known definition, or `None` when the
+/// server has no record of how it was defined; those are always replaced.
+fn plan(
+    desired: &[(String, serde_json::Value)],
+    actual: &HashMap<String, Option<serde_json::Value>>,
+) -> Plan {
+    let mut plan = Plan::default();
+    for (id, definition) in desired {
+        match actual.get(id) {
+            None => plan.create.push(id.clone()),
+            Some(current) if current.as_ref() != Some(definition) => {
+                plan.replace.push(id.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    let mut remove: Vec<String> = actual
+        .keys()
+        .filter(|id| !desired.iter().any(|(desired_id, _)| desired_id == *id))
+        .cloned()
+        .collect();
+    remove.sort();
+    plan.remove = remove;
+    plan
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Create,
+    Replace,
+    Remove,
+}
+
+impl Action {
+    fn verb(self) -> &'static str {
+        match self {
+            Action::Create => "create",
+            Action::Replace => "replace",
+            Action::Remove => "remove",
+        }
+    }
+
+    fn past(self) -> &'static str {
+        match self {
+            Action::Create => "created",
+            Action::Replace => "replaced",
+            Action::Remove => "removed",
+        }
+    }
+}
+
+/// Record a change that was applied, or log why it failed. Returns whether
+/// it was applied.
+fn record(
+    changes: &mut Vec<String>,
+    action: Action,
+    kind: ComponentKind,
+    id: &str,
+    result: Result<()>,
+) -> bool {
+    match result {
+        Ok(()) => {
+            changes.push(format!("{} {} '{id}'", action.past(), kind.label()));
+            true
+        }
+        Err(e) => {
+            warn!(
+                "Reconcile could not {} {} '{id}': {e:#}",
+                action.verb(),
+                kind.label()
+            );
+            false
+        }
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+}
+
+/// The query definition as it is handed to DrasiLib: disabled queries never
+/// auto-start.
+fn effective_query(query: &QueryConfig, config: &DrasiServerConfig) -> QueryConfig {
+    let mut query = query.clone();
+    if config.disabled.queries.contains(&query.id) {
+        query.auto_start = false;
+    }
+    query
+}
+
+pub struct Reconciler {
+    core: Arc<DrasiLib>,
+    registry: Arc<ComponentRegistry>,
+    config_path: PathBuf,
+    interval: Duration,
+    /// `--set` and `--strict`, applied on every reload as at startup
+    overrides: CliOverrides,
+    /// Reactions waiting for their queries before they start
+    gated: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+}
+
+impl Reconciler {
+    pub fn new(
+        core: Arc<DrasiLib>,
+        registry: Arc<ComponentRegistry>,
+        config_path: PathBuf,
+        interval: Duration,
+        overrides: CliOverrides,
+    ) -> Self {
+        Self {
+            core,
+            registry,
+            config_path,
+            interval,
+            overrides,
+            gated: Mutex::default(),
+        }
+    }
+
+    /// Run reconcile passes in the background until `shutdown` is cancelled.
+    pub fn spawn(self, shutdown: CancellationToken) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            info!(
+                "Reconcile mode enabled: converging to {} every {}s",
+                self.config_path.display(),
+                self.interval.as_secs()
+            );
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            // The first tick fires immediately; startup already matches the file
+            ticker.tick().await;
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                match self.reconcile_once().await {
+                    Ok(changes) if !changes.is_empty() => {
+                        info!("Reconciled with config file: {}", changes.join(", "))
+                    }
+                    Ok(_) => debug!("Reconcile pass: runtime matches config file"),
+                    Err(e) => warn!("Reconcile pass failed: {e}"),
+                }
+            }
+        })
+    }
+
+    /// Converge the runtime to the config file once, returning what changed.
+    pub async fn reconcile_once(&self) -> Result<Vec<String>> {
+        let config = load_config_file_with(&self.config_path, &self.overrides)?;
+        config.validate()?;
+        let mut changes = Vec::new();
+
+        let source_plan = self.plan_sources(&config).await?;
+        let query_plan = self.plan_queries(&config).await?;
+        let reaction_plan = self.plan_reactions(&config).await?;
+
+        // Replacements start components according to the disabled set
+        self.apply_disabled(&config, &mut changes).await;
+
+        // Remove dependents before what they depend on
+        let removals = [
+            (ComponentKind::Reaction, &reaction_plan.remove),
+            (ComponentKind::Query, &query_plan.remove),
+            (ComponentKind::Source, &source_plan.remove),
+        ];
+        for (kind, ids) in removals {
+            for id in ids {
+                let result = self.remove(kind, id).await;
+                if record(&mut changes, Action::Remove, kind, id, result) {
+                    self.registry.remove(kind, id).await;
+                }
+            }
+        }
+
+        // Create and replace in dependency order
+        for source in &config.sources {
+            let id = source.id();
+            let action = if source_plan.replace.iter().any(|r| r == id) {
+                Action::Replace
+            } else if source_plan.create.iter().any(|c| c == id) {
+                Action::Create
+            } else {
+                continue;
+            };
+            let result = self.put_source(source, action).await;
+            record(&mut changes, action, ComponentKind::Source, id, result);
+        }
+        for query in &config.queries {
+            let id = &query.id;
+            let action = if query_plan.replace.contains(id) {
+                Action::Replace
+            } else if query_plan.create.contains(id) {
+                Action::Create
+            } else {
+                continue;
+            };
+            let result = self.put_query(query, &config, action).await;
+            record(&mut changes, action, ComponentKind::Query, id, result);
+        }
+        for reaction in &config.reactions {
+            let id = reaction.id();
+            let action = if reaction_plan.replace.iter().any(|r| r == id) {
+                Action::Replace
+            } else if reaction_plan.create.iter().any(|c| c == id) {
+                Action::Create
+            } else {
+                continue;
+            };
+            let result = self.put_reaction(reaction, action).await;
+            record(&mut changes, action, ComponentKind::Reaction, id, result);
+        }
+
+        self.start_stopped(&config, &mut changes).await;
+
+        Ok(changes)
+    }
+
+    /// Create a source, or replace it the way `PUT /sources/{id}` does so
+    /// the queries reading from it are restarted. A source that failed to
+    /// build is not in DrasiLib, so it is always replaced.
+    async fn put_source(&self, source: &SourceConfig, action: Action) -> Result<()> {
+        let id = source.id();
+        // A new source is started by start_stopped; a replaced one as before
+        let mut instance_config = source.clone();
+        instance_config.set_auto_start(false);
+        let instance = create_source(instance_config).await?;
+        let failed = self
+            .registry
+            .build_error(ComponentKind::Source, id)
+            .await
+            .is_some();
+        if action == Action::Replace || failed {
+            replace_source_instance(&self.core, &self.registry, source.clone(), instance).await?;
+            return Ok(());
+        }
+        self.core.add_source(instance).await?;
+        self.registry.insert_source(source.clone()).await;
+        self.registry.reset_running(ComponentKind::Source, id).await;
+        Ok(())
+    }
+
+    /// Create a query, or replace it the way `PUT /queries/{id}` does so the
+    /// reactions subscribed to it are restarted.
+    async fn put_query(
+        &self,
+        query: &QueryConfig,
+        config: &DrasiServerConfig,
+        action: Action,
+    ) -> Result<()> {
+        if action == Action::Replace {
+            replace_query_instance(&self.core, &self.registry, query.clone()).await?;
+            return Ok(());
+        }
+        let id = &query.id;
+        let effective = effective_query(query, config);
+        if query.auto_start && !effective.auto_start {
+            self.registry.suppress_query_auto_start(id).await;
+        }
+        self.core.add_query(effective).await?;
+        self.registry.reset_running(ComponentKind::Query, id).await;
+        Ok(())
+    }
+
+    async fn put_reaction(&self, reaction: &ReactionConfig, action: Action) -> Result<()> {
+        let id = reaction.id();
+        let mut instance_config = reaction.clone();
+        instance_config.set_auto_start(false);
+        let instance = create_reaction(instance_config)?;
+        if action == Action::Replace {
+            self.core.remove_reaction(id).await?;
+        }
+        self.core.add_reaction(instance).await?;
+        self.registry.insert_reaction(reaction.clone()).await;
+        self.registry
+            .reset_running(ComponentKind::Reaction, id)
+            .await;
+        Ok(())
+    }
+
+    async fn plan_sources(&self, config: &DrasiServerConfig) -> Result<Plan> {
+        let desired: Vec<_> = config
+            .sources
+            .iter()
+            .map(|s| (s.id().to_string(), to_value(s)))
+            .collect();
+        let mut actual = HashMap::new();
+        for (id, _) in self.core.list_sources().await? {
+            let current = self.registry.get_source(&id).await.map(|c| to_value(&c));
+            actual.insert(id, current);
+        }
+        Ok(plan(&desired, &actual))
+    }
+
+    async fn plan_queries(&self, config: &DrasiServerConfig) -> Result<Plan> {
+        let desired: Vec<_> = config
+            .queries
+            .iter()
+            .map(|q| (q.id.clone(), to_value(&effective_query(q, config))))
+            .collect();
+        let mut actual = HashMap::new();
+        for (id, _) in self.core.list_queries().await? {
+            let current = self
+                .core
+                .get_query_config(&id)
+                .await
+                .ok()
+                .map(|c| to_value(&c));
+            actual.insert(id, current);
+        }
+        Ok(plan(&desired, &actual))
+    }
+
+    async fn plan_reactions(&self, config: &DrasiServerConfig) -> Result<Plan> {
+        let desired: Vec<_> = config
+            .reactions
+            .iter()
+            .map(|r| (r.id().to_string(), to_value(r)))
+            .collect();
+        let mut actual = HashMap::new();
+        for (id, _) in self.core.list_reactions().await? {
+            let current = self.registry.get_reaction(&id).await.map(|c| to_value(&c));
+            actual.insert(id, current);
+        }
+        Ok(plan(&desired, &actual))
+    }
+
+    /// Make the registry's disabled set match the file, stopping components
+    /// that have become disabled.
+    async fn apply_disabled(&self, config: &DrasiServerConfig, changes: &mut Vec<String>) {
+        let desired: [(ComponentKind, Vec<&str>, &Vec<String>); 3] = [
+            (
+                ComponentKind::Source,
+                config.sources.iter().map(SourceConfig::id).collect(),
+                &config.disabled.sources,
+            ),
+            (
+                ComponentKind::Query,
+                config.queries.iter().map(|q| q.id.as_str()).collect(),
+                &config.disabled.queries,
+            ),
+            (
+                ComponentKind::Reaction,
+                config.reactions.iter().map(ReactionConfig::id).collect(),
+                &config.disabled.reactions,
+            ),
+        ];
+        for (kind, ids, disabled) in desired {
+            for id in ids {
+                let want = disabled.iter().any(|d| d == id);
+                if self.registry.is_disabled(kind, id).await == want {
+                    continue;
+                }
+                self.registry.set_disabled(kind, id, want).await;
+                changes.push(format!(
+                    "{} {} '{id}'",
+                    if want { "disabled" } else { "enabled" },
+                    kind.label()
+                ));
+            }
+        }
+    }
+
+    /// Start components that should be running and stop disabled ones that are.
+    async fn start_stopped(&self, config: &DrasiServerConfig, changes: &mut Vec<String>) {
+        let sources: HashMap<_, _> = self
+            .core
+            .list_sources()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let queries: HashMap<_, _> = self
+            .core
+            .list_queries()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let reactions: HashMap<_, _> = self
+            .core
+            .list_reactions()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        let mut targets = Vec::new();
+        for s in &config.sources {
+            let status = sources.get(s.id());
+            // Lazy sources are started by lazy_sources once a query subscribes
+            let auto_start = s.auto_start() && !s.lazy();
+            targets.push((ComponentKind::Source, s.id(), auto_start, None, status));
+        }
+        for q in &config.queries {
+            let status = queries.get(&q.id);
+            let id = q.id.as_str();
+            targets.push((ComponentKind::Query, id, q.auto_start, None, status));
+        }
+        for r in &config.reactions {
+            let status = reactions.get(r.id());
+            // Group members are started by the group supervisor
+            let auto_start = r.auto_start() && r.group().is_none();
+            let gate = r.wait_for_queries_ms().map(|ms| (r.queries(), ms));
+            targets.push((ComponentKind::Reaction, r.id(), auto_start, gate, status));
+        }
+
+        for (kind, id, auto_start, gate, status) in targets {
+            let Some(status) = status else { continue };
+            let disabled = self.registry.is_disabled(kind, id).await;
+            let running = matches!(
+                status,
+                ComponentStatus::Running | ComponentStatus::Starting
+            );
+            let result = if disabled && running {
+                self.stop(kind, id).await.map(|_| "stopped")
+            } else if !disabled && auto_start && matches!(status, ComponentStatus::Stopped) {
+                if let Some((queries, wait_ms)) = gate {
+                    // Started in the background once its queries are running
+                    if self.start_gated(id, queries, wait_ms).await {
+                        changes.push(format!("starting reaction '{id}' once its queries run"));
+                    }
+                    continue;
+                }
+                self.start(kind, id).await.map(|_| "started")
+            } else {
+                continue;
+            };
+            match result {
+                Ok(action) => {
+                    if action == "started" {
+                        self.registry.mark_started(kind, id).await;
+                    } else {
+                        self.registry.reset_running(kind, id).await;
+                    }
+                    changes.push(format!("{action} {} '{id}'", kind.label()));
+                }
+                Err(e) => warn!(
+                    "Reconcile could not change state of {} '{id}': {e}",
+                    kind.label()
+                ),
+            }
+        }
+    }
+
+    async fn start(&self, kind: ComponentKind, id: &str) -> Result<()> {
+        match kind {
+            ComponentKind::Source => self.core.start_source(id).await?,
+            ComponentKind::Query => self.core.start_query(id).await?,
+            ComponentKind::Reaction => self.core.start_reaction(id).await?,
+        }
+        Ok(())
+    }
+
+    /// Start a reaction once its queries are running, unless an earlier pass
+    /// left one waiting already. Returns whether a start was scheduled.
+    async fn start_gated(&self, id: &str, queries: &[String], wait_ms: u64) -> bool {
+        let mut gated = self.gated.lock().await;
+        gated.retain(|_, task| !task.is_finished());
+        if gated.contains_key(id) {
+            return false;
+        }
+        let task = crate::reaction_gate::spawn_gated_start(
+            self.core.clone(),
+            self.registry.clone(),
+            id.to_string(),
+            queries.to_vec(),
+            Duration::from_millis(wait_ms),
+        );
+        gated.insert(id.to_string(), task);
+        true
+    }
+
+    async fn stop(&self, kind: ComponentKind, id: &str) -> Result<()> {
+        match kind {
+            ComponentKind::Source => self.core.stop_source(id).await?,
+            ComponentKind::Query => self.core.stop_query(id).await?,
+            ComponentKind::Reaction => self.core.stop_reaction(id).await?,
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, kind: ComponentKind, id: &str) -> Result<()> {
+        match kind {
+            ComponentKind::Source => self.core.remove_source(id).await?,
+            ComponentKind::Query => self.core.remove_query(id).await?,
+            ComponentKind::Reaction => self.core.remove_reaction(id).await?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plan_creates_replaces_and_removes() {
+        let desired = vec![
+            ("new".to_string(), json!({"id": "new"})),
+            ("changed".to_string(), json!({"id": "changed", "v": 2})),
+            ("same".to_string(), json!({"id": "same"})),
+        ];
+        let actual = HashMap::from([
+            ("changed".to_string(), Some(json!({"id": "changed", "v": 1}))),
+            ("same".to_string(), Some(json!({"id": "same"}))),
+            ("extra".to_string(), Some(json!({"id": "extra"}))),
+        ]);
+
+        let plan = plan(&desired, &actual);
+
+        assert_eq!(plan.create, vec!["new"]);
+        assert_eq!(plan.replace, vec!["changed"]);
+        assert_eq!(plan.remove, vec!["extra"]);
+    }
+
+    #[test]
+    fn test_plan_replaces_components_with_unknown_definition() {
+        let desired = vec![("programmatic".to_string(), json!({"id": "programmatic"}))];
+        let actual = HashMap::from([("programmatic".to_string(), None)]);
+
+        let plan = plan(&desired, &actual);
+
+        assert_eq!(plan.replace, vec!["programmatic"]);
+        assert!(plan.create.is_empty());
+        assert!(plan.remove.is_empty());
+    }
+    #[test]
+    fn test_failed_change_is_logged_and_skipped() {
+        let mut changes = Vec::new();
+
+        let created = record(
+            &mut changes,
+            Action::Create,
+            ComponentKind::Source,
+            "orders",
+            Ok(()),
+        );
+        let replaced = record(
+            &mut changes,
+            Action::Replace,
+            ComponentKind::Query,
+            "open-orders",
+            Err(anyhow::anyhow!("invalid query")),
+        );
+
+        assert!(created);
+        assert!(!replaced);
+        assert_eq!(changes, vec!["created source 'orders'"]);
+    }
+}
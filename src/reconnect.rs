@@ -0,0 +1,313 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Restarts for sources with a `reconnect` policy.
+//!
+//! A source that loses its upstream connection ends up in `Error` and stays
+//! there. The supervisor restarts sources with a policy after the policy's
+//! delay, backing off between attempts, and records every attempt in the
+//! source's status history. Stopping a source ends its attempts, and so does
+//! reaching `max_attempts`.
+//!
+//! A restarted source counts as recovered only once it has stayed `Running`
+//! for `max_delay_ms`. A source that fails again before that carries on
+//! where its attempts left off, so one that keeps flapping still backs off
+//! and reaches `max_attempts`.
+
+use drasi_lib::channels::ComponentStatus;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::api::models::ReconnectPolicyDto;
+use crate::registry::{ComponentKind, ComponentRegistry};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Attempts made for a source since it last failed
+#[derive(Debug)]
+struct Retry {
+    attempts: u32,
+    next_at: Instant,
+    gave_up: bool,
+    /// When the source was last seen running again, if it still is
+    running_since: Option<Instant>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    Wait,
+    Restart(u32),
+    GiveUp(u32),
+    Reconnected(u32),
+}
+
+/// What to do for a source, given its status and the attempts made so far.
+fn step(
+    policy: &ReconnectPolicyDto,
+    retry: &mut Option<Retry>,
+    status: &ComponentStatus,
+    now: Instant,
+) -> Step {
+    match status {
+        ComponentStatus::Error => {
+            let Some(state) = retry else {
+                *retry = Some(Retry {
+                    attempts: 0,
+                    next_at: now + policy.delay(0),
+                    gave_up: false,
+                    running_since: None,
+                });
+                return Step::Wait;
+            };
+            if state.running_since.take().is_some() {
+                // Failed again before it was stable, so keep backing off
+                state.next_at = now + policy.delay(state.attempts);
+                return Step::Wait;
+            }
+            if state.gave_up || now < state.next_at {
+                return Step::Wait;
+            }
+            if policy.max_attempts.is_some_and(|max| state.attempts >= max) {
+                state.gave_up = true;
+                return Step::GiveUp(state.attempts);
+            }
+            state.attempts += 1;
+            state.next_at = now + policy.delay(state.attempts);
+            Step::Restart(state.attempts)
+        }
+        ComponentStatus::Running => {
+            let Some(state) = retry else {
+                return Step::Wait;
+            };
+            match state.running_since {
+                None => {
+                    state.running_since = Some(now);
+                    if state.attempts > 0 {
+                        return Step::Reconnected(state.attempts);
+                    }
+                }
+                Some(since) => {
+                    if now.duration_since(since) >= Duration::from_millis(policy.max_delay_ms) {
+                        *retry = None;
+                    }
+                }
+            }
+            Step::Wait
+        }
+        ComponentStatus::Stopped => {
+            *retry = None;
+            Step::Wait
+        }
+        _ => Step::Wait,
+    }
+}
+
+/// Sources with a reconnect policy that are not disabled
+async fn policies(registry: &ComponentRegistry) -> Vec<(String, ReconnectPolicyDto)> {
+    let mut policies = Vec::new();
+    for config in registry.source_configs().await {
+        let Some(policy) = config.reconnect() else {
+            continue;
+        };
+        if registry
+            .is_disabled(ComponentKind::Source, config.id())
+            .await
+        {
+            continue;
+        }
+        policies.push((config.id().to_string(), policy.clone()));
+    }
+    policies
+}
+
+/// Restart failed sources according to their reconnect policy, in the
+/// background, until `shutdown` is cancelled.
+pub fn spawn_reconnect_supervisor(
+    core: Arc<drasi_lib::DrasiLib>,
+    registry: Arc<ComponentRegistry>,
+    shutdown: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut retries: HashMap<String, Option<Retry>> = HashMap::new();
+        loop {
+            let policies = policies(&registry).await;
+            retries.retain(|id, _| policies.iter().any(|(other, _)| other == id));
+            if !policies.is_empty() {
+                let statuses: HashMap<String, ComponentStatus> = core
+                    .list_sources()
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect();
+                for (id, policy) in policies {
+                    let Some(status) = statuses.get(&id) else {
+                        continue;
+                    };
+                    let retry = retries.entry(id.clone()).or_default();
+                    match step(&policy, retry, status, Instant::now()) {
+                        Step::Wait => {}
+                        Step::Restart(attempt) => {
+                            info!("Restarting source '{id}' (attempt {attempt})");
+                            registry
+                                .record_event(&id, format!("Reconnect attempt {attempt}"))
+                                .await;
+                            // The source may not consider itself running
+                            let _ = core.stop_source(&id).await;
+                            match core.start_source(&id).await {
+                                Ok(_) => registry.mark_started(ComponentKind::Source, &id).await,
+                                Err(e) => {
+                                    warn!(
+                                        "Reconnect attempt {attempt} of source '{id}' failed: {e}"
+                                    );
+                                    registry
+                                        .record_event(
+                                            &id,
+                                            format!("Reconnect attempt {attempt} failed: {e}"),
+                                        )
+                                        .await;
+                                }
+                            }
+                        }
+                        Step::GiveUp(attempts) => {
+                            warn!(
+                                "Giving up on source '{id}' after {attempts} reconnect attempt(s)"
+                            );
+                            registry
+                                .record_event(
+                                    &id,
+                                    format!("Gave up after {attempts} reconnect attempt(s)"),
+                                )
+                                .await;
+                        }
+                        Step::Reconnected(attempts) => {
+                            info!("Source '{id}' reconnected after {attempts} attempt(s)");
+                            registry
+                                .record_event(
+                                    &id,
+                                    format!("Reconnected after {attempts} attempt(s)"),
+                                )
+                                .await;
+                        }
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_attempts: Option<u32>) -> ReconnectPolicyDto {
+        ReconnectPolicyDto {
+            initial_delay_ms: 1000,
+            max_delay_ms: 4000,
+            max_attempts,
+        }
+    }
+
+    #[test]
+    fn test_failed_source_is_restarted_with_backoff() {
+        let policy = policy(None);
+        let mut retry = None;
+        let start = Instant::now();
+        let error = ComponentStatus::Error;
+
+        assert_eq!(step(&policy, &mut retry, &error, start), Step::Wait);
+        let at = |ms| start + Duration::from_millis(ms);
+        assert_eq!(step(&policy, &mut retry, &error, at(500)), Step::Wait);
+        assert_eq!(
+            step(&policy, &mut retry, &error, at(1000)),
+            Step::Restart(1)
+        );
+        // The second attempt waits twice as long
+        assert_eq!(step(&policy, &mut retry, &error, at(2500)), Step::Wait);
+        assert_eq!(
+            step(&policy, &mut retry, &error, at(3000)),
+            Step::Restart(2)
+        );
+
+        let running = ComponentStatus::Running;
+        assert_eq!(
+            step(&policy, &mut retry, &running, at(3500)),
+            Step::Reconnected(2)
+        );
+        // Attempts are reset once it has been running for max_delay_ms
+        assert_eq!(step(&policy, &mut retry, &running, at(5000)), Step::Wait);
+        assert!(retry.is_some());
+        assert_eq!(step(&policy, &mut retry, &running, at(7500)), Step::Wait);
+        assert!(retry.is_none());
+    }
+
+    #[test]
+    fn test_flapping_source_keeps_backing_off() {
+        let policy = policy(Some(2));
+        let mut retry = None;
+        let start = Instant::now();
+        let error = ComponentStatus::Error;
+        let running = ComponentStatus::Running;
+        let at = |ms| start + Duration::from_millis(ms);
+
+        step(&policy, &mut retry, &error, start);
+        assert_eq!(
+            step(&policy, &mut retry, &error, at(1000)),
+            Step::Restart(1)
+        );
+        assert_eq!(
+            step(&policy, &mut retry, &running, at(1100)),
+            Step::Reconnected(1)
+        );
+
+        // It fails again right away, so the next attempt waits longer
+        assert_eq!(step(&policy, &mut retry, &error, at(1200)), Step::Wait);
+        assert_eq!(step(&policy, &mut retry, &error, at(2200)), Step::Wait);
+        assert_eq!(
+            step(&policy, &mut retry, &error, at(3200)),
+            Step::Restart(2)
+        );
+        step(&policy, &mut retry, &running, at(3300));
+        step(&policy, &mut retry, &error, at(3400));
+        assert_eq!(
+            step(&policy, &mut retry, &error, at(10000)),
+            Step::GiveUp(2)
+        );
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let policy = policy(Some(1));
+        let mut retry = None;
+        let start = Instant::now();
+        let error = ComponentStatus::Error;
+        let at = |secs| start + Duration::from_secs(secs);
+
+        step(&policy, &mut retry, &error, start);
+        assert_eq!(step(&policy, &mut retry, &error, at(1)), Step::Restart(1));
+        assert_eq!(step(&policy, &mut retry, &error, at(10)), Step::GiveUp(1));
+        assert_eq!(step(&policy, &mut retry, &error, at(100)), Step::Wait);
+
+        // Stopping the source ends its attempts
+        step(&policy, &mut retry, &ComponentStatus::Stopped, at(101));
+        assert!(retry.is_none());
+    }
+}
@@ -0,0 +1,648 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Server-side registry of component definitions and lifecycle timestamps.
+//!
+//! DrasiLib owns the running source and reaction instances but only exposes
+//! their IDs and statuses. The registry keeps the configuration each component
+//! was created from, which components are disabled, and when the server last
+//! saw each component enter `Running`. The API uses it to report kind,
+//! subscriptions and uptime, and persistence uses it to write sources and
+//! reactions back to the config file. With `tolerate_component_errors`, it
+//! also keeps sources and reactions that could not be built at startup, so
+//! they are reported as failed and kept in the config file.
+//!
+//! Sources also get a short status history, where restarts under a
+//! `reconnect` policy are recorded.
+
+use chrono::{DateTime, Utc};
+use drasi_lib::channels::ComponentStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
+use utoipa::ToSchema;
+
+use crate::config::{DisabledComponents, ReactionConfig, SourceConfig};
+
+/// The category of a component tracked by the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentKind {
+    Source,
+    Query,
+    Reaction,
+}
+
+impl ComponentKind {
+    /// Lowercase name used in API messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            ComponentKind::Source => "source",
+            ComponentKind::Query => "query",
+            ComponentKind::Reaction => "reaction",
+        }
+    }
+
+    /// Path segment of the component's API collection, e.g. `queries`.
+    pub fn collection(self) -> &'static str {
+        match self {
+            ComponentKind::Source => "sources",
+            ComponentKind::Query => "queries",
+            ComponentKind::Reaction => "reactions",
+        }
+    }
+}
+
+/// Events kept per source; older ones are dropped
+const HISTORY_LEN: usize = 50;
+
+/// Something that happened to a source, for its status history
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StatusEvent {
+    pub at: DateTime<Utc>,
+    pub message: String,
+}
+
+/// Definitions are stored with an insertion sequence number so they can be
+/// written back in the order they were first added.
+#[derive(Default)]
+pub struct ComponentRegistry {
+    next_seq: AtomicU64,
+    sources: RwLock<HashMap<String, (u64, SourceConfig)>>,
+    reactions: RwLock<HashMap<String, (u64, ReactionConfig)>>,
+    disabled: RwLock<HashSet<(ComponentKind, String)>>,
+    /// Queries whose `auto_start` was forced off at boot because they were
+    /// disabled. DrasiLib only holds the overridden config, so this is what
+    /// lets persistence and restore recover the original value.
+    suppressed_query_auto_start: RwLock<HashSet<String>>,
+    running_since: RwLock<HashMap<(ComponentKind, String), DateTime<Utc>>>,
+    /// Components last stopped through the API, which supervisors leave
+    /// stopped until they are started again
+    stopped_by_api: RwLock<HashSet<(ComponentKind, String)>>,
+    /// Components registered without a DrasiLib instance because building
+    /// them failed, with the error
+    build_errors: RwLock<HashMap<(ComponentKind, String), String>>,
+    /// Last time each source was seen alive by the liveness monitor
+    heartbeats: RwLock<HashMap<String, DateTime<Utc>>>,
+    /// Running sources whose heartbeat stopped
+    stale_sources: RwLock<HashSet<String>>,
+    /// Recent events per source, oldest first
+    history: RwLock<HashMap<String, VecDeque<StatusEvent>>>,
+    /// One lock per component whose definition is being changed
+    definition_locks: Mutex<HashMap<(ComponentKind, String), Arc<Mutex<()>>>>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lock a component's definition against other changes made through the
+    /// API.
+    ///
+    /// Handlers hold the guard from the `If-Match` check until the change is
+    /// applied, so two requests carrying the same ETag cannot both succeed.
+    pub async fn lock_definition(&self, kind: ComponentKind, id: &str) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.definition_locks.lock().await;
+            // Drop locks nobody holds or waits for
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            locks.entry((kind, id.to_string())).or_default().clone()
+        };
+        lock.lock_owned().await
+    }
+
+    /// Record the configuration a source was created from.
+    ///
+    /// Replacing an existing source keeps its original position.
+    pub async fn insert_source(&self, config: SourceConfig) {
+        self.build_errors
+            .write()
+            .await
+            .remove(&(ComponentKind::Source, config.id().to_string()));
+        let mut sources = self.sources.write().await;
+        let seq = sources
+            .get(config.id())
+            .map(|(seq, _)| *seq)
+            .unwrap_or_else(|| self.next_seq.fetch_add(1, Ordering::Relaxed));
+        sources.insert(config.id().to_string(), (seq, config));
+    }
+
+    /// Record the configuration a reaction was created from.
+    ///
+    /// Replacing an existing reaction keeps its original position.
+    pub async fn insert_reaction(&self, config: ReactionConfig) {
+        self.build_errors
+            .write()
+            .await
+            .remove(&(ComponentKind::Reaction, config.id().to_string()));
+        let mut reactions = self.reactions.write().await;
+        let seq = reactions
+            .get(config.id())
+            .map(|(seq, _)| *seq)
+            .unwrap_or_else(|| self.next_seq.fetch_add(1, Ordering::Relaxed));
+        reactions.insert(config.id().to_string(), (seq, config));
+    }
+
+    /// Record a source whose instance could not be built. It is not in
+    /// DrasiLib, but its definition is still reported and persisted.
+    pub async fn insert_failed_source(&self, config: SourceConfig, error: String) {
+        let key = (ComponentKind::Source, config.id().to_string());
+        self.insert_source(config).await;
+        self.build_errors.write().await.insert(key, error);
+    }
+
+    /// Record a reaction whose instance could not be built. It is not in
+    /// DrasiLib, but its definition is still reported and persisted.
+    pub async fn insert_failed_reaction(&self, config: ReactionConfig, error: String) {
+        let key = (ComponentKind::Reaction, config.id().to_string());
+        self.insert_reaction(config).await;
+        self.build_errors.write().await.insert(key, error);
+    }
+
+    /// Why a component could not be built, if it is registered as failed.
+    pub async fn build_error(&self, kind: ComponentKind, id: &str) -> Option<String> {
+        self.build_errors
+            .read()
+            .await
+            .get(&(kind, id.to_string()))
+            .cloned()
+    }
+
+    /// Failed components of one kind with their errors, sorted by ID.
+    pub async fn failed(&self, kind: ComponentKind) -> Vec<(String, String)> {
+        let mut failed: Vec<_> = self
+            .build_errors
+            .read()
+            .await
+            .iter()
+            .filter(|((k, _), _)| *k == kind)
+            .map(|((_, id), error)| (id.clone(), error.clone()))
+            .collect();
+        failed.sort();
+        failed
+    }
+
+    pub async fn get_source(&self, id: &str) -> Option<SourceConfig> {
+        self.sources
+            .read()
+            .await
+            .get(id)
+            .map(|(_, config)| config.clone())
+    }
+
+    pub async fn get_reaction(&self, id: &str) -> Option<ReactionConfig> {
+        self.reactions
+            .read()
+            .await
+            .get(id)
+            .map(|(_, config)| config.clone())
+    }
+
+    /// All registered source definitions in insertion order.
+    pub async fn source_configs(&self) -> Vec<SourceConfig> {
+        let sources = self.sources.read().await;
+        let mut entries: Vec<_> = sources.values().collect();
+        entries.sort_by_key(|(seq, _)| *seq);
+        entries.into_iter().map(|(_, c)| c.clone()).collect()
+    }
+
+    /// All registered reaction definitions in insertion order.
+    pub async fn reaction_configs(&self) -> Vec<ReactionConfig> {
+        let reactions = self.reactions.read().await;
+        let mut entries: Vec<_> = reactions.values().collect();
+        entries.sort_by_key(|(seq, _)| *seq);
+        entries.into_iter().map(|(_, c)| c.clone()).collect()
+    }
+
+    /// Mark a component as disabled or enabled.
+    pub async fn set_disabled(&self, kind: ComponentKind, id: &str, disabled: bool) {
+        let key = (kind, id.to_string());
+        let mut set = self.disabled.write().await;
+        if disabled {
+            set.insert(key);
+        } else {
+            set.remove(&key);
+        }
+    }
+
+    pub async fn is_disabled(&self, kind: ComponentKind, id: &str) -> bool {
+        self.disabled
+            .read()
+            .await
+            .contains(&(kind, id.to_string()))
+    }
+
+    /// Record that a component was stopped, or started again, through the API.
+    pub async fn set_stopped_by_api(&self, kind: ComponentKind, id: &str, stopped: bool) {
+        let key = (kind, id.to_string());
+        let mut set = self.stopped_by_api.write().await;
+        if stopped {
+            set.insert(key);
+        } else {
+            set.remove(&key);
+        }
+    }
+
+    pub async fn is_stopped_by_api(&self, kind: ComponentKind, id: &str) -> bool {
+        self.stopped_by_api
+            .read()
+            .await
+            .contains(&(kind, id.to_string()))
+    }
+
+    /// Load the disabled set from configuration.
+    pub async fn load_disabled(&self, disabled: &DisabledComponents) {
+        let mut set = self.disabled.write().await;
+        for id in &disabled.sources {
+            set.insert((ComponentKind::Source, id.clone()));
+        }
+        for id in &disabled.queries {
+            set.insert((ComponentKind::Query, id.clone()));
+        }
+        for id in &disabled.reactions {
+            set.insert((ComponentKind::Reaction, id.clone()));
+        }
+    }
+
+    /// Record that a query's `auto_start` was turned off only because it is
+    /// disabled.
+    pub async fn suppress_query_auto_start(&self, id: &str) {
+        self.suppressed_query_auto_start
+            .write()
+            .await
+            .insert(id.to_string());
+    }
+
+    pub async fn is_query_auto_start_suppressed(&self, id: &str) -> bool {
+        self.suppressed_query_auto_start.read().await.contains(id)
+    }
+
+    /// The disabled set in its config file form, with IDs sorted.
+    pub async fn disabled_components(&self) -> DisabledComponents {
+        let mut disabled = DisabledComponents::default();
+        for (kind, id) in self.disabled.read().await.iter() {
+            match kind {
+                ComponentKind::Source => disabled.sources.push(id.clone()),
+                ComponentKind::Query => disabled.queries.push(id.clone()),
+                ComponentKind::Reaction => disabled.reactions.push(id.clone()),
+            }
+        }
+        disabled.sources.sort();
+        disabled.queries.sort();
+        disabled.reactions.sort();
+        disabled
+    }
+
+    /// Map each query ID to the registered reactions that subscribe to it.
+    pub async fn reactions_by_query(&self) -> HashMap<String, Vec<String>> {
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for (reaction_id, (_, config)) in self.reactions.read().await.iter() {
+            for query_id in config.queries() {
+                map.entry(query_id.clone())
+                    .or_default()
+                    .push(reaction_id.clone());
+            }
+        }
+        for reactions in map.values_mut() {
+            reactions.sort();
+        }
+        map
+    }
+
+    /// Forget a component after it has been removed from DrasiLib.
+    pub async fn remove(&self, kind: ComponentKind, id: &str) {
+        self.disabled
+            .write()
+            .await
+            .remove(&(kind, id.to_string()));
+        self.build_errors
+            .write()
+            .await
+            .remove(&(kind, id.to_string()));
+        self.stopped_by_api
+            .write()
+            .await
+            .remove(&(kind, id.to_string()));
+        if kind == ComponentKind::Query {
+            self.suppressed_query_auto_start.write().await.remove(id);
+        }
+        match kind {
+            ComponentKind::Source => {
+                self.sources.write().await.remove(id);
+                self.heartbeats.write().await.remove(id);
+                self.stale_sources.write().await.remove(id);
+                self.history.write().await.remove(id);
+            }
+            ComponentKind::Reaction => {
+                self.reactions.write().await.remove(id);
+            }
+            ComponentKind::Query => {}
+        }
+        self.running_since
+            .write()
+            .await
+            .remove(&(kind, id.to_string()));
+    }
+
+    /// Clear the running timestamp after a component is stopped or replaced,
+    /// so uptime is measured from its next run rather than an earlier one.
+    pub async fn reset_running(&self, kind: ComponentKind, id: &str) {
+        self.running_since
+            .write()
+            .await
+            .remove(&(kind, id.to_string()));
+        if kind == ComponentKind::Query {
+            self.suppressed_query_auto_start.write().await.remove(id);
+        }
+    }
+
+    /// Record that a component was just started, so its uptime counts from
+    /// the start rather than from the first poll that sees it running.
+    pub async fn mark_started(&self, kind: ComponentKind, id: &str) {
+        self.running_since
+            .write()
+            .await
+            .insert((kind, id.to_string()), Utc::now());
+        if kind == ComponentKind::Query {
+            self.suppressed_query_auto_start.write().await.remove(id);
+        }
+    }
+
+    /// Record that a source was seen alive.
+    pub async fn record_heartbeat(&self, id: &str) {
+        self.heartbeats
+            .write()
+            .await
+            .insert(id.to_string(), Utc::now());
+    }
+
+    pub async fn last_heartbeat(&self, id: &str) -> Option<DateTime<Utc>> {
+        self.heartbeats.read().await.get(id).copied()
+    }
+
+    /// Mark a source stale or not, returning whether it was stale before.
+    pub async fn set_stale(&self, id: &str, stale: bool) -> bool {
+        let mut stale_sources = self.stale_sources.write().await;
+        if stale {
+            !stale_sources.insert(id.to_string())
+        } else {
+            stale_sources.remove(id)
+        }
+    }
+
+    pub async fn is_stale(&self, id: &str) -> bool {
+        self.stale_sources.read().await.contains(id)
+    }
+
+    /// Add an event to a source's status history.
+    pub async fn record_event(&self, id: &str, message: impl Into<String>) {
+        let mut history = self.history.write().await;
+        let events = history.entry(id.to_string()).or_default();
+        if events.len() == HISTORY_LEN {
+            events.pop_front();
+        }
+        events.push_back(StatusEvent {
+            at: Utc::now(),
+            message: message.into(),
+        });
+    }
+
+    /// A source's status history, oldest first
+    pub async fn history(&self, id: &str) -> Vec<StatusEvent> {
+        self.history
+            .read()
+            .await
+            .get(id)
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Update the running timestamp from a freshly observed status and return
+    /// how long the component has been running, if it is.
+    ///
+    /// Uptime counts from [`Self::mark_started`]. A running component that
+    /// was started some other way, e.g. after failing, counts from the first
+    /// time the server observes it running.
+    pub async fn observe_status(
+        &self,
+        kind: ComponentKind,
+        id: &str,
+        status: &ComponentStatus,
+    ) -> Option<u64> {
+        let key = (kind, id.to_string());
+        let mut running_since = self.running_since.write().await;
+        match status {
+            ComponentStatus::Running => {
+                let since = *running_since.entry(key).or_insert_with(Utc::now);
+                Some((Utc::now() - since).num_seconds().max(0) as u64)
+            }
+            // Still counting from the start
+            ComponentStatus::Starting => None,
+            _ => {
+                running_since.remove(&key);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::api::models::{ConfigValue, LogReactionConfigDto, MockSourceConfigDto};
+
+    fn mock_source(id: &str) -> SourceConfig {
+        SourceConfig::Mock {
+            id: id.to_string(),
+            auto_start: true,
+            bootstrap_provider: None,
+            dispatch_mode: None,
+            dispatch_buffer_capacity: None,
+            lazy: false,
+            config: MockSourceConfigDto {
+                data_type: ConfigValue::Static("generic".to_string()),
+                interval_ms: ConfigValue::Static(1000),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_get_and_remove_source() {
+        let registry = ComponentRegistry::new();
+        registry.insert_source(mock_source("s1")).await;
+
+        let stored = registry.get_source("s1").await.unwrap();
+        assert_eq!(stored.kind(), "mock");
+
+        registry.remove(ComponentKind::Source, "s1").await;
+        assert!(registry.get_source("s1").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_insert_reaction_keeps_queries() {
+        let registry = ComponentRegistry::new();
+        registry
+            .insert_reaction(ReactionConfig::Log {
+                id: "r1".to_string(),
+                queries: vec!["q1".to_string()],
+                auto_start: true,
+                wait_for_queries_ms: None,
+                group: None,
+                config: LogReactionConfigDto::default(),
+            })
+            .await;
+
+        let stored = registry.get_reaction("r1").await.unwrap();
+        assert_eq!(stored.queries(), ["q1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_source_configs_keep_insertion_order() {
+        let registry = ComponentRegistry::new();
+        registry.insert_source(mock_source("b")).await;
+        registry.insert_source(mock_source("a")).await;
+        registry.insert_source(mock_source("b")).await;
+
+        let ids: Vec<String> = registry
+            .source_configs()
+            .await
+            .iter()
+            .map(|c| c.id().to_string())
+            .collect();
+        assert_eq!(ids, vec!["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_source_is_kept_until_replaced() {
+        let registry = ComponentRegistry::new();
+        registry
+            .insert_failed_source(mock_source("s1"), "connection refused".to_string())
+            .await;
+
+        assert!(registry.get_source("s1").await.is_some());
+        assert_eq!(
+            registry.failed(ComponentKind::Source).await,
+            vec![("s1".to_string(), "connection refused".to_string())]
+        );
+        assert!(registry.failed(ComponentKind::Reaction).await.is_empty());
+
+        registry.insert_source(mock_source("s1")).await;
+        assert_eq!(
+            registry.build_error(ComponentKind::Source, "s1").await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_roundtrip() {
+        let registry = ComponentRegistry::new();
+        registry
+            .load_disabled(&DisabledComponents {
+                sources: vec!["s1".to_string()],
+                queries: vec!["q1".to_string()],
+                reactions: vec![],
+            })
+            .await;
+        assert!(registry.is_disabled(ComponentKind::Source, "s1").await);
+        assert!(!registry.is_disabled(ComponentKind::Reaction, "s1").await);
+
+        registry
+            .set_disabled(ComponentKind::Reaction, "r1", true)
+            .await;
+        registry.set_disabled(ComponentKind::Query, "q1", false).await;
+
+        let disabled = registry.disabled_components().await;
+        assert_eq!(disabled.sources, vec!["s1"]);
+        assert!(disabled.queries.is_empty());
+        assert_eq!(disabled.reactions, vec!["r1"]);
+    }
+
+    #[tokio::test]
+    async fn test_observe_status_tracks_running_periods() {
+        let registry = ComponentRegistry::new();
+
+        let uptime = registry
+            .observe_status(ComponentKind::Query, "q1", &ComponentStatus::Stopped)
+            .await;
+        assert_eq!(uptime, None);
+
+        let uptime = registry
+            .observe_status(ComponentKind::Query, "q1", &ComponentStatus::Running)
+            .await;
+        assert_eq!(uptime, Some(0));
+
+        // Stopping clears the timestamp so a restart measures from scratch
+        registry
+            .observe_status(ComponentKind::Query, "q1", &ComponentStatus::Stopped)
+            .await;
+        assert!(registry.running_since.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_uptime_counts_from_the_start() {
+        let registry = ComponentRegistry::new();
+        let key = (ComponentKind::Source, "s1".to_string());
+        registry.mark_started(ComponentKind::Source, "s1").await;
+        // As if it was started a while before the first poll
+        let mut running_since = registry.running_since.write().await;
+        *running_since.get_mut(&key).unwrap() -= chrono::Duration::seconds(30);
+        drop(running_since);
+
+        let uptime = registry
+            .observe_status(ComponentKind::Source, "s1", &ComponentStatus::Starting)
+            .await;
+        assert_eq!(uptime, None);
+        let uptime = registry
+            .observe_status(ComponentKind::Source, "s1", &ComponentStatus::Running)
+            .await;
+        assert!(uptime.is_some_and(|secs| secs >= 30));
+    }
+
+    #[tokio::test]
+    async fn test_history_keeps_recent_events() {
+        let registry = ComponentRegistry::new();
+        for n in 0..HISTORY_LEN + 5 {
+            registry.record_event("s1", format!("event {n}")).await;
+        }
+
+        let history = registry.history("s1").await;
+        assert_eq!(history.len(), HISTORY_LEN);
+        assert_eq!(history[0].message, "event 5");
+
+        registry.remove(ComponentKind::Source, "s1").await;
+        assert!(registry.history("s1").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_definition_lock_is_held_per_component() {
+        let registry = ComponentRegistry::new();
+        let held = registry.lock_definition(ComponentKind::Source, "s1").await;
+
+        // Another component can still be changed
+        let _other = registry.lock_definition(ComponentKind::Query, "s1").await;
+        let waiting = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            registry.lock_definition(ComponentKind::Source, "s1"),
+        )
+        .await;
+        assert!(waiting.is_err());
+
+        drop(held);
+        let _again = registry.lock_definition(ComponentKind::Source, "s1").await;
+    }
+}
@@ -12,43 +12,109 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use axum::{
     extract::Extension,
-    routing::{get, post},
+    routing::{get, patch, post, put},
     Router,
 };
+use futures::StreamExt;
 use log::{error, info, warn};
+use socket2::{Domain, Protocol, Socket, Type};
 use std::fs::OpenOptions;
-use std::path::PathBuf;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use tower_http::cors::CorsLayer;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::api;
 use crate::api::mappings::{map_server_settings, DtoMapper};
+use crate::config::loader::{load_config_file_with, CliOverrides};
+use crate::config::split_files::read_config_file;
+use crate::config::types::unbracketed;
+use crate::config::{BindHosts, ReactionConfig, SourceConfig};
 use crate::factories::{create_reaction, create_source};
-use crate::load_config_file;
 use crate::persistence::ConfigPersistence;
+use crate::registry::{ComponentKind, ComponentRegistry};
 use drasi_index_rocksdb::RocksDbIndexProvider;
+use drasi_lib::channels::ComponentStatus;
+use drasi_lib::plugin_core::{Reaction, Source};
 use drasi_lib::DrasiLib;
 
+/// Where RocksDB indexes are stored when `persist_index` is enabled and no
+/// `index_path` is set, relative to the working directory.
+pub const INDEX_PATH: &str = "./data/index";
+
+/// Directory RocksDB indexes are stored in for `config`.
+pub fn index_path(config: &crate::config::DrasiServerConfig) -> Result<PathBuf> {
+    match &config.index_path {
+        Some(path) => Ok(PathBuf::from(DtoMapper::new().resolve_typed(path)?)),
+        None => Ok(PathBuf::from(INDEX_PATH)),
+    }
+}
+
 pub struct DrasiServer {
     core: Option<DrasiLib>,
     enable_api: bool,
-    host: String,
+    host: BindHosts,
     port: u16,
+    /// Worker threads of a dedicated API runtime, if configured
+    api_worker_threads: Option<usize>,
+    strict_joins: bool,
+    /// Keys required on API requests that change the server; empty for none
+    api_keys: api::auth::ApiKeys,
+    /// OIDC provider whose tokens are accepted as well as the keys
+    jwt: Option<api::jwt::JwtConfig>,
+    /// Serve the API over HTTPS with this certificate
+    tls: Option<api::tls::TlsConfig>,
+    /// Check running sources for liveness
+    heartbeat: Option<crate::config::HeartbeatSettings>,
+    /// Whether `/docs` is served, and whether reading it needs credentials
+    docs: crate::config::DocsSettings,
+    /// Applied over the built-in OpenAPI title, description and contact
+    openapi: api::openapi::OpenApiMetadata,
+    /// Reported by `GET /about` and logged at startup
+    about: Arc<api::about::AboutResponse>,
+    /// Components that failed at startup, reported by `GET /admin/startup-report`
+    startup_report: Arc<api::startup_report::StartupReport>,
+    /// Measured by `GET /admin/usage`
+    index_dir: Arc<api::usage::IndexDir>,
     config_file_path: Option<String>,
+    /// `--set` and `--strict`, applied whenever the config file is loaded
+    overrides: CliOverrides,
     read_only: Arc<bool>,
     #[allow(dead_code)]
     config_persistence: Option<Arc<ConfigPersistence>>,
+    registry: Arc<ComponentRegistry>,
 }
 
 impl DrasiServer {
     /// Create a new DrasiServer from a configuration file
     pub async fn new(config_path: PathBuf, port: u16) -> Result<Self> {
-        let config = load_config_file(&config_path)?;
+        Self::with_overrides(config_path, port, CliOverrides::default()).await
+    }
+
+    /// Create a new DrasiServer from a configuration file, with command line
+    /// overrides applied on top of it here and on every later reload.
+    pub async fn with_overrides(
+        config_path: PathBuf,
+        port: u16,
+        overrides: CliOverrides,
+    ) -> Result<Self> {
+        // Changes journaled but not yet saved when the server last stopped
+        let recovered = crate::journal::recover(&config_path)
+            .context("Failed to replay the persistence journal")?;
+        if recovered > 0 {
+            warn!(
+                "Replayed {recovered} configuration change(s) that were not saved before the last shutdown"
+            );
+        }
+
+        let config = load_config_file_with(&config_path, &overrides)?;
         config.validate()?;
 
         // Resolve server settings using the mapper
@@ -79,18 +145,27 @@ impl DrasiServer {
         let mut builder = DrasiLib::builder().with_id(&id);
 
         // Set capacity defaults if configured (resolve env vars)
+        let mut limits = api::about::AboutLimits {
+            worker_threads: config.runtime.worker_threads,
+            api_worker_threads: config.runtime.api_worker_threads,
+            ..Default::default()
+        };
         if let Some(ref capacity_config) = config.default_priority_queue_capacity {
             let capacity: usize = mapper.resolve_typed(capacity_config)?;
             builder = builder.with_priority_queue_capacity(capacity);
+            limits.default_priority_queue_capacity = Some(capacity);
         }
         if let Some(ref capacity_config) = config.default_dispatch_buffer_capacity {
             let capacity: usize = mapper.resolve_typed(capacity_config)?;
             builder = builder.with_dispatch_buffer_capacity(capacity);
+            limits.default_dispatch_buffer_capacity = Some(capacity);
         }
 
         // Create and add RocksDB index provider if persist_index is enabled
+        let mut index_dir = api::usage::IndexDir::default();
         if config.persist_index {
-            let index_path = PathBuf::from("./data/index");
+            let index_path = index_path(&config)?;
+            index_dir.0 = Some(index_path.clone());
             info!(
                 "Enabling persistent indexing with RocksDB at: {}",
                 index_path.display()
@@ -102,25 +177,139 @@ impl DrasiServer {
             builder = builder.with_index_provider(Arc::new(rocksdb_provider));
         }
 
-        // Create and add sources from config
+        // Disabled components are still created so they can be restored via the
+        // API, but they never start automatically. The registry keeps the
+        // original auto_start so a restore knows whether to start them.
+        let registry = Arc::new(ComponentRegistry::new());
+        registry.load_disabled(&config.disabled).await;
+        let source_instances: Vec<SourceConfig> = config
+            .sources
+            .iter()
+            .map(|source_config| {
+                let mut instance_config = source_config.clone();
+                if config.disabled.sources.contains(&source_config.id().to_string()) {
+                    info!("Source '{}' is disabled", source_config.id());
+                    instance_config.set_auto_start(false);
+                }
+                if source_config.lazy() {
+                    // Started by lazy_sources once a query subscribes
+                    instance_config.set_auto_start(false);
+                }
+                instance_config
+            })
+            .collect();
+        let reaction_instances: Vec<ReactionConfig> = config
+            .reactions
+            .iter()
+            .map(|reaction_config| {
+                let mut instance_config = reaction_config.clone();
+                if config
+                    .disabled
+                    .reactions
+                    .contains(&reaction_config.id().to_string())
+                {
+                    info!("Reaction '{}' is disabled", reaction_config.id());
+                    instance_config.set_auto_start(false);
+                }
+                if reaction_config.wait_for_queries_ms().is_some() {
+                    // Started from run() once its queries are running
+                    instance_config.set_auto_start(false);
+                }
+                if reaction_config.group().is_some() {
+                    // Started by the group supervisor, one member at a time
+                    instance_config.set_auto_start(false);
+                }
+                instance_config
+            })
+            .collect();
+
+        // Creating a component may connect to an external system, so sources
+        // and reactions are created concurrently and every failure is reported
         info!(
-            "Loading {} source(s) from configuration",
-            config.sources.len()
+            "Loading {} source(s) and {} reaction(s) from configuration",
+            config.sources.len(),
+            config.reactions.len()
         );
-        for source_config in config.sources.clone() {
-            let source = create_source(source_config).await?;
-            builder = builder.with_source(source);
+        let (sources, reactions) = tokio::join!(
+            create_sources(source_instances),
+            create_reactions(reaction_instances)
+        );
+        // With tolerate_component_errors, components that fail are registered
+        // as failed and the server starts without them
+        let mut report = api::startup_report::StartupReport::default();
+
+        for (source_config, source) in config.sources.iter().zip(sources) {
+            match source {
+                Ok(source) => {
+                    builder = builder.with_source(source);
+                    registry.insert_source(source_config.clone()).await;
+                    report.record_built();
+                }
+                Err(e) => {
+                    let error = format!("{e:#}");
+                    report.record_failure(ComponentKind::Source, source_config.id(), &error);
+                    if config.tolerate_component_errors {
+                        registry
+                            .insert_failed_source(source_config.clone(), error)
+                            .await;
+                    }
+                }
+            }
         }
 
-        // Add queries from config
+        // Add queries from config. Queries reading from a failed source are
+        // kept, but cannot start until the source is replaced through the API.
+        let failed_sources: Vec<String> = registry
+            .failed(ComponentKind::Source)
+            .await
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
         for query_config in &config.queries {
-            builder = builder.with_query(query_config.clone());
+            let mut query_config = query_config.clone();
+            let disabled = config.disabled.queries.contains(&query_config.id);
+            let failed_source = query_config
+                .sources
+                .iter()
+                .map(|s| s.source_id.clone())
+                .find(|id| failed_sources.contains(id));
+            if disabled {
+                info!("Query '{}' is disabled", query_config.id);
+            } else if let Some(source_id) = &failed_source {
+                report.record_failure(
+                    ComponentKind::Query,
+                    &query_config.id,
+                    format!("not started: source '{source_id}' failed to build"),
+                );
+            }
+            if (disabled || failed_source.is_some()) && query_config.auto_start {
+                query_config.auto_start = false;
+                registry.suppress_query_auto_start(&query_config.id).await;
+            }
+            builder = builder.with_query(query_config);
         }
 
-        // Create and add reactions from config
-        for reaction_config in config.reactions.clone() {
-            let reaction = create_reaction(reaction_config)?;
-            builder = builder.with_reaction(reaction);
+        for (reaction_config, reaction) in config.reactions.iter().zip(reactions) {
+            match reaction {
+                Ok(reaction) => {
+                    builder = builder.with_reaction(reaction);
+                    registry.insert_reaction(reaction_config.clone()).await;
+                    report.record_built();
+                }
+                Err(e) => {
+                    let error = format!("{e:#}");
+                    report.record_failure(ComponentKind::Reaction, reaction_config.id(), &error);
+                    if config.tolerate_component_errors {
+                        registry
+                            .insert_failed_reaction(reaction_config.clone(), error)
+                            .await;
+                    }
+                }
+            }
+        }
+
+        if !report.is_clean() && !config.tolerate_component_errors {
+            return Err(anyhow::anyhow!("Failed to create components. {report}"));
         }
 
         // Build and initialize the core
@@ -134,9 +323,39 @@ impl DrasiServer {
             enable_api: true,
             host: resolved_settings.host,
             port,
+            api_worker_threads: config.runtime.api_worker_threads,
+            strict_joins: config.strict_joins,
+            api_keys: resolved_settings.api_keys,
+            jwt: resolved_settings.jwt,
+            tls: resolved_settings.tls,
+            heartbeat: config.heartbeat.clone(),
+            docs: config.docs.clone(),
+            openapi: resolved_settings.openapi,
+            about: Arc::new(api::about::AboutResponse::new(
+                api::about::AboutFeatures {
+                    persist_index: config.persist_index,
+                    reconcile: config.reconcile,
+                    read_only,
+                    strict_joins: config.strict_joins,
+                    tolerate_component_errors: config.tolerate_component_errors,
+                    pprof: cfg!(all(feature = "pprof", unix)),
+                    api_keys: !config.api_keys.is_empty(),
+                    jwt: config.jwt.is_some(),
+                    tls: config.tls.is_some(),
+                    client_cert: config
+                        .tls
+                        .as_ref()
+                        .is_some_and(|tls| tls.client_ca_path.is_some()),
+                },
+                limits,
+            )),
+            startup_report: Arc::new(report),
+            index_dir: Arc::new(index_dir),
             config_file_path: Some(config_path.to_string_lossy().to_string()),
+            overrides,
             read_only: Arc::new(read_only),
             config_persistence: None, // Will be set after core is started
+            registry,
         })
     }
 
@@ -144,18 +363,37 @@ impl DrasiServer {
     pub fn from_core(
         core: DrasiLib,
         enable_api: bool,
-        host: String,
+        host: impl Into<BindHosts>,
         port: u16,
         config_file_path: Option<String>,
     ) -> Self {
         Self {
             core: Some(core),
             enable_api,
-            host,
+            host: host.into(),
             port,
+            api_worker_threads: None,
+            strict_joins: false,
+            api_keys: api::auth::ApiKeys::default(),
+            jwt: None,
+            tls: None,
+            heartbeat: None,
+            docs: crate::config::DocsSettings::default(),
+            openapi: api::openapi::OpenApiMetadata::default(),
+            about: Arc::new(api::about::AboutResponse::new(
+                api::about::AboutFeatures {
+                    pprof: cfg!(all(feature = "pprof", unix)),
+                    ..Default::default()
+                },
+                api::about::AboutLimits::default(),
+            )),
+            startup_report: Arc::default(),
+            index_dir: Arc::default(),
             config_file_path,
+            overrides: CliOverrides::default(),
             read_only: Arc::new(false), // Programmatic mode assumes write access
             config_persistence: None,   // Will be set up if config file is provided
+            registry: Arc::new(ComponentRegistry::new()),
         }
     }
 
@@ -166,7 +404,7 @@ impl DrasiServer {
     }
 
     #[allow(clippy::print_stdout)]
-    pub async fn run(mut self) -> Result<()> {
+    pub async fn run(self) -> Result<()> {
         println!("Starting Drasi Server");
         if let Some(config_file) = &self.config_file_path {
             println!("  Config file: {config_file}");
@@ -176,7 +414,30 @@ impl DrasiServer {
             "  Log level: {}",
             std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string())
         );
+        if !self.startup_report.is_clean() {
+            println!(
+                "  {}",
+                self.startup_report.to_string().replace('\n', "\n  ")
+            );
+        }
+
+        let server = self.start().await?;
+
+        // Wait for shutdown signal
+        tokio::signal::ctrl_c().await?;
+
+        info!("Shutting down Drasi Server");
+        server.stop().await
+    }
+
+    /// Start the server and its API without waiting for a shutdown signal.
+    ///
+    /// `run` is this followed by waiting for Ctrl-C. Embedding applications
+    /// and tests use it directly, to learn the bound address (useful with
+    /// port 0) and to stop the server when they are done.
+    pub async fn start(mut self) -> Result<RunningServer> {
         info!("Initializing Drasi Server");
+        info!("{}", self.about.banner());
 
         // Take the core out of self
         let core = self.core.take().expect("Core should be initialized");
@@ -187,27 +448,95 @@ impl DrasiServer {
 
         // Start the core server
         core.start().await?;
+        mark_auto_started(&core, &self.registry).await;
+
+        // Start reactions that wait for their queries
+        for reaction_config in self.registry.reaction_configs().await {
+            let Some(wait_ms) = reaction_config.wait_for_queries_ms() else {
+                continue;
+            };
+            if !reaction_config.auto_start()
+                || self
+                    .registry
+                    .is_disabled(ComponentKind::Reaction, reaction_config.id())
+                    .await
+                || self
+                    .registry
+                    .build_error(ComponentKind::Reaction, reaction_config.id())
+                    .await
+                    .is_some()
+            {
+                continue;
+            }
+            crate::reaction_gate::spawn_gated_start(
+                core.clone(),
+                self.registry.clone(),
+                reaction_config.id().to_string(),
+                reaction_config.queries().to_vec(),
+                std::time::Duration::from_millis(wait_ms),
+            );
+        }
+
+        // Background tasks run until `stop` cancels them
+        let shutdown = CancellationToken::new();
+        let mut tasks = vec![
+            crate::lazy_sources::spawn_lazy_start(
+                core.clone(),
+                self.registry.clone(),
+                shutdown.clone(),
+            ),
+            crate::reaction_groups::spawn_group_supervisor(
+                core.clone(),
+                self.registry.clone(),
+                shutdown.clone(),
+            ),
+            crate::reconnect::spawn_reconnect_supervisor(
+                core.clone(),
+                self.registry.clone(),
+                shutdown.clone(),
+            ),
+        ];
+        if let Some(heartbeat) = &self.heartbeat {
+            tasks.push(crate::liveness::spawn_liveness_monitor(
+                core.clone(),
+                self.registry.clone(),
+                heartbeat.clone(),
+                shutdown.clone(),
+            ));
+        }
+
+        // In reconcile mode the config file is the desired state, so API changes
+        // are reverted by the reconciler instead of being persisted
+        let reconcile_config = match &self.config_file_path {
+            Some(config_file) => {
+                let config = load_config_file_with(config_file, &self.overrides)?;
+                config.reconcile.then_some(config)
+            }
+            None => None,
+        };
 
         // Initialize persistence if config file is provided and persistence is enabled
-        let config_persistence = if let Some(config_file) = &self.config_file_path {
+        let config_persistence = if reconcile_config.is_some() {
+            info!("Configuration persistence disabled (reconcile mode)");
+            None
+        } else if let Some(config_file) = &self.config_file_path {
             if !*self.read_only {
                 // Need to reload config to check disable_persistence flag
-                let config = load_config_file(PathBuf::from(config_file))?;
+                let config = load_config_file_with(config_file, &self.overrides)?;
                 let mapper = DtoMapper::new();
                 let resolved_settings = map_server_settings(&config, &mapper)?;
                 let persistence_disabled = resolved_settings.disable_persistence;
 
                 if !persistence_disabled {
-                    // Persistence is enabled - create ConfigPersistence instance
-                    let persistence = Arc::new(ConfigPersistence::new(
-                        PathBuf::from(config_file),
-                        core.clone(),
-                        self.host.clone(),
-                        self.port,
-                        resolved_settings.log_level,
-                        false,
-                        config.persist_index,
-                    ));
+                    // Persistence is enabled - create ConfigPersistence instance.
+                    // It saves from the file as written, so DRASI__ overrides
+                    // are not baked into it
+                    let written = read_config_file(Path::new(config_file))?;
+                    let persistence = Arc::new(
+                        ConfigPersistence::new(PathBuf::from(config_file), core.clone(), written)
+                            .with_loaded_config(config)
+                            .with_registry(self.registry.clone()),
+                    );
                     info!("Configuration persistence enabled");
                     Some(persistence)
                 } else {
@@ -223,48 +552,118 @@ impl DrasiServer {
             None
         };
 
+        if let (Some(config), Some(config_file)) = (&reconcile_config, &self.config_file_path) {
+            let interval = config
+                .reconcile_interval_secs
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(crate::reconciler::DEFAULT_RECONCILE_INTERVAL);
+            let reconciler = crate::reconciler::Reconciler::new(
+                core.clone(),
+                self.registry.clone(),
+                PathBuf::from(config_file),
+                interval,
+                self.overrides.clone(),
+            );
+            tasks.push(reconciler.spawn(shutdown.clone()));
+        }
+
         // Start web API if enabled
-        if self.enable_api {
-            self.start_api(&core, config_persistence.clone()).await?;
+        let mut api_runtime = None;
+        let api = if self.enable_api {
+            let api = self
+                .start_api(
+                    &core,
+                    config_persistence.clone(),
+                    &shutdown,
+                    &mut tasks,
+                    &mut api_runtime,
+                )
+                .await?;
             info!(
                 "Drasi Server started successfully with API on port {}",
                 self.port
             );
+            api
         } else {
             info!("Drasi Server started successfully (API disabled)");
-        }
-
-        // Wait for shutdown signal
-        tokio::signal::ctrl_c().await?;
-
-        info!("Shutting down Drasi Server");
-        core.stop().await?;
+            Vec::new()
+        };
 
-        Ok(())
+        Ok(RunningServer {
+            core,
+            config_persistence,
+            api,
+            api_runtime,
+            shutdown,
+            tasks,
+        })
     }
 
+    /// Serve the API, returning each bound address with the task serving it.
+    ///
+    /// The API shuts down gracefully once `shutdown` is cancelled. Other
+    /// tasks it needs are added to `tasks`, and a dedicated runtime it runs
+    /// on is left in `api_runtime`.
     async fn start_api(
         &self,
         core: &Arc<DrasiLib>,
         config_persistence: Option<Arc<ConfigPersistence>>,
-    ) -> Result<()> {
+        shutdown: &CancellationToken,
+        tasks: &mut Vec<tokio::task::JoinHandle<()>>,
+        api_runtime: &mut Option<ApiRuntime>,
+    ) -> Result<Vec<(SocketAddr, tokio::task::JoinHandle<()>)>> {
         // Create OpenAPI documentation
-        let openapi = api::ApiDoc::openapi();
+        let mut openapi = api::ApiDoc::openapi();
+        self.openapi.apply(&mut openapi);
+        let docs: Router = if self.docs.enabled {
+            SwaggerUi::new("/docs")
+                .url("/api-docs/openapi.json", openapi.clone())
+                .into()
+        } else {
+            info!("Swagger UI and OpenAPI document disabled (docs.enabled: false)");
+            Router::new()
+        };
         let app = Router::new()
             .route("/health", get(api::health_check))
+            .route("/about", get(api::about::about))
+            .route(
+                "/admin/startup-report",
+                get(api::startup_report::startup_report),
+            )
+            .route("/admin/usage", get(api::usage::usage))
+            .route("/config", patch(api::config_patch::patch_config))
             .route("/sources", get(api::list_sources))
             .route("/sources", post(api::create_source_handler))
             .route("/sources/:id", get(api::get_source))
+            .route("/sources/:id", put(api::replace_source))
             .route("/sources/:id", axum::routing::delete(api::delete_source))
+            .route("/sources/:id/history", get(api::get_source_history))
             .route("/sources/:id/start", post(api::start_source))
             .route("/sources/:id/stop", post(api::stop_source))
+            .route("/sources/:id/restore", post(api::restore_source))
+            .route("/sources/:id/enable", post(api::enable_source))
+            .route("/sources/:id/disable", post(api::disable_source))
             .route("/queries", get(api::list_queries))
             .route("/queries", post(api::create_query))
+            .route(
+                "/queries/validate-joins",
+                post(api::joins::validate_joins_handler),
+            )
             .route("/queries/:id", get(api::get_query))
+            .route("/queries/:id", put(api::replace_query))
             .route("/queries/:id", axum::routing::delete(api::delete_query))
             .route("/queries/:id/start", post(api::start_query))
             .route("/queries/:id/stop", post(api::stop_query))
+            .route("/queries/:id/restore", post(api::restore_query))
+            .route("/queries/:id/enable", post(api::enable_query))
+            .route("/queries/:id/disable", post(api::disable_query))
+            .route("/queries/:id/joins", get(api::joins::get_query_joins))
+            .route("/queries/:id/joins", put(api::joins::replace_query_joins))
             .route("/queries/:id/results", get(api::get_query_results))
+            .route(
+                "/queries/:id/results/diff",
+                get(api::results_diff::get_query_results_diff),
+            )
             .route("/reactions", get(api::list_reactions))
             .route("/reactions", post(api::create_reaction_handler))
             .route("/reactions/:id", get(api::get_reaction))
@@ -274,25 +673,347 @@ impl DrasiServer {
             )
             .route("/reactions/:id/start", post(api::start_reaction))
             .route("/reactions/:id/stop", post(api::stop_reaction))
-            .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", openapi.clone()))
+            .route("/reactions/:id/restore", post(api::restore_reaction))
+            .route("/reactions/:id/enable", post(api::enable_reaction))
+            .route("/reactions/:id/disable", post(api::disable_reaction))
+            .merge(docs);
+
+        let authenticator = api::auth::Authenticator::new(
+            self.api_keys.clone(),
+            self.jwt
+                .clone()
+                .map(api::jwt::JwtValidator::new)
+                .transpose()?,
+        )
+        .with_protected_docs(self.docs.require_auth);
+
+        // Profiling ties up a thread for minutes, so it is only served to
+        // clients that authenticate
+        #[cfg(all(feature = "pprof", unix))]
+        let app = if authenticator.is_open() {
+            info!("CPU profiling endpoint disabled: it needs api_keys or jwt");
+            app
+        } else {
+            app.route(api::auth::PROFILE_PATH, get(api::pprof::cpu_profile))
+        };
+
+        let app = app
+            .layer(axum::middleware::from_fn_with_state(
+                api::idempotency::IdempotencyStore::default(),
+                api::idempotency::idempotency_middleware,
+            ))
+            // Outside idempotency, so rejected requests are not recorded
+            .layer(axum::middleware::from_fn_with_state(
+                authenticator,
+                api::auth::auth_middleware,
+            ))
             .layer(CorsLayer::permissive())
             // Inject DrasiLib for handlers to use
             .layer(Extension(core.clone()))
             .layer(Extension(self.read_only.clone()))
-            .layer(Extension(config_persistence));
-
-        let addr = format!("{}:{}", self.host, self.port);
-        info!("Starting web API on {addr}");
-        info!("Swagger UI available at http://{addr}/docs/");
+            .layer(Extension(api::joins::StrictJoins(self.strict_joins)))
+            .layer(Extension(api::results_diff::ResultLogs::default()))
+            .layer(Extension(self.about.clone()))
+            .layer(Extension(self.startup_report.clone()))
+            .layer(Extension(self.index_dir.clone()))
+            .layer(Extension(config_persistence))
+            .layer(Extension(self.registry.clone()))
+            // Outermost, so every response carries the request ID
+            .layer(axum::middleware::from_fn(
+                api::request_id::request_id_middleware,
+            ));
+
+        let runtime = match self.api_worker_threads {
+            Some(threads) => {
+                info!("Running the web API on a dedicated runtime ({threads} worker threads)");
+                let (handle, dedicated) = spawn_api_runtime(threads)?;
+                *api_runtime = Some(dedicated);
+                handle
+            }
+            None => tokio::runtime::Handle::current(),
+        };
 
-        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        if !self.api_keys.is_empty() {
+            info!(
+                "API key authentication enabled for requests that change the server ({} key(s))",
+                self.api_keys.len()
+            );
+        }
+        if let Some(jwt) = &self.jwt {
+            info!(
+                "JWT authentication enabled for requests that change the server (issuer {}, audience {})",
+                jwt.issuer, jwt.audience
+            );
+        }
 
-        tokio::spawn(async move {
-            if let Err(e) = axum::serve(listener, app).await {
-                error!("Web API server error: {e}");
+        let rustls = match &self.tls {
+            Some(tls) => {
+                let rustls = tls.load()?;
+                tasks.extend(tls.reload_on_sighup(rustls.clone(), shutdown.clone())?);
+                info!(
+                    "Serving the web API over HTTPS with {}",
+                    tls.cert_path.display()
+                );
+                if let Some(ca) = &tls.client_ca_path {
+                    info!(
+                        "Verifying client certificates against {} ({})",
+                        ca.display(),
+                        if tls.require_client_cert {
+                            "required"
+                        } else {
+                            "optional"
+                        }
+                    );
+                }
+                Some(rustls)
             }
-        });
+            None => None,
+        };
+        let scheme = if rustls.is_some() { "https" } else { "http" };
+
+        // Bind every address before serving any, so a bad host fails startup
+        let listeners = bind_api(&self.host, self.port).await?;
+        let mut served = Vec::with_capacity(listeners.len());
+        for listener in listeners {
+            let addr = listener.local_addr()?;
+            info!("Starting web API on {addr}");
+            if self.docs.enabled {
+                info!("Swagger UI available at {scheme}://{addr}/docs/");
+            }
+
+            let app = app.clone();
+            let rustls = rustls.clone();
+            let shutdown = shutdown.clone();
+            let task = runtime.spawn(async move {
+                // Registered here, with the runtime that serves it
+                let result = match rustls {
+                    Some(rustls) => {
+                        let handle = axum_server::Handle::new();
+                        let graceful = handle.clone();
+                        tokio::spawn(async move {
+                            shutdown.cancelled().await;
+                            graceful.graceful_shutdown(Some(SHUTDOWN_TIMEOUT));
+                        });
+                        axum_server::from_tcp(listener)
+                            .handle(handle)
+                            .acceptor(api::tls::ClientCertAcceptor::new(rustls))
+                            .serve(app.into_make_service())
+                            .await
+                    }
+                    None => match tokio::net::TcpListener::from_std(listener) {
+                        Ok(listener) => {
+                            axum::serve(listener, app)
+                                .with_graceful_shutdown(shutdown.cancelled_owned())
+                                .await
+                        }
+                        Err(e) => Err(e),
+                    },
+                };
+                if let Err(e) = result {
+                    error!("Web API server error on {addr}: {e}");
+                }
+            });
+            served.push((addr, task));
+        }
+
+        Ok(served)
+    }
+}
+
+/// How long [`RunningServer::stop`] lets in-flight API requests finish
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// A started server, returned by [`DrasiServer::start`].
+pub struct RunningServer {
+    core: Arc<DrasiLib>,
+    config_persistence: Option<Arc<ConfigPersistence>>,
+    api: Vec<(SocketAddr, tokio::task::JoinHandle<()>)>,
+    /// The runtime the API runs on, if it has one of its own
+    api_runtime: Option<ApiRuntime>,
+    shutdown: CancellationToken,
+    /// Supervisors and other background tasks, ended by `shutdown`
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl RunningServer {
+    /// First address the API is served on; `None` with the API disabled
+    pub fn addr(&self) -> Option<SocketAddr> {
+        self.api.first().map(|(addr, _)| *addr)
+    }
+
+    /// Every address the API is served on
+    pub fn addrs(&self) -> Vec<SocketAddr> {
+        self.api.iter().map(|(addr, _)| *addr).collect()
+    }
+
+    pub fn core(&self) -> &Arc<DrasiLib> {
+        &self.core
+    }
+
+    /// Stop serving the API, write pending configuration changes and stop
+    /// every component.
+    ///
+    /// The API stops accepting connections and waits up to ten seconds for
+    /// requests in flight; connections still open after that are dropped.
+    /// Background supervisors are stopped before the components.
+    pub async fn stop(self) -> Result<()> {
+        self.shutdown.cancel();
+        for (addr, mut task) in self.api {
+            if tokio::time::timeout(SHUTDOWN_TIMEOUT, &mut task)
+                .await
+                .is_err()
+            {
+                warn!("Web API on {addr} did not shut down in time, closing its connections");
+                task.abort();
+            }
+        }
+        if let Some(runtime) = self.api_runtime {
+            runtime.shutdown().await;
+        }
+        for task in self.tasks {
+            let _ = task.await;
+        }
+        if let Some(persistence) = &self.config_persistence {
+            if let Err(e) = persistence.flush().await {
+                error!("Failed to write pending configuration changes: {e}");
+            }
+        }
+        self.core.stop().await?;
         Ok(())
     }
 }
+
+/// Components created at once during startup
+const STARTUP_CONCURRENCY: usize = 8;
+
+/// Record the components the core has just started on its own, so their
+/// uptime counts from startup.
+async fn mark_auto_started(core: &DrasiLib, registry: &ComponentRegistry) {
+    let sources = core.list_sources().await.unwrap_or_default();
+    let queries = core.list_queries().await.unwrap_or_default();
+    let reactions = core.list_reactions().await.unwrap_or_default();
+    let started = [
+        (ComponentKind::Source, sources),
+        (ComponentKind::Query, queries),
+        (ComponentKind::Reaction, reactions),
+    ];
+    for (kind, statuses) in started {
+        for (id, status) in statuses {
+            if matches!(status, ComponentStatus::Running | ComponentStatus::Starting) {
+                registry.mark_started(kind, &id).await;
+            }
+        }
+    }
+}
+
+/// Create sources with bounded concurrency, returning results in input order.
+async fn create_sources(configs: Vec<SourceConfig>) -> Vec<Result<Box<dyn Source + 'static>>> {
+    futures::stream::iter(configs)
+        .map(|config| async move { create_source(config).await.map_err(anyhow::Error::from) })
+        .buffered(STARTUP_CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// Create reactions with bounded concurrency, returning results in input
+/// order. Reaction constructors are synchronous, so each runs on a blocking
+/// thread.
+async fn create_reactions(
+    configs: Vec<ReactionConfig>,
+) -> Vec<Result<Box<dyn Reaction + 'static>>> {
+    futures::stream::iter(configs)
+        .map(|config| async move {
+            tokio::task::spawn_blocking(move || create_reaction(config))
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|reaction| reaction.map_err(anyhow::Error::from))
+        })
+        .buffered(STARTUP_CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// Bind the API on every address the configured hosts resolve to.
+///
+/// IPv6 sockets are dual-stack, so `::` on its own also accepts IPv4
+/// connections, unless an IPv4 address is bound on the same port as well.
+async fn bind_api(hosts: &BindHosts, port: u16) -> Result<Vec<std::net::TcpListener>> {
+    let mut addrs: Vec<SocketAddr> = Vec::new();
+    for host in hosts.iter() {
+        let resolved = tokio::net::lookup_host((unbracketed(host), port))
+            .await
+            .with_context(|| format!("Could not resolve API host '{host}'"))?;
+        for addr in resolved {
+            if !addrs.contains(&addr) {
+                addrs.push(addr);
+            }
+        }
+    }
+
+    let dual_stack = !addrs.iter().any(SocketAddr::is_ipv4);
+    addrs
+        .into_iter()
+        .map(|addr| {
+            bind_listener(addr, dual_stack)
+                .with_context(|| format!("Failed to bind web API on {addr}"))
+        })
+        .collect()
+}
+
+fn bind_listener(addr: SocketAddr, dual_stack: bool) -> std::io::Result<std::net::TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    if addr.is_ipv6() {
+        socket.set_only_v6(!dual_stack)?;
+    }
+    // As tokio's TcpListener::bind does, so a restart can rebind right away
+    #[cfg(not(windows))]
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    Ok(socket.into())
+}
+
+/// A runtime dedicated to the API, kept on a thread of its own
+struct ApiRuntime {
+    stop: tokio::sync::oneshot::Sender<()>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+impl ApiRuntime {
+    /// Shut the runtime down and wait for its threads to finish.
+    async fn shutdown(self) {
+        let _ = self.stop.send(());
+        let thread = self.thread;
+        if !matches!(
+            tokio::task::spawn_blocking(move || thread.join()).await,
+            Ok(Ok(()))
+        ) {
+            warn!("The API runtime did not shut down cleanly");
+        }
+    }
+}
+
+/// Start a multi-thread runtime for the API on its own thread and return a
+/// handle to spawn onto it. It runs until it is shut down or the returned
+/// [`ApiRuntime`] is dropped.
+fn spawn_api_runtime(worker_threads: usize) -> Result<(tokio::runtime::Handle, ApiRuntime)> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(worker_threads)
+        .thread_name("drasi-api")
+        .enable_all()
+        .build()
+        .context("Failed to create the API runtime")?;
+    let handle = runtime.handle().clone();
+    let (stop, stopped) = tokio::sync::oneshot::channel();
+    // The runtime is moved off this thread: dropping it inside the main
+    // runtime would panic
+    let thread = std::thread::Builder::new()
+        .name("drasi-api-runtime".to_string())
+        .spawn(move || {
+            let _ = runtime.block_on(stopped);
+            runtime.shutdown_timeout(SHUTDOWN_TIMEOUT);
+        })
+        .context("Failed to start the API runtime thread")?;
+    Ok((handle, ApiRuntime { stop, thread }))
+}
@@ -0,0 +1,208 @@
+// Copyright 2025 The Drasi Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for testing applications that embed the server, enabled by the
+//! `testing` feature.
+//!
+//! [`MockSource`] and [`MockReaction`] are in-memory components that can be
+//! passed to [`crate::DrasiServerBuilder`]. A mock source emits only the
+//! events pushed to it with [`MockSource::dispatch`], so a test controls
+//! exactly what its queries see, and [`wait_for_results`] waits for a query
+//! to produce the expected rows.
+//!
+//! ```ignore
+//! let source = MockSource::new("orders");
+//! let core = DrasiServerBuilder::new()
+//!     .with_source(source.clone())
+//!     .with_query_config("open", "MATCH (o:Order) RETURN o.id", vec!["orders".into()])
+//!     .build_core()
+//!     .await?;
+//! core.start().await?;
+//! source.dispatch(event).await?;
+//! let rows = wait_for_results(&core, "open", Duration::from_secs(5), |rows| rows.len() == 1).await?;
+//! ```
+
+use async_trait::async_trait;
+use drasi_lib::channels::dispatcher::{ChangeDispatcher, ChannelChangeDispatcher};
+use drasi_lib::channels::{
+    ComponentEventSender, ComponentStatus, SourceEventWrapper, SubscriptionResponse,
+};
+use drasi_lib::plugin_core::{QuerySubscriber, Reaction, Source};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Events a mock source can hold before slow queries miss them
+const DISPATCH_CAPACITY: usize = 100;
+
+/// How often [`wait_for_results`] reads the results
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A source that emits only the events pushed to it.
+///
+/// Clones share their state, so a test can keep one to push events into
+/// after handing the other to the builder.
+#[derive(Clone)]
+pub struct MockSource {
+    id: String,
+    status: Arc<RwLock<ComponentStatus>>,
+    dispatcher: Arc<ChannelChangeDispatcher<SourceEventWrapper>>,
+}
+
+impl MockSource {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            status: Arc::new(RwLock::new(ComponentStatus::Stopped)),
+            dispatcher: Arc::new(ChannelChangeDispatcher::new(DISPATCH_CAPACITY)),
+        }
+    }
+
+    /// Send an event to every query subscribed to this source.
+    pub async fn dispatch(&self, event: SourceEventWrapper) -> anyhow::Result<()> {
+        self.dispatcher.dispatch_change(Arc::new(event)).await
+    }
+}
+
+#[async_trait]
+impl Source for MockSource {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn type_name(&self) -> &str {
+        "mock"
+    }
+
+    fn properties(&self) -> HashMap<String, serde_json::Value> {
+        HashMap::new()
+    }
+
+    async fn start(&self) -> anyhow::Result<()> {
+        *self.status.write().await = ComponentStatus::Running;
+        Ok(())
+    }
+
+    async fn stop(&self) -> anyhow::Result<()> {
+        *self.status.write().await = ComponentStatus::Stopped;
+        Ok(())
+    }
+
+    async fn status(&self) -> ComponentStatus {
+        self.status.read().await.clone()
+    }
+
+    async fn subscribe(
+        &self,
+        settings: drasi_lib::config::SourceSubscriptionSettings,
+    ) -> anyhow::Result<SubscriptionResponse> {
+        let receiver = self.dispatcher.create_receiver().await?;
+        Ok(SubscriptionResponse {
+            query_id: settings.query_id,
+            source_id: self.id.clone(),
+            receiver,
+            bootstrap_receiver: None,
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn inject_event_tx(&self, _tx: ComponentEventSender) {}
+}
+
+/// A reaction that subscribes to queries and ignores their results.
+///
+/// Read results with [`wait_for_results`] instead.
+#[derive(Clone)]
+pub struct MockReaction {
+    id: String,
+    queries: Vec<String>,
+    status: Arc<RwLock<ComponentStatus>>,
+}
+
+impl MockReaction {
+    pub fn new(id: &str, queries: Vec<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            queries,
+            status: Arc::new(RwLock::new(ComponentStatus::Stopped)),
+        }
+    }
+}
+
+#[async_trait]
+impl Reaction for MockReaction {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn type_name(&self) -> &str {
+        "log"
+    }
+
+    fn properties(&self) -> HashMap<String, serde_json::Value> {
+        HashMap::new()
+    }
+
+    fn query_ids(&self) -> Vec<String> {
+        self.queries.clone()
+    }
+
+    async fn inject_query_subscriber(&self, _query_subscriber: Arc<dyn QuerySubscriber>) {}
+
+    async fn start(&self) -> anyhow::Result<()> {
+        *self.status.write().await = ComponentStatus::Running;
+        Ok(())
+    }
+
+    async fn stop(&self) -> anyhow::Result<()> {
+        *self.status.write().await = ComponentStatus::Stopped;
+        Ok(())
+    }
+
+    async fn status(&self) -> ComponentStatus {
+        self.status.read().await.clone()
+    }
+
+    async fn inject_event_tx(&self, _tx: ComponentEventSender) {}
+}
+
+/// Wait until a query's results satisfy `done`, and return them.
+///
+/// Fails with the last results read if `timeout` passes first.
+pub async fn wait_for_results(
+    core: &drasi_lib::DrasiLib,
+    query_id: &str,
+    timeout: Duration,
+    done: impl Fn(&[serde_json::Value]) -> bool,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        // The query may not be running yet
+        let results = core.get_query_results(query_id).await.unwrap_or_default();
+        if done(&results) {
+            return Ok(results);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(anyhow::anyhow!(
+                "Query '{query_id}' did not produce the expected results within {timeout:?}; last results: {}",
+                serde_json::Value::from(results)
+            ));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
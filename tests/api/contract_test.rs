@@ -208,19 +208,14 @@ mod contract_tests {
     }
 
     #[test]
-    fn test_idempotent_response_format() {
-        // Test the response format for idempotent operations
-        let status_json = json!({
-            "message": "Source 'test-source' already exists"
-        });
-        let response: ApiResponse<serde_json::Value> = ApiResponse::success(status_json);
+    fn test_already_exists_response_format() {
+        // Creating a component whose ID is taken is an error
+        let response: ApiResponse<serde_json::Value> =
+            ApiResponse::error("Source 'test-source' already exists".to_string());
 
         let json = serde_json::to_value(&response).unwrap();
-        assert_eq!(json["success"], true);
-        assert!(json["data"]["message"]
-            .as_str()
-            .unwrap()
-            .contains("already exists"));
+        assert_eq!(json["success"], false);
+        assert!(json["error"].as_str().unwrap().contains("already exists"));
     }
 
     #[test]
@@ -6,7 +6,7 @@
 
 #![allow(clippy::unwrap_used)]
 
-use crate::test_utils::{create_mock_reaction, create_mock_source};
+use crate::test_utils::{MockReaction, MockSource};
 use axum::{
     body::{to_bytes, Body},
     extract::Extension,
@@ -24,13 +24,13 @@ async fn create_test_router() -> (Router, Arc<drasi_lib::DrasiLib>) {
     use drasi_lib::DrasiLib;
 
     // Create mock source instances
-    let test_source = create_mock_source("test-source");
-    let query_source = create_mock_source("query-source");
-    let auto_source = create_mock_source("auto-source");
+    let test_source = MockSource::new("test-source");
+    let query_source = MockSource::new("query-source");
+    let auto_source = MockSource::new("auto-source");
 
     // Create mock reaction instances
-    let test_reaction = create_mock_reaction("test-reaction", vec!["reaction-query".to_string()]);
-    let auto_reaction = create_mock_reaction("auto-reaction", vec!["auto-query".to_string()]);
+    let test_reaction = MockReaction::new("test-reaction", vec!["reaction-query".to_string()]);
+    let auto_reaction = MockReaction::new("auto-reaction", vec!["auto-query".to_string()]);
 
     // Create a minimal DrasiLib using the builder with mock instances
     let core = DrasiLib::builder()
@@ -55,6 +55,11 @@ async fn create_test_router() -> (Router, Arc<drasi_lib::DrasiLib>) {
     let router = Router::new()
         // Health endpoint
         .route("/health", axum::routing::get(api::handlers::health_check))
+        // Config endpoint
+        .route(
+            "/config",
+            axum::routing::patch(api::config_patch::patch_config),
+        )
         // Source endpoints
         .route("/sources", axum::routing::get(api::handlers::list_sources))
         .route(
@@ -77,6 +82,18 @@ async fn create_test_router() -> (Router, Arc<drasi_lib::DrasiLib>) {
             "/sources/:id/stop",
             axum::routing::post(api::handlers::stop_source),
         )
+        .route(
+            "/sources/:id/restore",
+            axum::routing::post(api::handlers::restore_source),
+        )
+        .route(
+            "/sources/:id/enable",
+            axum::routing::post(api::handlers::enable_source),
+        )
+        .route(
+            "/sources/:id/disable",
+            axum::routing::post(api::handlers::disable_source),
+        )
         // Query endpoints
         .route("/queries", axum::routing::get(api::handlers::list_queries))
         .route("/queries", axum::routing::post(api::handlers::create_query))
@@ -93,6 +110,18 @@ async fn create_test_router() -> (Router, Arc<drasi_lib::DrasiLib>) {
             "/queries/:id/stop",
             axum::routing::post(api::handlers::stop_query),
         )
+        .route(
+            "/queries/:id/restore",
+            axum::routing::post(api::handlers::restore_query),
+        )
+        .route(
+            "/queries/:id/enable",
+            axum::routing::post(api::handlers::enable_query),
+        )
+        .route(
+            "/queries/:id/disable",
+            axum::routing::post(api::handlers::disable_query),
+        )
         .route(
             "/queries/:id/results",
             axum::routing::get(api::handlers::get_query_results),
@@ -122,10 +151,27 @@ async fn create_test_router() -> (Router, Arc<drasi_lib::DrasiLib>) {
             "/reactions/:id/stop",
             axum::routing::post(api::handlers::stop_reaction),
         )
+        .route(
+            "/reactions/:id/restore",
+            axum::routing::post(api::handlers::restore_reaction),
+        )
+        .route(
+            "/reactions/:id/enable",
+            axum::routing::post(api::handlers::enable_reaction),
+        )
+        .route(
+            "/reactions/:id/disable",
+            axum::routing::post(api::handlers::disable_reaction),
+        )
         // Add extensions using new architecture
         .layer(Extension(core.clone()))
         .layer(Extension(read_only))
-        .layer(Extension(config_persistence));
+        .layer(Extension(api::joins::StrictJoins::default()))
+        .layer(Extension(api::results_diff::ResultLogs::default()))
+        .layer(Extension(config_persistence))
+        .layer(Extension(Arc::new(
+            drasi_server::registry::ComponentRegistry::new(),
+        )));
 
     (router, core)
 }
@@ -391,6 +437,50 @@ async fn test_dynamic_source_creation_via_api() {
         .contains("created successfully"));
 }
 
+#[tokio::test]
+async fn test_source_details_include_kind_after_api_creation() {
+    let (router, _) = create_test_router().await;
+
+    let source_config = json!({
+        "kind": "mock",
+        "id": "described-source",
+        "auto_start": false
+    });
+
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/sources")
+                .header("content-type", "application/json")
+                .body(Body::from(source_config.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/sources/described-source")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"]["id"], "described-source");
+    assert_eq!(json["data"]["kind"], "mock");
+    // Not running, so no uptime is reported
+    assert!(json["data"].get("uptime_seconds").is_none());
+}
+
 #[tokio::test]
 async fn test_dynamic_reaction_creation_via_api() {
     let (router, _) = create_test_router().await;
@@ -458,6 +548,10 @@ async fn test_error_handling() {
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["success"], false);
+    assert_eq!(json["code"], "NOT_FOUND");
 }
 
 #[tokio::test]
@@ -472,7 +566,7 @@ async fn test_query_results_endpoint() {
         .build();
     core.add_query(query_config.clone()).await.unwrap();
 
-    // Try to get results - should return error (not exposed in public API)
+    // The query is not running, so its results cannot be fetched
     let response = router
         .clone()
         .oneshot(
@@ -484,10 +578,11 @@ async fn test_query_results_endpoint() {
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
     assert_eq!(json["success"], false);
+    assert_eq!(json["code"], "VALIDATION");
     // The error should contain some information about why results can't be fetched
     assert!(json["error"].is_string());
 
@@ -505,3 +600,72 @@ async fn test_query_results_endpoint() {
 
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
+
+#[tokio::test]
+async fn test_patch_config_creates_and_replaces_components() {
+    let (router, core) = create_test_router().await;
+
+    let patch = json!({
+        "sources": [
+            {"kind": "mock", "id": "patched-source", "auto_start": false}
+        ],
+        "queries": [
+            {
+                "id": "patched-query",
+                "query": "MATCH (n) RETURN n",
+                "sources": [{"source_id": "patched-source"}],
+                "auto_start": false
+            }
+        ]
+    });
+
+    for expected in ["created", "replaced"] {
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/config")
+                    .header("content-type", "application/json")
+                    .body(Body::from(patch.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["success"], true);
+        assert_eq!(
+            json["data"]["changes"],
+            json!([
+                format!("{expected} source 'patched-source'"),
+                format!("{expected} query 'patched-query'")
+            ])
+        );
+    }
+
+    assert!(core.get_source_status("patched-source").await.is_ok());
+    assert!(core.get_query_config("patched-query").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_patch_config_rejects_restart_only_settings() {
+    let (router, _) = create_test_router().await;
+
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method("PATCH")
+                .uri("/config")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"port": 9090}).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Unknown fields fail JSON extraction before the handler runs
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
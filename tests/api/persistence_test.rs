@@ -27,7 +27,7 @@ async fn test_persistence_creates_config_file_on_save() {
 
     // Create initial config
     let config = DrasiServerConfig {
-        host: ConfigValue::Static("127.0.0.1".to_string()),
+        host: ConfigValue::Static("127.0.0.1".into()),
         port: ConfigValue::Static(8080),
         log_level: ConfigValue::Static("info".to_string()),
         disable_persistence: false,
@@ -47,7 +47,7 @@ async fn test_persistence_creates_config_file_on_save() {
         drasi_server::load_config_file(&config_path).expect("Failed to load config");
     assert_eq!(
         loaded_config.host,
-        ConfigValue::Static("127.0.0.1".to_string())
+        ConfigValue::Static("127.0.0.1".into())
     );
     assert_eq!(loaded_config.port, ConfigValue::Static(8080));
     assert!(!loaded_config.disable_persistence);
@@ -60,7 +60,7 @@ async fn test_persistence_disabled_by_flag() {
 
     // Create config with persistence disabled
     let config = DrasiServerConfig {
-        host: ConfigValue::Static("127.0.0.1".to_string()),
+        host: ConfigValue::Static("127.0.0.1".into()),
         port: ConfigValue::Static(8080),
         log_level: ConfigValue::Static("info".to_string()),
         disable_persistence: true, // Disabled
@@ -85,7 +85,7 @@ async fn test_persistence_saves_complete_configuration() {
 
     // Create config with all components
     let config = DrasiServerConfig {
-        host: ConfigValue::Static("0.0.0.0".to_string()),
+        host: ConfigValue::Static("0.0.0.0".into()),
         port: ConfigValue::Static(9090),
         log_level: ConfigValue::Static("debug".to_string()),
         disable_persistence: false,
@@ -144,7 +144,7 @@ async fn test_persistence_saves_complete_configuration() {
     // Verify server settings
     assert_eq!(
         loaded_config.host,
-        ConfigValue::Static("0.0.0.0".to_string())
+        ConfigValue::Static("0.0.0.0".into())
     );
     assert_eq!(loaded_config.port, ConfigValue::Static(9090));
     assert_eq!(
@@ -190,7 +190,7 @@ async fn test_persistence_atomic_write() {
 
     // Create initial config
     let initial_config = DrasiServerConfig {
-        host: ConfigValue::Static("127.0.0.1".to_string()),
+        host: ConfigValue::Static("127.0.0.1".into()),
         port: ConfigValue::Static(8080),
         log_level: ConfigValue::Static("info".to_string()),
         disable_persistence: false,
@@ -206,7 +206,7 @@ async fn test_persistence_atomic_write() {
 
     // Create updated config
     let updated_config = DrasiServerConfig {
-        host: ConfigValue::Static("0.0.0.0".to_string()),
+        host: ConfigValue::Static("0.0.0.0".into()),
         port: ConfigValue::Static(9090),
         log_level: ConfigValue::Static("debug".to_string()),
         disable_persistence: false,
@@ -251,7 +251,7 @@ async fn test_persistence_atomic_write() {
 async fn test_persistence_validation_before_save() {
     // Create invalid config (port = 0)
     let invalid_config = DrasiServerConfig {
-        host: ConfigValue::Static("127.0.0.1".to_string()),
+        host: ConfigValue::Static("127.0.0.1".into()),
         port: ConfigValue::Static(0), // Invalid port
         log_level: ConfigValue::Static("info".to_string()),
         disable_persistence: false,
@@ -291,7 +291,7 @@ reactions: []
     let config = drasi_server::load_config_file(&config_path).expect("Failed to load YAML config");
     assert_eq!(
         config.host,
-        ConfigValue::Static("127.0.0.1".to_string())
+        ConfigValue::Static("127.0.0.1".into())
     );
     assert_eq!(config.sources.len(), 1);
     assert_eq!(config.sources[0].id, "test-source");
@@ -312,7 +312,7 @@ reactions: []
 
     // Load and verify defaults are applied
     let config = drasi_server::load_config_file(&config_path).expect("Failed to load config");
-    assert_eq!(config.host, ConfigValue::Static("0.0.0.0".to_string())); // Default
+    assert_eq!(config.host, ConfigValue::Static("0.0.0.0".into())); // Default
     assert_eq!(config.port, ConfigValue::Static(8080)); // Default
     assert_eq!(
         config.log_level,
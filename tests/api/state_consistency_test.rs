@@ -6,13 +6,13 @@
 //! Note: Sources and reactions must be provided as instances when building DrasiLib.
 //! Dynamic creation via config is not supported.
 
-use crate::test_utils::{create_mock_reaction, create_mock_source};
+use crate::test_utils::{MockReaction, MockSource};
 use drasi_lib::{DrasiLib, Query};
 use std::sync::Arc;
 
 #[tokio::test]
 async fn test_server_start_stop_cycle() {
-    let test_source = create_mock_source("test-source");
+    let test_source = MockSource::new("test-source");
 
     let core = DrasiLib::builder()
         .with_id("test-server")
@@ -43,8 +43,8 @@ async fn test_server_start_stop_cycle() {
 
 #[tokio::test]
 async fn test_components_with_auto_start() {
-    let test_source = create_mock_source("test-source");
-    let test_reaction = create_mock_reaction("test-reaction", vec!["test-query".to_string()]);
+    let test_source = MockSource::new("test-source");
+    let test_reaction = MockReaction::new("test-reaction", vec!["test-query".to_string()]);
 
     let query = Query::cypher("test-query")
         .query("MATCH (n) RETURN n")
@@ -80,8 +80,8 @@ async fn test_components_with_auto_start() {
 
 #[tokio::test]
 async fn test_components_without_auto_start() {
-    let test_source = create_mock_source("test-source");
-    let test_reaction = create_mock_reaction("test-reaction", vec!["test-query".to_string()]);
+    let test_source = MockSource::new("test-source");
+    let test_reaction = MockReaction::new("test-reaction", vec!["test-query".to_string()]);
 
     let query = Query::cypher("test-query")
         .query("MATCH (n) RETURN n")
@@ -115,7 +115,7 @@ async fn test_components_without_auto_start() {
 
 #[tokio::test]
 async fn test_restart_with_components() {
-    let restart_source = create_mock_source("restart-source");
+    let restart_source = MockSource::new("restart-source");
 
     let query = Query::cypher("restart-query")
         .query("MATCH (n) RETURN n")
@@ -159,8 +159,8 @@ async fn test_restart_with_components() {
 
 #[tokio::test]
 async fn test_multiple_query_sources() {
-    let source1 = create_mock_source("source1");
-    let source2 = create_mock_source("source2");
+    let source1 = MockSource::new("source1");
+    let source2 = MockSource::new("source2");
 
     let query = Query::cypher("multi-source-query")
         .query("MATCH (n) RETURN n")
@@ -194,8 +194,8 @@ async fn test_multiple_query_sources() {
 
 #[tokio::test]
 async fn test_multiple_reaction_queries() {
-    let test_source = create_mock_source("test-source");
-    let multi_query_reaction = create_mock_reaction(
+    let test_source = MockSource::new("test-source");
+    let multi_query_reaction = MockReaction::new(
         "multi-query-reaction",
         vec!["query1".to_string(), "query2".to_string()],
     );
@@ -237,8 +237,8 @@ async fn test_multiple_reaction_queries() {
 
 #[tokio::test]
 async fn test_query_with_joins() {
-    let join_source1 = create_mock_source("join-source1");
-    let join_source2 = create_mock_source("join-source2");
+    let join_source1 = MockSource::new("join-source1");
+    let join_source2 = MockSource::new("join-source2");
 
     // For joins, we need to use the lower-level QueryConfig since the builder API
     // may not support join configuration yet
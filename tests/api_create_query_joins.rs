@@ -5,7 +5,8 @@ use drasi_lib::{
     config::{QueryJoinConfig, QueryJoinKeyConfig},
     DrasiLib, Query, QueryConfig,
 };
-use drasi_server::api::handlers::create_query;
+use drasi_server::api::handlers::{create_query, CreateQueryParams};
+use drasi_server::api::joins::StrictJoins;
 use std::sync::Arc;
 
 // Helper to build a minimal QueryConfig with joins
@@ -55,6 +56,8 @@ async fn test_create_query_with_joins_via_handler() {
         Extension(core.clone()),
         Extension(read_only.clone()),
         Extension(config_persistence),
+        Extension(StrictJoins::default()),
+        axum::extract::Query(CreateQueryParams::default()),
         axum::Json(cfg.clone()),
     )
     .await
@@ -17,158 +17,15 @@
 //! Note: Sources and reactions must be provided as instances when building DrasiLib.
 //! Dynamic creation via config is not supported.
 
-use async_trait::async_trait;
-use drasi_lib::channels::dispatcher::ChangeDispatcher;
-use drasi_lib::channels::{ComponentEventSender, ComponentStatus, SubscriptionResponse};
-use drasi_lib::plugin_core::QuerySubscriber;
-use drasi_lib::plugin_core::Reaction as ReactionTrait;
-use drasi_lib::plugin_core::Source as SourceTrait;
+use drasi_server::testing::{MockReaction, MockSource};
 use drasi_server::DrasiServerBuilder;
-use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 use tokio::time::{timeout, Duration};
 
-/// A mock source for testing
-struct MockSource {
-    id: String,
-    status: Arc<RwLock<ComponentStatus>>,
-}
-
-impl MockSource {
-    fn new(id: &str) -> Self {
-        Self {
-            id: id.to_string(),
-            status: Arc::new(RwLock::new(ComponentStatus::Stopped)),
-        }
-    }
-}
-
-#[async_trait]
-impl SourceTrait for MockSource {
-    fn id(&self) -> &str {
-        &self.id
-    }
-
-    fn type_name(&self) -> &str {
-        "mock"
-    }
-
-    fn properties(&self) -> HashMap<String, serde_json::Value> {
-        HashMap::new()
-    }
-
-    async fn start(&self) -> anyhow::Result<()> {
-        *self.status.write().await = ComponentStatus::Running;
-        Ok(())
-    }
-
-    async fn stop(&self) -> anyhow::Result<()> {
-        *self.status.write().await = ComponentStatus::Stopped;
-        Ok(())
-    }
-
-    async fn status(&self) -> ComponentStatus {
-        self.status.read().await.clone()
-    }
-
-    async fn subscribe(
-        &self,
-        settings: drasi_lib::config::SourceSubscriptionSettings,
-    ) -> anyhow::Result<SubscriptionResponse> {
-        use drasi_lib::channels::dispatcher::ChannelChangeDispatcher;
-        let dispatcher =
-            ChannelChangeDispatcher::<drasi_lib::channels::SourceEventWrapper>::new(100);
-        let receiver = dispatcher.create_receiver().await?;
-        Ok(SubscriptionResponse {
-            query_id: settings.query_id,
-            source_id: self.id.clone(),
-            receiver,
-            bootstrap_receiver: None,
-        })
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-
-    async fn inject_event_tx(&self, _tx: ComponentEventSender) {
-        // No-op for testing
-    }
-}
-
-/// A mock reaction for testing
-struct MockReaction {
-    id: String,
-    queries: Vec<String>,
-    status: Arc<RwLock<ComponentStatus>>,
-}
-
-impl MockReaction {
-    fn new(id: &str, queries: Vec<String>) -> Self {
-        Self {
-            id: id.to_string(),
-            queries,
-            status: Arc::new(RwLock::new(ComponentStatus::Stopped)),
-        }
-    }
-}
-
-#[async_trait]
-impl ReactionTrait for MockReaction {
-    fn id(&self) -> &str {
-        &self.id
-    }
-
-    fn type_name(&self) -> &str {
-        "log"
-    }
-
-    fn properties(&self) -> HashMap<String, serde_json::Value> {
-        HashMap::new()
-    }
-
-    fn query_ids(&self) -> Vec<String> {
-        self.queries.clone()
-    }
-
-    async fn inject_query_subscriber(&self, _query_subscriber: Arc<dyn QuerySubscriber>) {
-        // No-op for testing
-    }
-
-    async fn start(&self) -> anyhow::Result<()> {
-        *self.status.write().await = ComponentStatus::Running;
-        Ok(())
-    }
-
-    async fn stop(&self) -> anyhow::Result<()> {
-        *self.status.write().await = ComponentStatus::Stopped;
-        Ok(())
-    }
-
-    async fn status(&self) -> ComponentStatus {
-        self.status.read().await.clone()
-    }
-
-    async fn inject_event_tx(&self, _tx: ComponentEventSender) {
-        // No-op for testing
-    }
-}
-
-/// Create a mock source for testing
-fn create_mock_source(id: &str) -> MockSource {
-    MockSource::new(id)
-}
-
-/// Create a mock reaction for testing
-fn create_mock_reaction(id: &str, queries: Vec<String>) -> MockReaction {
-    MockReaction::new(id, queries)
-}
-
 #[tokio::test]
 async fn test_basic_server_lifecycle() {
     // Create source instance
-    let test_source = create_mock_source("test-source");
+    let test_source = MockSource::new("test-source");
 
     // Create a basic server using the new builder API
     let server = DrasiServerBuilder::new()
@@ -191,8 +48,8 @@ async fn test_basic_server_lifecycle() {
 #[tokio::test]
 async fn test_server_with_components() {
     // Create source and reaction instances
-    let test_source = create_mock_source("test_source");
-    let test_reaction = create_mock_reaction("test_reaction", vec!["test_query".to_string()]);
+    let test_source = MockSource::new("test_source");
+    let test_reaction = MockReaction::new("test_reaction", vec!["test_query".to_string()]);
 
     // Create server with components using the new builder API
     let server = DrasiServerBuilder::new()
@@ -223,7 +80,7 @@ async fn test_server_with_components() {
 #[tokio::test]
 async fn test_source_lifecycle_operations() {
     // Create source instance
-    let test_source = create_mock_source("lifecycle_source");
+    let test_source = MockSource::new("lifecycle_source");
 
     // Start with server with source
     let server = DrasiServerBuilder::new()
@@ -261,7 +118,7 @@ async fn test_source_lifecycle_operations() {
 #[tokio::test]
 async fn test_server_with_api() {
     // Create source instance
-    let test_source = create_mock_source("api_source");
+    let test_source = MockSource::new("api_source");
 
     // Create server with API
     let _server = DrasiServerBuilder::new()
@@ -283,7 +140,7 @@ async fn test_config_persistence() {
     let config_file = "test_config_persistence.yaml";
 
     // Create source instance
-    let persist_source = create_mock_source("persist_source");
+    let persist_source = MockSource::new("persist_source");
 
     // Create server with config persistence
     let _server = DrasiServerBuilder::new()
@@ -303,11 +160,11 @@ async fn test_config_persistence() {
 #[tokio::test]
 async fn test_concurrent_start_stop_operations() {
     // Create multiple source instances
-    let source1 = create_mock_source("concurrent_source_1");
-    let source2 = create_mock_source("concurrent_source_2");
-    let source3 = create_mock_source("concurrent_source_3");
-    let source4 = create_mock_source("concurrent_source_4");
-    let source5 = create_mock_source("concurrent_source_5");
+    let source1 = MockSource::new("concurrent_source_1");
+    let source2 = MockSource::new("concurrent_source_2");
+    let source3 = MockSource::new("concurrent_source_3");
+    let source4 = MockSource::new("concurrent_source_4");
+    let source5 = MockSource::new("concurrent_source_5");
 
     // Start with server with all sources pre-registered
     let server = DrasiServerBuilder::new()
@@ -370,7 +227,7 @@ async fn test_concurrent_start_stop_operations() {
 #[tokio::test]
 async fn test_graceful_shutdown_timeout() {
     // Create source instance
-    let timeout_source = create_mock_source("timeout_source");
+    let timeout_source = MockSource::new("timeout_source");
 
     // Create server with a source
     let server = DrasiServerBuilder::new()
@@ -389,3 +246,22 @@ async fn test_graceful_shutdown_timeout() {
     assert!(shutdown_result.is_ok(), "Shutdown timed out");
     shutdown_result.expect("Timeout").expect("Shutdown failed");
 }
+
+#[tokio::test]
+async fn test_ephemeral_server_serves_api_on_a_free_port() {
+    let server = DrasiServerBuilder::ephemeral()
+        .with_source(MockSource::new("ephemeral_source"))
+        .start()
+        .await
+        .expect("Failed to start server");
+
+    let addr = server.addr().expect("API should be enabled");
+    assert_ne!(addr.port(), 0);
+
+    let response = reqwest::get(format!("http://{addr}/health"))
+        .await
+        .expect("Health check failed");
+    assert!(response.status().is_success());
+
+    server.stop().await.expect("Failed to stop server");
+}
@@ -18,156 +18,13 @@
 //! Dynamic creation via config is not supported.
 
 use anyhow::Result;
-use async_trait::async_trait;
-use drasi_lib::channels::dispatcher::ChangeDispatcher;
-use drasi_lib::channels::{ComponentEventSender, ComponentStatus, SubscriptionResponse};
-use drasi_lib::plugin_core::QuerySubscriber;
-use drasi_lib::plugin_core::Reaction as ReactionTrait;
-use drasi_lib::plugin_core::Source as SourceTrait;
 use drasi_lib::Query;
+use drasi_server::testing::{MockReaction, MockSource};
 use drasi_server::DrasiLib;
-use std::collections::HashMap;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 use tokio::time::{sleep, Duration};
 
-/// A mock source for testing
-struct MockSource {
-    id: String,
-    status: Arc<RwLock<ComponentStatus>>,
-}
-
-impl MockSource {
-    fn new(id: &str) -> Self {
-        Self {
-            id: id.to_string(),
-            status: Arc::new(RwLock::new(ComponentStatus::Stopped)),
-        }
-    }
-}
-
-#[async_trait]
-impl SourceTrait for MockSource {
-    fn id(&self) -> &str {
-        &self.id
-    }
-
-    fn type_name(&self) -> &str {
-        "mock"
-    }
-
-    fn properties(&self) -> HashMap<String, serde_json::Value> {
-        HashMap::new()
-    }
-
-    async fn start(&self) -> anyhow::Result<()> {
-        *self.status.write().await = ComponentStatus::Running;
-        Ok(())
-    }
-
-    async fn stop(&self) -> anyhow::Result<()> {
-        *self.status.write().await = ComponentStatus::Stopped;
-        Ok(())
-    }
-
-    async fn status(&self) -> ComponentStatus {
-        self.status.read().await.clone()
-    }
-
-    async fn subscribe(
-        &self,
-        settings: drasi_lib::config::SourceSubscriptionSettings,
-    ) -> anyhow::Result<SubscriptionResponse> {
-        use drasi_lib::channels::dispatcher::ChannelChangeDispatcher;
-        let dispatcher =
-            ChannelChangeDispatcher::<drasi_lib::channels::SourceEventWrapper>::new(100);
-        let receiver = dispatcher.create_receiver().await?;
-        Ok(SubscriptionResponse {
-            query_id: settings.query_id,
-            source_id: self.id.clone(),
-            receiver,
-            bootstrap_receiver: None,
-        })
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-
-    async fn inject_event_tx(&self, _tx: ComponentEventSender) {
-        // No-op for testing
-    }
-}
-
-/// A mock reaction for testing
-struct MockReaction {
-    id: String,
-    queries: Vec<String>,
-    status: Arc<RwLock<ComponentStatus>>,
-}
-
-impl MockReaction {
-    fn new(id: &str, queries: Vec<String>) -> Self {
-        Self {
-            id: id.to_string(),
-            queries,
-            status: Arc::new(RwLock::new(ComponentStatus::Stopped)),
-        }
-    }
-}
-
-#[async_trait]
-impl ReactionTrait for MockReaction {
-    fn id(&self) -> &str {
-        &self.id
-    }
-
-    fn type_name(&self) -> &str {
-        "log"
-    }
-
-    fn properties(&self) -> HashMap<String, serde_json::Value> {
-        HashMap::new()
-    }
-
-    fn query_ids(&self) -> Vec<String> {
-        self.queries.clone()
-    }
-
-    async fn inject_query_subscriber(&self, _query_subscriber: Arc<dyn QuerySubscriber>) {
-        // No-op for testing
-    }
-
-    async fn start(&self) -> anyhow::Result<()> {
-        *self.status.write().await = ComponentStatus::Running;
-        Ok(())
-    }
-
-    async fn stop(&self) -> anyhow::Result<()> {
-        *self.status.write().await = ComponentStatus::Stopped;
-        Ok(())
-    }
-
-    async fn status(&self) -> ComponentStatus {
-        self.status.read().await.clone()
-    }
-
-    async fn inject_event_tx(&self, _tx: ComponentEventSender) {
-        // No-op for testing
-    }
-}
-
-/// Create a mock source for testing
-fn create_mock_source(id: &str) -> MockSource {
-    MockSource::new(id)
-}
-
-/// Create a mock reaction for testing
-fn create_mock_reaction(id: &str, queries: Vec<String>) -> MockReaction {
-    MockReaction::new(id, queries)
-}
-
 /// Integration test demonstrating data flow continues after server restart
 #[tokio::test]
 async fn test_data_flow_with_server_restart() -> Result<()> {
@@ -179,9 +36,8 @@ async fn test_data_flow_with_server_restart() -> Result<()> {
     let server_id = uuid::Uuid::new_v4().to_string();
 
     // Create source and reaction instances
-    let counter_source = create_mock_source("counter-source");
-    let counter_reaction =
-        create_mock_reaction("counter-reaction", vec!["counter-query".to_string()]);
+    let counter_source = MockSource::new("counter-source");
+    let counter_reaction = MockReaction::new("counter-reaction", vec!["counter-query".to_string()]);
 
     // Build the core using the new builder API
     let query = Query::cypher("counter-query")
@@ -243,9 +99,9 @@ async fn test_multiple_sources_and_queries() -> Result<()> {
     let server_id = uuid::Uuid::new_v4().to_string();
 
     // Create source and reaction instances
-    let sensors_source = create_mock_source("sensors-source");
-    let vehicles_source = create_mock_source("vehicles-source");
-    let alert_handler = create_mock_reaction(
+    let sensors_source = MockSource::new("sensors-source");
+    let vehicles_source = MockSource::new("vehicles-source");
+    let alert_handler = MockReaction::new(
         "alert-handler",
         vec!["sensor-alerts".to_string(), "combined-view".to_string()],
     );
@@ -311,8 +167,8 @@ async fn test_component_failure_recovery() -> Result<()> {
     let server_id = uuid::Uuid::new_v4().to_string();
 
     // Create source and reaction instances
-    let test_source = create_mock_source("test-source");
-    let test_reaction = create_mock_reaction("test-reaction", vec!["test-query".to_string()]);
+    let test_source = MockSource::new("test-source");
+    let test_reaction = MockReaction::new("test-reaction", vec!["test-query".to_string()]);
 
     // Build the core using the new builder API
     let query = Query::cypher("test-query")
@@ -358,10 +214,10 @@ async fn test_concurrent_operations() -> Result<()> {
     let server_id = uuid::Uuid::new_v4().to_string();
 
     // Create source instances for concurrent test
-    let concurrent_source = create_mock_source("concurrent-source");
-    let extra_source_0 = create_mock_source("concurrent-source-0");
-    let extra_source_2 = create_mock_source("concurrent-source-2");
-    let extra_source_4 = create_mock_source("concurrent-source-4");
+    let concurrent_source = MockSource::new("concurrent-source");
+    let extra_source_0 = MockSource::new("concurrent-source-0");
+    let extra_source_2 = MockSource::new("concurrent-source-2");
+    let extra_source_4 = MockSource::new("concurrent-source-4");
 
     // Build the core using the new builder API with all sources pre-registered
     let core = Arc::new(